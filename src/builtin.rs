@@ -0,0 +1,163 @@
+use crate::error::{Error, SemanticError};
+use crate::result::Result;
+use crate::value::types::FunType;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+pub type BuiltinFun<V> = Rc<dyn Fn(Vec<V>) -> Result<V>>;
+
+pub struct Builtin<V> {
+    pub name: String,
+    pub sig: FunType,
+    pub fun: BuiltinFun<V>,
+}
+
+impl<V> Builtin<V> {
+    pub fn call(&self, args: Vec<V>) -> Result<V> {
+        (self.fun)(args)
+    }
+}
+
+impl<V> Clone for Builtin<V> {
+    fn clone(&self) -> Builtin<V> {
+        Builtin {
+            name: self.name.clone(),
+            sig: self.sig.clone(),
+            fun: self.fun.clone(),
+        }
+    }
+}
+
+impl<V> fmt::Debug for Builtin<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Builtin({} {})", self.name, self.sig)
+    }
+}
+
+pub struct Builtins<V> {
+    pub entries: BTreeMap<String, Builtin<V>>,
+}
+
+impl<V> Default for Builtins<V> {
+    fn default() -> Builtins<V> {
+        Builtins {
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+impl<V> Clone for Builtins<V> {
+    fn clone(&self) -> Builtins<V> {
+        Builtins {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<V> fmt::Debug for Builtins<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries.values()).finish()
+    }
+}
+
+impl<V> Builtins<V> {
+    pub fn new() -> Builtins<V> {
+        Builtins::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Builtin<V>> {
+        self.entries.get(name)
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.entries.keys().cloned().collect::<Vec<String>>()
+    }
+
+    pub fn signatures(&self) -> BTreeMap<String, FunType> {
+        self.entries
+            .iter()
+            .map(|(name, builtin)| (name.clone(), builtin.sig.clone()))
+            .collect::<BTreeMap<String, FunType>>()
+    }
+
+    pub fn register<F>(&mut self, name: &str, sig: &str, fun: F) -> Result<()>
+    where
+        F: Fn(Vec<V>) -> Result<V> + 'static,
+    {
+        if self.contains(name) {
+            return Err(Error::Semantic(SemanticError {
+                loc: None,
+                desc: format!("builtin {} is already registered", name),
+            }));
+        }
+
+        let builtin = Builtin {
+            name: name.into(),
+            sig: FunType::from_str(sig)?,
+            fun: Rc::new(fun),
+        };
+
+        self.entries.insert(name.into(), builtin);
+
+        Ok(())
+    }
+
+    pub fn call(&self, name: &str, args: Vec<V>) -> Result<V> {
+        match self.get(name) {
+            Some(builtin) => builtin.call(args),
+            None => Err(Error::Semantic(SemanticError {
+                loc: None,
+                desc: format!("unknown builtin {}", name),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn builtins_register() {
+        use super::Builtins;
+
+        let mut builtins: Builtins<u64> = Builtins::new();
+
+        let mut res = builtins.register("std.math.+", "(Fun UInt UInt UInt)", |args| {
+            Ok(args.iter().sum())
+        });
+
+        assert!(res.is_ok());
+        assert!(builtins.contains("std.math.+"));
+        assert_eq!(
+            builtins.signatures()["std.math.+"].to_string(),
+            "(Fun UInt UInt UInt)".to_string()
+        );
+        assert_eq!(builtins.call("std.math.+", vec![1, 2]).unwrap(), 3);
+
+        res = builtins.register("std.math.+", "(Fun UInt UInt UInt)", |_| Ok(0));
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("builtin std.math.+ is already registered"));
+
+        res = builtins.register("std.math.-", "UInt", |_| Ok(0));
+
+        assert!(res.is_err());
+
+        assert!(builtins.call("std.math.*", vec![]).is_err());
+    }
+}
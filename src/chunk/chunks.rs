@@ -64,21 +64,33 @@ impl CharChunks {
 
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Self {
+        CharChunks::from_source(s, None)
+    }
+
+    pub fn from_str_with_file(s: &str, file: &str) -> Self {
+        CharChunks::from_source(s, Some(file.to_string()))
+    }
+
+    fn from_source(s: &str, file: Option<String>) -> Self {
         let mut line = 0;
         let mut pos = 0;
+        let mut offset = 0;
 
         let chunks: Vec<CharChunk> = s
             .chars()
             .map(|content| {
                 let chunk = CharChunk {
                     loc: Loc {
-                        file: None,
+                        file: file.clone(),
                         line,
                         pos,
+                        offset,
                     },
                     content,
                 };
 
+                offset += content.len_utf8();
+
                 if content == '\n' {
                     line += 1;
                     pos = 0;
@@ -91,7 +103,7 @@ impl CharChunks {
             .collect();
 
         CharChunks {
-            files: Vec::new(),
+            files: file.into_iter().collect(),
             content: chunks,
         }
     }
@@ -272,6 +284,10 @@ impl StringChunks {
         StringChunks::from_char_chunks(CharChunks::from_str(s))
     }
 
+    pub fn from_str_with_file(s: &str, file: &str) -> Self {
+        StringChunks::from_char_chunks(CharChunks::from_str_with_file(s, file))
+    }
+
     pub fn from_string(s: String) -> Self {
         Self::from_str(&s)
     }
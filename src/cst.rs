@@ -0,0 +1,505 @@
+use crate::chunk::StringChunk;
+use crate::error::{Error, SyntacticError};
+use crate::loc::Loc;
+use crate::program::Program;
+use crate::result::Result;
+use crate::span::Span;
+use crate::token::{Token, TokenKind, Tokens};
+use crate::value::forms::form::Form;
+use std::fmt;
+use std::fs;
+use std::mem;
+use std::path::Path;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum TriviaKind {
+    Whitespace,
+    Comment,
+    DocComment,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    pub span: Span,
+}
+
+impl Trivia {
+    pub fn is_comment(&self) -> bool {
+        self.kind != TriviaKind::Whitespace
+    }
+
+    pub fn to_token(&self) -> Option<Token> {
+        let mut token = match self.kind {
+            TriviaKind::Whitespace => return None,
+            TriviaKind::Comment => Token::new_comment(),
+            TriviaKind::DocComment => Token::new_doc_comment(),
+        };
+
+        token.push(StringChunk {
+            loc: self.span.start.clone(),
+            content: self.text.clone(),
+        });
+
+        Some(token)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl fmt::Display for Trivia {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct CstToken {
+    pub leading: Vec<Trivia>,
+    pub token: Token,
+}
+
+impl CstToken {
+    pub fn span(&self) -> Option<Span> {
+        self.token.span()
+    }
+
+    pub fn to_tokens(&self) -> Tokens {
+        let mut tokens = Tokens::new();
+
+        for trivia in self.leading.iter() {
+            if let Some(token) = trivia.to_token() {
+                tokens.push(token);
+            }
+        }
+
+        tokens.push(self.token.clone());
+
+        tokens
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut s = trivia_to_string(&self.leading);
+        s.push_str(&self.token.to_string());
+
+        s
+    }
+}
+
+impl fmt::Display for CstToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct CstForm {
+    pub open: CstToken,
+    pub children: Vec<CstNode>,
+    pub close: CstToken,
+}
+
+impl CstForm {
+    pub fn head(&self) -> Option<&CstNode> {
+        self.children.first()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        let open = self.open.span()?;
+        let close = self.close.span()?;
+
+        Some(open.join(&close))
+    }
+
+    pub fn to_tokens(&self) -> Tokens {
+        let mut tokens = self.open.to_tokens();
+
+        for child in self.children.iter() {
+            for token in child.to_tokens().into_iter() {
+                tokens.push(token);
+            }
+        }
+
+        for token in self.close.to_tokens().into_iter() {
+            tokens.push(token);
+        }
+
+        tokens
+    }
+
+    pub fn to_form(&self) -> Result<Form> {
+        let mut tokens = Tokens::new();
+
+        for token in self.to_tokens().into_iter() {
+            if !tokens.is_empty() || token.kind == TokenKind::FormStart {
+                tokens.push(token);
+            }
+        }
+
+        Form::from_tokens(&tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut s = self.open.to_string();
+
+        for child in self.children.iter() {
+            s.push_str(&child.to_string());
+        }
+
+        s.push_str(&self.close.to_string());
+
+        s
+    }
+}
+
+impl fmt::Display for CstForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+pub enum CstNode {
+    Token(Box<CstToken>),
+    Form(Box<CstForm>),
+}
+
+impl CstNode {
+    pub fn leading(&self) -> &[Trivia] {
+        match self {
+            CstNode::Token(token) => &token.leading,
+            CstNode::Form(form) => &form.open.leading,
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CstNode::Token(token) => token.span(),
+            CstNode::Form(form) => form.span(),
+        }
+    }
+
+    pub fn to_tokens(&self) -> Tokens {
+        match self {
+            CstNode::Token(token) => token.to_tokens(),
+            CstNode::Form(form) => form.to_tokens(),
+        }
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        match self {
+            CstNode::Token(token) => token.to_string(),
+            CstNode::Form(form) => form.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for CstNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct Cst {
+    pub nodes: Vec<CstNode>,
+    pub trailing: Vec<Trivia>,
+}
+
+impl Cst {
+    pub fn new() -> Cst {
+        Cst::default()
+    }
+
+    pub fn forms(&self) -> Vec<&CstForm> {
+        self.nodes
+            .iter()
+            .filter_map(|node| match node {
+                CstNode::Form(form) => Some(form.as_ref()),
+                _ => None,
+            })
+            .collect::<Vec<&CstForm>>()
+    }
+
+    pub fn to_tokens(&self) -> Tokens {
+        let mut tokens = Tokens::new();
+
+        for node in self.nodes.iter() {
+            for token in node.to_tokens().into_iter() {
+                tokens.push(token);
+            }
+        }
+
+        for trivia in self.trailing.iter() {
+            if let Some(token) = trivia.to_token() {
+                tokens.push(token);
+            }
+        }
+
+        tokens
+    }
+
+    pub fn to_program(&self) -> Result<Program> {
+        Program::from_tokens(&self.to_tokens())
+    }
+
+    pub fn from_tokens(s: &str, tokens: &Tokens) -> Result<Cst> {
+        let mut stack: Vec<(CstToken, Vec<CstNode>)> = vec![];
+        let mut nodes = vec![];
+        let mut leading = vec![];
+        let mut offset = 0;
+        let mut loc = Loc::new();
+
+        for token in tokens.clone().into_iter() {
+            let span = match token.span() {
+                Some(span) => span,
+                None => continue,
+            };
+
+            if span.start.offset < offset || span.end.offset > s.len() {
+                return Err(Error::Syntactic(SyntacticError {
+                    loc: token.loc(),
+                    desc: "token out of source bounds".into(),
+                }));
+            }
+
+            if span.start.offset > offset {
+                leading.push(whitespace(s, &loc, span.start.offset));
+            }
+
+            offset = span.end.offset;
+            loc = span.end.clone();
+
+            match token.kind {
+                TokenKind::Comment | TokenKind::DocComment => {
+                    let kind = if token.kind == TokenKind::Comment {
+                        TriviaKind::Comment
+                    } else {
+                        TriviaKind::DocComment
+                    };
+
+                    leading.push(Trivia {
+                        kind,
+                        text: token.to_string(),
+                        span,
+                    });
+
+                    continue;
+                }
+                TokenKind::FormStart => {
+                    let open = CstToken {
+                        leading: mem::take(&mut leading),
+                        token,
+                    };
+
+                    stack.push((open, vec![]));
+                }
+                TokenKind::FormEnd => {
+                    let close = CstToken {
+                        leading: mem::take(&mut leading),
+                        token: token.clone(),
+                    };
+
+                    let (open, children) = match stack.pop() {
+                        Some(entry) => entry,
+                        None => {
+                            return Err(Error::Syntactic(SyntacticError {
+                                loc: token.loc(),
+                                desc: "closing a form never opened".into(),
+                            }));
+                        }
+                    };
+
+                    let node = CstNode::Form(Box::new(CstForm {
+                        open,
+                        children,
+                        close,
+                    }));
+
+                    match stack.last_mut() {
+                        Some((_, children)) => children.push(node),
+                        None => nodes.push(node),
+                    }
+                }
+                _ => {
+                    let node = CstNode::Token(Box::new(CstToken {
+                        leading: mem::take(&mut leading),
+                        token,
+                    }));
+
+                    match stack.last_mut() {
+                        Some((_, children)) => children.push(node),
+                        None => nodes.push(node),
+                    }
+                }
+            }
+        }
+
+        if let Some((open, _)) = stack.first() {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: open.token.loc(),
+                desc: "form not closed".into(),
+            }));
+        }
+
+        if offset < s.len() {
+            leading.push(whitespace(s, &loc, s.len()));
+        }
+
+        Ok(Cst {
+            nodes,
+            trailing: leading,
+        })
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Cst> {
+        let tokens = Tokens::from_str(s)?;
+
+        Cst::from_tokens(s, &tokens)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Cst> {
+        let s = fs::read_to_string(&path)?;
+        let tokens = Tokens::from_str_with_file(&s, &path.as_ref().display().to_string())?;
+
+        Cst::from_tokens(&s, &tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut s = String::new();
+
+        for node in self.nodes.iter() {
+            s.push_str(&node.to_string());
+        }
+
+        s.push_str(&trivia_to_string(&self.trailing));
+
+        s
+    }
+}
+
+impl fmt::Display for Cst {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl std::str::FromStr for Cst {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+fn whitespace(s: &str, loc: &Loc, end: usize) -> Trivia {
+    let text = s[loc.offset..end].to_string();
+    let span = Span::from_loc(loc.clone(), &text);
+
+    Trivia {
+        kind: TriviaKind::Whitespace,
+        text,
+        span,
+    }
+}
+
+fn trivia_to_string(trivia: &[Trivia]) -> String {
+    trivia
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join("")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cst_round_trip() {
+        use super::Cst;
+        use std::fs;
+
+        let paths = fs::read_dir("./examples").unwrap();
+
+        for path in paths {
+            let path = path.unwrap().path();
+            let s = fs::read_to_string(&path).unwrap();
+
+            let cst = Cst::from_str(&s).unwrap();
+
+            assert_eq!(cst.to_string(), s);
+        }
+
+        let s = "\n  # a comment\n(module m #! doc\n  (block\t(val x  1) ) )  \n# tail";
+
+        let cst = Cst::from_str(s).unwrap();
+
+        assert_eq!(cst.to_string(), s.to_string());
+        assert_eq!(cst.forms().len(), 1);
+        assert_eq!(cst.trailing.len(), 2);
+    }
+
+    #[test]
+    fn cst_trivia() {
+        use super::{Cst, CstNode, TriviaKind};
+
+        let s = "# c\n(f #! doc\n  x)";
+
+        let cst = Cst::from_str(s).unwrap();
+        let form = cst.forms()[0];
+
+        assert_eq!(
+            form.open
+                .leading
+                .iter()
+                .map(|t| t.kind)
+                .collect::<Vec<TriviaKind>>(),
+            vec![TriviaKind::Comment]
+        );
+        assert_eq!(form.open.leading[0].text, "# c\n".to_string());
+
+        let x = &form.children[1];
+        let leading = x.leading();
+
+        assert!(matches!(x, CstNode::Token(_)));
+        assert_eq!(leading.len(), 3);
+        assert_eq!(leading[1].kind, TriviaKind::DocComment);
+        assert_eq!(leading[1].span.start.offset, 7);
+        assert_eq!(leading[2].text, "  ".to_string());
+        assert_eq!(form.span().unwrap().start.offset, 4);
+        assert_eq!(form.span().unwrap().end.offset, s.len());
+
+        assert!(Cst::from_str("(f x").is_err());
+    }
+
+    #[test]
+    fn cst_to_forms() {
+        use super::Cst;
+        use crate::program::Program;
+        use crate::token::Tokens;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let s = "# header\n(module m (block\n  # a val\n  (val x 1)))\n";
+
+        let cst = Cst::from_str(s).unwrap();
+
+        assert_eq!(cst.to_tokens(), Tokens::from_str(s).unwrap());
+        assert_eq!(cst.to_program().unwrap(), Program::from_str(s).unwrap());
+
+        let form = cst.forms()[0].to_form().unwrap();
+        let module = ModuleForm::from_form(&form).unwrap();
+
+        assert_eq!(
+            module.to_string(),
+            "(module m (block (val x 1)))".to_string()
+        );
+    }
+}
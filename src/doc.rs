@@ -0,0 +1,505 @@
+use crate::program::Program;
+use crate::result::Result;
+use crate::syntax::{is_separator_char, is_type_symbol};
+use crate::token::join_docs;
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::types::Type;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Default)]
+pub enum DocFormat {
+    #[default]
+    Markdown,
+    Html,
+}
+
+impl DocFormat {
+    pub fn extension(&self) -> String {
+        match self {
+            DocFormat::Markdown => "md".into(),
+            DocFormat::Html => "html".into(),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct DocPage {
+    pub module: String,
+    pub file: String,
+    pub content: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+enum Segment {
+    Text(String),
+    Link(String, String),
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+struct DocItem {
+    name: String,
+    decl: Option<(String, Type)>,
+    doc: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Default)]
+pub struct DocGenerator {
+    pub format: DocFormat,
+}
+
+impl DocGenerator {
+    pub fn new() -> DocGenerator {
+        DocGenerator::default()
+    }
+
+    pub fn with_format(format: DocFormat) -> DocGenerator {
+        DocGenerator { format }
+    }
+
+    pub fn generate(&self, program: &Program) -> Result<Vec<DocPage>> {
+        program
+            .modules
+            .iter()
+            .map(|module| self.module_page(program, module))
+            .collect()
+    }
+
+    pub fn module_page(&self, program: &Program, module: &ModuleForm) -> Result<DocPage> {
+        let links = self.links(program, module)?;
+        let (types, values) = items(module)?;
+        let name = module.name.to_string();
+
+        let mut content = match self.format {
+            DocFormat::Markdown => format!("# Module `{}`\n", name),
+            DocFormat::Html => format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Module {}</title>\n</head>\n<body>\n<h1>Module <code>{}</code></h1>\n",
+                escape_html(&name),
+                escape_html(&name)
+            ),
+        };
+
+        if !module.type_parameters.is_empty() {
+            let params = module.type_parameters_to_string();
+
+            content.push_str(&match self.format {
+                DocFormat::Markdown => format!("\nType parameters: `{}`\n", params),
+                DocFormat::Html => format!(
+                    "<p>Type parameters: <code>{}</code></p>\n",
+                    escape_html(&params)
+                ),
+            });
+        }
+
+        if let Some(doc) = module.doc.as_ref() {
+            content.push_str(&self.doc_to_string(doc));
+        }
+
+        content.push_str(&self.section("Types", &types, &links));
+        content.push_str(&self.section("Values", &values, &links));
+
+        if self.format == DocFormat::Html {
+            content.push_str("</body>\n</html>\n");
+        }
+
+        Ok(DocPage {
+            file: format!("{}.{}", name, self.format.extension()),
+            module: name,
+            content,
+        })
+    }
+
+    fn links(&self, program: &Program, module: &ModuleForm) -> Result<BTreeMap<String, String>> {
+        let mut links = BTreeMap::new();
+
+        let (types, _) = items(module)?;
+
+        for item in types.iter() {
+            links.insert(item.name.clone(), format!("#{}", item.name));
+        }
+
+        let locals = module
+            .block_entries()
+            .iter()
+            .filter_map(|entry| match entry {
+                BlockFormEntry::TypeForm(form) => Some(form.name.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+
+        for entry in module.block_entries() {
+            let form = match entry {
+                BlockFormEntry::ImportForm(form) => form,
+                _ => continue,
+            };
+
+            let imported = match program.module(&form.module.to_string()) {
+                Some(imported) => imported,
+                None => continue,
+            };
+
+            let names = form
+                .defs
+                .iter()
+                .map(|def| def.to_string())
+                .collect::<Vec<String>>();
+
+            let qualifier = form.qualifier_name();
+            let (types, _) = items(&imported)?;
+
+            for item in types.iter() {
+                let link = format!(
+                    "{}.{}#{}",
+                    imported.name,
+                    self.format.extension(),
+                    item.name
+                );
+
+                let is_imported =
+                    !form.is_qualified_only() && (form.imports_all() || names.contains(&item.name));
+
+                if is_imported && !locals.contains(&item.name) {
+                    links
+                        .entry(item.name.clone())
+                        .or_insert_with(|| link.clone());
+                }
+
+                links.insert(format!("{}.{}", qualifier, item.name), link);
+            }
+        }
+
+        Ok(links)
+    }
+
+    fn section(&self, title: &str, items: &[DocItem], links: &BTreeMap<String, String>) -> String {
+        if items.is_empty() {
+            return String::new();
+        }
+
+        let mut s = match self.format {
+            DocFormat::Markdown => format!("\n## {}\n", title),
+            DocFormat::Html => format!("<h2>{}</h2>\n", title),
+        };
+
+        for item in items.iter() {
+            s.push_str(&match self.format {
+                DocFormat::Markdown => {
+                    format!("\n<a id=\"{}\"></a>\n### `{}`\n", item.name, item.name)
+                }
+                DocFormat::Html => format!(
+                    "<h3 id=\"{}\"><code>{}</code></h3>\n",
+                    escape_html(&item.name),
+                    escape_html(&item.name)
+                ),
+            });
+
+            if let Some((keyword, value)) = item.decl.as_ref() {
+                let mut segments = vec![Segment::Text(format!("({} {} ", keyword, item.name))];
+                segments.extend(link_segments(&value.to_string(), links));
+                segments.push(Segment::Text(")".into()));
+
+                s.push_str(&self.segments_to_string(&merge_segments(segments)));
+            }
+
+            if let Some(doc) = item.doc.as_ref() {
+                s.push_str(&self.doc_to_string(doc));
+            }
+        }
+
+        s
+    }
+
+    fn segments_to_string(&self, segments: &[Segment]) -> String {
+        match self.format {
+            DocFormat::Markdown => {
+                let s = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => format!("`{}`", text),
+                        Segment::Link(name, href) => format!("[`{}`]({})", name, href),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("");
+
+                format!("\n{}\n", s)
+            }
+            DocFormat::Html => {
+                let s = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => escape_html(text),
+                        Segment::Link(name, href) => format!(
+                            "<a href=\"{}\">{}</a>",
+                            escape_html(href),
+                            escape_html(name)
+                        ),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("");
+
+                format!("<pre><code>{}</code></pre>\n", s)
+            }
+        }
+    }
+
+    fn doc_to_string(&self, doc: &str) -> String {
+        match self.format {
+            DocFormat::Markdown => format!("\n{}\n", doc),
+            DocFormat::Html => doc
+                .split("\n\n")
+                .map(|paragraph| format!("<p>{}</p>\n", escape_html(paragraph.trim())))
+                .collect::<Vec<String>>()
+                .join(""),
+        }
+    }
+}
+
+fn items(module: &ModuleForm) -> Result<(Vec<DocItem>, Vec<DocItem>)> {
+    let exports = module
+        .exports()?
+        .iter()
+        .map(|export| export.to_string())
+        .collect::<Vec<String>>();
+
+    let mut types: Vec<DocItem> = vec![];
+    let mut values: Vec<DocItem> = vec![];
+
+    for entry in module.block_entries() {
+        let (name, decl, doc, is_type) = match entry {
+            BlockFormEntry::TypeForm(form) => (
+                form.name.to_string(),
+                Some(("type".to_string(), *form.value)),
+                form.doc,
+                true,
+            ),
+            BlockFormEntry::SigForm(form) => (
+                form.name.to_string(),
+                Some(("sig".to_string(), *form.value)),
+                form.doc,
+                false,
+            ),
+            BlockFormEntry::ValForm(form) => (form.name.to_string(), None, form.doc, false),
+            BlockFormEntry::AttrsForm(form) => {
+                let name = form.name.to_string();
+                let is_type = is_type_symbol(&name);
+                (name, None, form.doc, is_type)
+            }
+            _ => continue,
+        };
+
+        if !exports.contains(&name) {
+            continue;
+        }
+
+        let list = if is_type { &mut types } else { &mut values };
+
+        let item = match list.iter_mut().find(|item| item.name == name) {
+            Some(item) => item,
+            None => {
+                list.push(DocItem {
+                    name,
+                    ..DocItem::default()
+                });
+                list.last_mut().unwrap()
+            }
+        };
+
+        if item.decl.is_none() {
+            item.decl = decl;
+        }
+
+        item.doc = join_docs(item.doc.take(), doc);
+    }
+
+    Ok((types, values))
+}
+
+fn link_segments(s: &str, links: &BTreeMap<String, String>) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut word = String::new();
+
+    let push_word = |word: &mut String, segments: &mut Vec<Segment>| {
+        if word.is_empty() {
+            return;
+        }
+
+        match links.get(word.as_str()) {
+            Some(href) => segments.push(Segment::Link(word.clone(), href.clone())),
+            None => segments.push(Segment::Text(word.clone())),
+        }
+
+        word.clear();
+    };
+
+    for c in s.chars() {
+        if is_separator_char(c) {
+            push_word(&mut word, &mut segments);
+            segments.push(Segment::Text(c.to_string()));
+        } else {
+            word.push(c);
+        }
+    }
+
+    push_word(&mut word, &mut segments);
+
+    segments
+}
+
+fn merge_segments(segments: Vec<Segment>) -> Vec<Segment> {
+    let mut merged: Vec<Segment> = vec![];
+
+    for segment in segments.into_iter() {
+        match (merged.last_mut(), segment) {
+            (Some(Segment::Text(last)), Segment::Text(text)) => last.push_str(&text),
+            (_, segment) => merged.push(segment),
+        }
+    }
+
+    merged
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn doc_attach() {
+        use crate::program::Program;
+        use crate::value::forms::block_form::BlockFormEntry;
+
+        let s = "#! The result module.
+(module result (list T E) (block
+  #! An example on how Result and unwrap can be implemented.
+
+  #! The Result type.
+  (type Result (Enum T E))
+
+  # not a doc comment
+  (sig unwrap (Fun Result T))
+  #! Returns the ok value or panics.
+  #! Second line.
+  (val unwrap (fun res (case res (match T id) (match E panic))))))";
+
+        let program = Program::from_str(s).unwrap();
+        let module = &program.modules[0];
+
+        assert_eq!(
+            module.doc,
+            Some(
+                "The result module.\nAn example on how Result and unwrap can be implemented."
+                    .to_string()
+            )
+        );
+
+        let entries = module.block_entries();
+
+        match &entries[0] {
+            BlockFormEntry::TypeForm(form) => {
+                assert_eq!(form.doc, Some("The Result type.".to_string()));
+            }
+            _ => panic!("expected a type form"),
+        }
+
+        match &entries[1] {
+            BlockFormEntry::SigForm(form) => assert_eq!(form.doc, None),
+            _ => panic!("expected a sig form"),
+        }
+
+        match &entries[2] {
+            BlockFormEntry::ValForm(form) => {
+                assert_eq!(
+                    form.doc,
+                    Some("Returns the ok value or panics.\nSecond line.".to_string())
+                );
+            }
+            _ => panic!("expected a val form"),
+        }
+
+        let (program, errors) = Program::from_str_recovering(s);
+
+        assert!(errors.is_empty());
+        assert_eq!(program.modules[0].doc, module.doc);
+
+        let program = Program::from_file("./examples/hello_world.at").unwrap();
+
+        assert_eq!(
+            program.modules[0].doc,
+            Some("Hello World example".to_string())
+        );
+    }
+
+    #[test]
+    fn doc_generate() {
+        use super::{DocFormat, DocGenerator};
+        use crate::program::Program;
+
+        let program = Program::from_file("./examples/result.at").unwrap();
+
+        let pages = DocGenerator::new().generate(&program).unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].file, "result.md".to_string());
+        assert!(pages[0].content.starts_with("# Module `result`\n"));
+        assert!(pages[0]
+            .content
+            .contains("An example on how Result and unwrap can be implemented."));
+        assert!(pages[0]
+            .content
+            .contains("<a id=\"Result\"></a>\n### `Result`"));
+        assert!(pages[0]
+            .content
+            .contains("`(sig unwrap (Fun `[`Result`](#Result)` T))`"));
+        assert!(pages[1].content.contains("`(type StringError String)`"));
+
+        let pages = DocGenerator::with_format(DocFormat::Html)
+            .generate(&program)
+            .unwrap();
+
+        assert_eq!(pages[1].file, "main.html".to_string());
+        assert!(pages[1]
+            .content
+            .contains("<h1>Module <code>main</code></h1>"));
+        assert!(pages[1]
+            .content
+            .contains("<pre><code>(sig main (Fun IO IO))</code></pre>"));
+        assert!(pages[0].content.contains(
+            "<pre><code>(sig unwrap (Fun <a href=\"#Result\">Result</a> T))</code></pre>"
+        ));
+
+        let s = "(module result (block
+  (export (list Result Error peek))
+  (type Result (Enum UInt String))
+  (type Error String)
+  (type Hidden UInt)
+  (sig peek (Fun Hidden Result))
+  (val peek (fun h h))))
+
+(module main (block
+  (import result _ Result r)
+  #! Checks a result.
+  (sig check (Fun Result IO IO))
+  (val check (fun res io io))
+  (sig fail (Fun r.Error Error IO))
+  (val fail (fun e f io io))))";
+
+        let program = Program::from_str(s).unwrap();
+        let pages = DocGenerator::new().generate(&program).unwrap();
+
+        assert!(pages[0].content.contains("### `Result`"));
+        assert!(!pages[0].content.contains("### `Hidden`"));
+        assert!(pages[0]
+            .content
+            .contains("`(sig peek (Fun Hidden `[`Result`](#Result)`))`"));
+        assert!(pages[1].content.contains(
+            "`(sig check (Fun `[`Result`](result.md#Result)` IO IO))`\n\nChecks a result.\n"
+        ));
+        assert!(pages[1]
+            .content
+            .contains("`(sig fail (Fun `[`r.Error`](result.md#Error)` Error IO))`"));
+    }
+}
@@ -4,7 +4,7 @@ use std::error;
 use std::fmt;
 use std::io;
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 pub struct SyntacticError {
     pub loc: Option<Loc>,
     pub desc: String,
@@ -22,7 +22,7 @@ impl fmt::Display for SyntacticError {
 
 impl error::Error for SyntacticError {}
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub struct SemanticError {
     pub loc: Option<Loc>,
     pub desc: String,
@@ -40,10 +40,29 @@ impl fmt::Display for SemanticError {
 
 impl error::Error for SemanticError {}
 
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RuntimeError {
+    pub loc: Option<Loc>,
+    pub desc: String,
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(ref loc) = self.loc {
+            write!(f, "runtime error at {}: {}", loc.to_string(), self.desc)
+        } else {
+            write!(f, "runtime error: {}", self.desc)
+        }
+    }
+}
+
+impl error::Error for RuntimeError {}
+
 #[derive(Debug)]
 pub enum Error {
     Syntactic(SyntacticError),
     Semantic(SemanticError),
+    Runtime(RuntimeError),
     IO(io::Error),
 }
 
@@ -52,6 +71,7 @@ impl fmt::Display for Error {
         match self {
             Self::Syntactic(err) => err.fmt(f),
             Self::Semantic(err) => err.fmt(f),
+            Self::Runtime(err) => err.fmt(f),
             Self::IO(err) => err.fmt(f),
         }
     }
@@ -62,6 +82,7 @@ impl error::Error for Error {
         match self {
             Self::Syntactic(err) => err.source(),
             Self::Semantic(err) => err.source(),
+            Self::Runtime(err) => err.source(),
             Self::IO(err) => err.source(),
         }
     }
@@ -0,0 +1,478 @@
+use crate::cst::{Cst, CstForm, CstNode, Trivia, TriviaKind};
+use crate::result::Result;
+use crate::token::TokenKind;
+use std::path::Path;
+
+pub const DEFAULT_WIDTH: usize = 80;
+pub const DEFAULT_INDENT: usize = 2;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct Formatter {
+    pub width: usize,
+    pub indent: usize,
+}
+
+impl Default for Formatter {
+    fn default() -> Formatter {
+        Formatter {
+            width: DEFAULT_WIDTH,
+            indent: DEFAULT_INDENT,
+        }
+    }
+}
+
+impl Formatter {
+    pub fn new() -> Formatter {
+        Formatter::default()
+    }
+
+    pub fn with_width(width: usize) -> Formatter {
+        Formatter {
+            width,
+            ..Formatter::default()
+        }
+    }
+
+    pub fn format_str(&self, s: &str) -> Result<String> {
+        let cst = Cst::from_str(s)?;
+
+        Ok(self.format_cst(&cst))
+    }
+
+    pub fn format_file<P: AsRef<Path>>(&self, path: P) -> Result<String> {
+        let cst = Cst::from_file(path)?;
+
+        Ok(self.format_cst(&cst))
+    }
+
+    pub fn format_cst(&self, cst: &Cst) -> String {
+        let mut printer = Printer::new(*self);
+
+        for node in cst.nodes.iter() {
+            printer.blank(0);
+            printer.node(node, 0, true, false);
+        }
+
+        printer.comments(&cst.trailing, 0, false);
+
+        let mut out = printer.out;
+        trim_line_end(&mut out);
+
+        if !out.is_empty() {
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Layout {
+    Broken(usize),
+    Hugged(usize),
+}
+
+struct Printer {
+    config: Formatter,
+    out: String,
+    col: usize,
+    line_indent: usize,
+    force_newline: bool,
+}
+
+impl Printer {
+    fn new(config: Formatter) -> Printer {
+        Printer {
+            config,
+            out: String::new(),
+            col: 0,
+            line_indent: 0,
+            force_newline: false,
+        }
+    }
+
+    fn push(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.col += s.chars().count();
+    }
+
+    fn newline(&mut self, indent: usize) {
+        trim_line_end(&mut self.out);
+
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(indent));
+        self.col = indent;
+        self.line_indent = indent;
+        self.force_newline = false;
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.trim_end_matches(' ').ends_with('\n')
+    }
+
+    fn separate(&mut self, indent: usize, own_line: bool) {
+        if self.out.is_empty() {
+            return;
+        }
+
+        if own_line || self.force_newline {
+            if !self.at_line_start() || self.force_newline {
+                self.newline(indent);
+            }
+        } else if !self.out.ends_with('(') && !self.out.ends_with(' ') {
+            self.push(" ");
+        }
+    }
+
+    fn blank(&mut self, indent: usize) {
+        if self.out.is_empty() || self.out.trim_end().ends_with('(') {
+            return;
+        }
+
+        if !self.at_line_start() {
+            self.newline(indent);
+        }
+
+        self.newline(indent);
+    }
+
+    fn comments(&mut self, leading: &[Trivia], indent: usize, entry: bool) -> usize {
+        let mut newlines = 0;
+
+        for trivia in leading.iter() {
+            match trivia.kind {
+                TriviaKind::Whitespace => {
+                    newlines += trivia.text.matches('\n').count();
+                }
+                _ => {
+                    let text = trivia.text.trim_end();
+
+                    if newlines == 0 && !self.force_newline && !self.at_line_start() {
+                        self.push(" ");
+                    } else {
+                        if entry && newlines > 1 {
+                            self.blank(indent);
+                        }
+
+                        self.separate(indent, true);
+
+                        if self.out.is_empty() {
+                            self.push(&" ".repeat(indent));
+                            self.line_indent = indent;
+                        }
+                    }
+
+                    self.push(text);
+                    self.force_newline = true;
+                    newlines = if trivia.text.ends_with('\n') { 1 } else { 0 };
+                }
+            }
+        }
+
+        newlines
+    }
+
+    fn node(&mut self, node: &CstNode, indent: usize, own_line: bool, entry: bool) {
+        let newlines = self.comments(node.leading(), indent, entry);
+
+        if entry && newlines > 1 {
+            self.blank(indent);
+        }
+
+        self.separate(indent, own_line);
+
+        match node {
+            CstNode::Token(token) => {
+                let text = token.token.to_string();
+                self.push(&text);
+            }
+            CstNode::Form(form) => self.form(form),
+        }
+    }
+
+    fn form(&mut self, form: &CstForm) {
+        let base = self.line_indent;
+        let indent = base + self.config.indent;
+        let len = form.children.len();
+
+        if let Some(flat) = flat_form(form) {
+            if self.col + flat.chars().count() <= self.config.width {
+                self.push(&flat);
+                return;
+            }
+        }
+
+        self.push("(");
+
+        let (header, hug) = match layout(form) {
+            Layout::Broken(header) => (header.min(len), false),
+            Layout::Hugged(header) => (header.min(len), true),
+        };
+
+        for child in form.children[..header].iter() {
+            self.node(child, indent, false, false);
+        }
+
+        let rest = &form.children[header..];
+
+        if hug && rest.len() == 1 {
+            self.node(&rest[0], indent, false, false);
+        } else if is_head(form, "case") {
+            self.arms(rest, indent);
+        } else {
+            let entry = is_head(form, "block") || is_head(form, "let");
+
+            for child in rest.iter() {
+                self.node(child, indent, true, entry);
+            }
+        }
+
+        self.comments(&form.close.leading, indent, false);
+
+        if self.force_newline {
+            self.newline(base);
+        }
+
+        self.push(")");
+    }
+
+    fn arms(&mut self, arms: &[CstNode], indent: usize) {
+        let width = arms
+            .iter()
+            .filter_map(arm_parts)
+            .map(|(pattern, _)| pattern.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        for arm in arms.iter() {
+            match arm_parts(arm) {
+                Some((pattern, action))
+                    if indent + width + action.chars().count() + 9 <= self.config.width =>
+                {
+                    self.comments(arm.leading(), indent, false);
+                    self.separate(indent, true);
+                    self.push(&format!(
+                        "(match {}{} {})",
+                        pattern,
+                        " ".repeat(width - pattern.chars().count()),
+                        action
+                    ));
+                }
+                _ => self.node(arm, indent, true, false),
+            }
+        }
+    }
+}
+
+fn layout(form: &CstForm) -> Layout {
+    let len = form.children.len();
+
+    let head = match form.head() {
+        Some(CstNode::Token(token)) => token.token.to_string(),
+        _ => return Layout::Broken(1),
+    };
+
+    match head.as_str() {
+        "module" => Layout::Hugged(len.saturating_sub(1)),
+        "block" | "let" => Layout::Broken(1),
+        "case" => Layout::Broken(2),
+        "val" | "sig" | "type" | "attrs" => Layout::Hugged(2),
+        "fun" => Layout::Broken(len.saturating_sub(1)),
+        _ => match form.children.get(1) {
+            Some(CstNode::Token(_)) => Layout::Broken(2),
+            _ => Layout::Broken(1),
+        },
+    }
+}
+
+fn is_head(form: &CstForm, keyword: &str) -> bool {
+    match form.head() {
+        Some(CstNode::Token(token)) => {
+            token.token.kind == TokenKind::Keyword && token.token.to_string() == keyword
+        }
+        _ => false,
+    }
+}
+
+fn always_broken(form: &CstForm) -> bool {
+    ["module", "block", "let", "case"]
+        .iter()
+        .any(|keyword| is_head(form, keyword))
+}
+
+fn has_comments(leading: &[Trivia]) -> bool {
+    leading.iter().any(|trivia| trivia.is_comment())
+}
+
+fn flat_node(node: &CstNode) -> Option<String> {
+    if has_comments(node.leading()) {
+        return None;
+    }
+
+    match node {
+        CstNode::Token(token) => Some(token.token.to_string()),
+        CstNode::Form(form) => flat_form(form),
+    }
+}
+
+fn flat_form(form: &CstForm) -> Option<String> {
+    if always_broken(form) || has_comments(&form.close.leading) {
+        return None;
+    }
+
+    let children = form
+        .children
+        .iter()
+        .map(flat_node)
+        .collect::<Option<Vec<String>>>()?;
+
+    Some(format!("({})", children.join(" ")))
+}
+
+fn arm_parts(node: &CstNode) -> Option<(String, String)> {
+    let form = match node {
+        CstNode::Form(form) if is_head(form, "match") && form.children.len() == 3 => form,
+        _ => return None,
+    };
+
+    let pattern = flat_node(&form.children[1])?;
+    let action = flat_node(&form.children[2])?;
+
+    flat_form(form)?;
+
+    Some((pattern, action))
+}
+
+fn trim_line_end(out: &mut String) {
+    let len = out.trim_end_matches(' ').len();
+    out.truncate(len);
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn formatter_format_str() {
+        use super::Formatter;
+
+        let s = "(module m (block (import std.io _ println) (sig x UInt) (val x 1)))";
+
+        let formatter = Formatter::new();
+        let res = formatter.format_str(s).unwrap();
+
+        assert_eq!(
+            res,
+            "(module m (block\n  (import std.io _ println)\n  (sig x UInt)\n  (val x 1)))\n"
+                .to_string()
+        );
+
+        let s = "(module m (block
+            (sig f (Fun UInt UInt))
+            (val f (fun x (case x (match 0 1) (match 10 x)))))) ";
+
+        let res = formatter.format_str(s).unwrap();
+
+        assert_eq!(
+            res,
+            "(module m (block
+  (sig f (Fun UInt UInt))
+  (val f (fun x
+    (case x
+      (match 0  1)
+      (match 10 x))))))
+"
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn formatter_comments() {
+        use super::Formatter;
+
+        let s = "# header
+(module m (block #! doc for x
+
+  (val x 1) # trailing
+  # before y
+  (val y (f x
+    # inside
+    2))))";
+
+        let formatter = Formatter::new();
+        let res = formatter.format_str(s).unwrap();
+
+        assert_eq!(
+            res,
+            "# header
+(module m (block #! doc for x
+
+  (val x 1) # trailing
+  # before y
+  (val y (f x
+    # inside
+    2))))
+"
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn formatter_width() {
+        use super::Formatter;
+
+        let s = "(module m (block (val x (printf io \"{} {}\" first second))))";
+
+        let res = Formatter::with_width(30).format_str(s).unwrap();
+
+        assert_eq!(
+            res,
+            "(module m (block
+  (val x (printf io
+    \"{} {}\"
+    first
+    second))))
+"
+            .to_string()
+        );
+
+        let res = Formatter::new().format_str(s).unwrap();
+
+        assert!(res.contains("  (val x (printf io \"{} {}\" first second))))"));
+    }
+
+    #[test]
+    fn formatter_idempotent() {
+        use super::Formatter;
+        use crate::cst::Cst;
+        use crate::program::Program;
+        use std::fs;
+
+        for width in [20, 40, 80].iter() {
+            let formatter = Formatter::with_width(*width);
+
+            for path in fs::read_dir("./examples").unwrap() {
+                let s = fs::read_to_string(path.unwrap().path()).unwrap();
+
+                let once = formatter.format_str(&s).unwrap();
+                let twice = formatter.format_str(&once).unwrap();
+
+                assert_eq!(once, twice);
+                assert_eq!(
+                    Program::from_str(&once).unwrap().to_string(),
+                    Program::from_str(&s).unwrap().to_string()
+                );
+
+                let comments = |s: &str| {
+                    Cst::from_str(s)
+                        .unwrap()
+                        .to_tokens()
+                        .into_iter()
+                        .filter(|token| token.kind == crate::token::TokenKind::Comment)
+                        .map(|token| token.to_string().trim_end().to_string())
+                        .collect::<Vec<String>>()
+                };
+
+                assert_eq!(comments(&once), comments(&s));
+            }
+        }
+    }
+}
@@ -0,0 +1,1767 @@
+use crate::builtin::Builtins;
+use crate::error::{Error, RuntimeError, SemanticError};
+use crate::loader::Loader;
+use crate::loc::Loc;
+use crate::program::Program;
+use crate::result::Result;
+use crate::semantic::cast::{CastType, CastValue};
+use crate::semantic::checker::Checker;
+use crate::semantic::linearity::LinearityChecker;
+use crate::stdlib;
+use crate::syntax::{symbol_name, symbol_qualifier};
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::case_form::{
+    CaseForm, CaseFormMatchAction, CaseFormMatchCase, CaseFormVariable,
+};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::{LinearForm, LinearFormKind};
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::{MemForm, MemFormKind};
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::object::{Closure, Object, Scope};
+use crate::value::types::{SimpleType, Type};
+use crate::value::SimpleValue;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Default)]
+struct Context {
+    path: String,
+    types: BTreeMap<String, (usize, Type)>,
+    vals: BTreeMap<String, ValForm>,
+    imports: Vec<(ImportForm, usize)>,
+    names: BTreeMap<String, usize>,
+    globals: BTreeMap<String, Object>,
+    evaluating: Vec<String>,
+}
+
+type ContextKey = (String, Vec<(usize, String)>);
+
+pub const MAX_CALL_DEPTH: usize = 100_000;
+pub const CALL_STACK_SIZE: usize = 8 * 1024;
+
+pub fn stack_size(max_call_depth: usize) -> usize {
+    max_call_depth.saturating_mul(CALL_STACK_SIZE)
+}
+
+pub struct Interpreter {
+    pub loader: Loader,
+    pub builtins: Builtins<Object>,
+    pub max_call_depth: usize,
+    output: Rc<RefCell<dyn Write>>,
+    contexts: Vec<Context>,
+    keys: BTreeMap<ContextKey, usize>,
+    depth: usize,
+}
+
+impl Interpreter {
+    pub fn new() -> Result<Interpreter> {
+        Interpreter::with_output(Rc::new(RefCell::new(io::stdout())))
+    }
+
+    pub fn with_output<W: Write + 'static>(output: Rc<RefCell<W>>) -> Result<Interpreter> {
+        let mut interpreter = Interpreter {
+            loader: Loader::new(),
+            builtins: Builtins::new(),
+            max_call_depth: MAX_CALL_DEPTH,
+            output,
+            contexts: vec![],
+            keys: BTreeMap::new(),
+            depth: 0,
+        };
+
+        interpreter.register_std(stdlib::builtins())?;
+
+        Ok(interpreter)
+    }
+
+    fn register_std(&mut self, builtins: Vec<(String, String)>) -> Result<()> {
+        for (name, sig) in builtins {
+            let output = self.output.clone();
+            let key = name.clone();
+
+            let res = match key.as_str() {
+                "std.io.print" => self.builtins.register(&key, &sig, move |args| {
+                    std_print(&output, &name, args, false)
+                }),
+                "std.io.println" => self.builtins.register(&key, &sig, move |args| {
+                    std_print(&output, &name, args, true)
+                }),
+                "std.io.printf" => self
+                    .builtins
+                    .register(&key, &sig, move |args| std_printf(&output, &name, args)),
+                "std.math.+" => self.builtins.register(&key, &sig, move |args| {
+                    std_arith(&name, args, u64::checked_add)
+                }),
+                "std.math.-" => self.builtins.register(&key, &sig, move |args| {
+                    std_arith(&name, args, u64::checked_sub)
+                }),
+                "std.math.*" => self.builtins.register(&key, &sig, move |args| {
+                    std_arith(&name, args, u64::checked_mul)
+                }),
+                "std.math./" => self.builtins.register(&key, &sig, move |args| {
+                    std_arith(&name, args, u64::checked_div)
+                }),
+                "std.math.%" => self.builtins.register(&key, &sig, move |args| {
+                    std_arith(&name, args, u64::checked_rem)
+                }),
+                "std.string.length" => self
+                    .builtins
+                    .register(&key, &sig, move |args| std_length(&name, args)),
+                "std.string.concat" => self
+                    .builtins
+                    .register(&key, &sig, move |args| std_concat(&name, args)),
+                "std.string.removeAll" => self
+                    .builtins
+                    .register(&key, &sig, move |args| std_remove_all(&name, args)),
+                _ => Err(Error::Semantic(SemanticError {
+                    loc: None,
+                    desc: format!("builtin {} has no implementation", key),
+                })),
+            };
+
+            res?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check(&self) -> Result<Vec<SemanticError>> {
+        let (warnings, mut errors) = self.check_recovering();
+
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    pub fn check_recovering(&self) -> (Vec<SemanticError>, Vec<Error>) {
+        let mut resolver = match self.loader.check().and_then(|_| self.loader.resolver()) {
+            Ok(resolver) => resolver,
+            Err(err) => return (vec![], vec![err]),
+        };
+
+        let mut errors = vec![];
+
+        for module in self.loader.modules.values() {
+            let (_, resolution_errors) = resolver.resolve_recovering(module);
+
+            errors.extend(resolution_errors);
+            errors.extend(resolver.deferred_errors());
+        }
+
+        if !errors.is_empty() {
+            return (vec![], errors);
+        }
+
+        let mut warnings = vec![];
+
+        let modules = self
+            .loader
+            .modules
+            .iter()
+            .map(|(path, module)| (path, module, false));
+
+        let instances = self
+            .loader
+            .instances
+            .iter()
+            .filter(|((path, _), _)| {
+                self.loader
+                    .modules
+                    .get(path)
+                    .map_or(false, |module| !module.type_parameters.is_empty())
+            })
+            .map(|((path, _), instance)| (path, instance, true));
+
+        for (path, module, is_instance) in modules.chain(instances) {
+            let mut checker = Checker::new();
+            checker.builtins = self.builtins.signatures();
+            checker.skip_coverage = is_instance;
+
+            match self.loader.module_import_types(module) {
+                Ok(imports) => {
+                    checker.imports = imports;
+
+                    let (_, check_errors) = checker.check_as_recovering(path, module);
+
+                    errors.extend(check_errors);
+                    warnings.append(&mut checker.warnings);
+                }
+                Err(err) => errors.push(err),
+            }
+
+            let mut linearity = LinearityChecker::new();
+            linearity.droppable = checker.droppable;
+
+            if let Err(err) = linearity.check(module) {
+                errors.push(err);
+            }
+        }
+
+        (warnings, errors)
+    }
+
+    pub fn run_program(&mut self, program: &Program, module: &str) -> Result<Object> {
+        self.loader.load_program(program)?;
+
+        self.run(module)
+    }
+
+    pub fn run(&mut self, module: &str) -> Result<Object> {
+        let form = self.loader.load(module)?;
+
+        self.check()?;
+
+        let main_sig = form
+            .block_entries()
+            .into_iter()
+            .find_map(|entry| match entry {
+                BlockFormEntry::SigForm(sig) if sig.name.to_string() == "main" => Some(sig),
+                _ => None,
+            });
+
+        match main_sig {
+            Some(sig) if sig.value.to_string() == "(Fun IO IO)" => {}
+            Some(sig) => {
+                return Err(runtime_error(
+                    sig.loc(),
+                    format!("main must have type (Fun IO IO), found {}", sig.value),
+                ));
+            }
+            None => {
+                return Err(runtime_error(
+                    form.loc(),
+                    format!("module {} has no main", module),
+                ));
+            }
+        }
+
+        self.contexts.clear();
+        self.keys.clear();
+        self.depth = 0;
+
+        let idx = self.add_context((module.into(), vec![]), module, &form, BTreeMap::new())?;
+
+        let main = match self.global(idx, "main", form.loc())? {
+            Some(main) => main,
+            None => {
+                return Err(runtime_error(
+                    form.loc(),
+                    format!("module {} has no main", module),
+                ));
+            }
+        };
+
+        let res = self.apply(main, vec![Object::IO], form.loc());
+
+        self.output.borrow_mut().flush()?;
+
+        res
+    }
+
+    fn add_context(
+        &mut self,
+        key: ContextKey,
+        path: &str,
+        module: &ModuleForm,
+        types: BTreeMap<String, (usize, Type)>,
+    ) -> Result<usize> {
+        let idx = self.contexts.len();
+
+        let mut context = Context {
+            path: path.into(),
+            types,
+            ..Context::default()
+        };
+
+        let mut imports = vec![];
+
+        for entry in module.block_entries() {
+            match entry {
+                BlockFormEntry::ImportForm(form) => imports.push(*form),
+                BlockFormEntry::TypeForm(form) => {
+                    context
+                        .types
+                        .insert(form.name.to_string(), (idx, *form.value));
+                }
+                BlockFormEntry::ValForm(form) => {
+                    context.vals.insert(form.name.to_string(), *form);
+                }
+                _ => {}
+            }
+        }
+
+        self.contexts.push(context);
+        self.keys.insert(key, idx);
+
+        for import in imports {
+            let target = self.import_context(idx, &import)?;
+
+            if !import.is_qualified_only() {
+                let names = if import.imports_all() {
+                    self.loader.exports(&import.module.to_string())?
+                } else {
+                    import
+                        .defs
+                        .iter()
+                        .filter_map(|def| match def {
+                            ImportFormDef::ValueSymbol(value)
+                            | ImportFormDef::TypeSymbol(value) => Some(value.to_string()),
+                            _ => None,
+                        })
+                        .collect::<Vec<String>>()
+                };
+
+                for name in names {
+                    self.contexts[idx].names.insert(name, target);
+                }
+            }
+
+            self.contexts[idx].imports.push((import, target));
+        }
+
+        Ok(idx)
+    }
+
+    fn import_context(&mut self, idx: usize, import: &ImportForm) -> Result<usize> {
+        let path = import.module.to_string();
+        let args = import.type_arguments();
+
+        let key = (
+            path.clone(),
+            args.iter()
+                .map(|arg| (idx, arg.to_string()))
+                .collect::<Vec<(usize, String)>>(),
+        );
+
+        if let Some(target) = self.keys.get(&key) {
+            return Ok(*target);
+        }
+
+        let module = self.loader.load(&path)?;
+
+        let types = module
+            .type_parameters
+            .iter()
+            .map(|tp| tp.to_string())
+            .zip(args.into_iter().map(|arg| (idx, arg)))
+            .collect::<BTreeMap<String, (usize, Type)>>();
+
+        self.add_context(key, &path, &module, types)
+    }
+
+    fn global(&mut self, idx: usize, name: &str, loc: Option<Loc>) -> Result<Option<Object>> {
+        if let Some(value) = self.contexts[idx].globals.get(name) {
+            return Ok(Some(value.clone()));
+        }
+
+        let form = match self.contexts[idx].vals.get(name) {
+            Some(form) => form.clone(),
+            None => return Ok(None),
+        };
+
+        if self.contexts[idx].evaluating.iter().any(|n| n == name) {
+            return Err(runtime_error(
+                loc,
+                format!("recursive definition of {}", name),
+            ));
+        }
+
+        self.contexts[idx].evaluating.push(name.into());
+        let res = self.eval_val(idx, &Rc::new(Scope::new()), &form);
+        self.contexts[idx].evaluating.pop();
+
+        let value = res?;
+
+        self.contexts[idx]
+            .globals
+            .insert(name.into(), value.clone());
+
+        Ok(Some(value))
+    }
+
+    fn lookup(&mut self, idx: usize, env: &Rc<Scope>, value: &SimpleValue) -> Result<Object> {
+        let name = value.to_string();
+
+        if let SimpleValue::ValuePathSymbol(_) = value {
+            return self.lookup_path(idx, value);
+        }
+
+        if let Some(object) = env.lookup(&name) {
+            return Ok(object);
+        }
+
+        if let Some(object) = self.global(idx, &name, value.loc())? {
+            return Ok(object);
+        }
+
+        if let Some(target) = self.contexts[idx].names.get(&name).copied() {
+            if let Some(object) = self.global(target, &name, value.loc())? {
+                return Ok(object);
+            }
+        }
+
+        Err(runtime_error(
+            value.loc(),
+            format!("undefined value {}", name),
+        ))
+    }
+
+    fn lookup_path(&mut self, idx: usize, value: &SimpleValue) -> Result<Object> {
+        let name = value.to_string();
+        let qualifier = symbol_qualifier(&name);
+        let unqualified = symbol_name(&name);
+
+        let target = self.contexts[idx]
+            .imports
+            .iter()
+            .find(|(import, _)| import.qualifier_name() == qualifier)
+            .map(|(_, target)| *target);
+
+        let target = match target {
+            Some(target) => target,
+            None => {
+                return Err(runtime_error(
+                    value.loc(),
+                    format!("undefined qualifier {} in {}", qualifier, name),
+                ));
+            }
+        };
+
+        match self.global(target, &unqualified, value.loc())? {
+            Some(object) => Ok(object),
+            None => Err(runtime_error(
+                value.loc(),
+                format!("undefined value {}", name),
+            )),
+        }
+    }
+
+    fn lookup_type(&self, idx: usize, name: &str) -> Option<(usize, Type)> {
+        let context = &self.contexts[idx];
+
+        if name.contains('.') {
+            let qualifier = symbol_qualifier(name);
+
+            let target = context
+                .imports
+                .iter()
+                .find(|(import, _)| import.qualifier_name() == qualifier)
+                .map(|(_, target)| *target)?;
+
+            return self.contexts[target].types.get(&symbol_name(name)).cloned();
+        }
+
+        if let Some(entry) = context.types.get(name) {
+            return Some(entry.clone());
+        }
+
+        let target = context.names.get(name)?;
+
+        self.contexts[*target].types.get(name).cloned()
+    }
+
+    fn expand(&self, idx: usize, t: &Type) -> (usize, Type) {
+        let mut idx = idx;
+        let mut t = t.clone();
+        let mut seen = vec![];
+
+        loop {
+            let name = match t {
+                Type::Simple(SimpleType::Symbol(ref value))
+                | Type::Simple(SimpleType::PathSymbol(ref value)) => value.to_string(),
+                _ => return (idx, t),
+            };
+
+            if seen.contains(&(idx, name.clone())) {
+                return (idx, t);
+            }
+
+            seen.push((idx, name.clone()));
+
+            match self.lookup_type(idx, &name) {
+                Some((next_idx, next)) => {
+                    idx = next_idx;
+                    t = next;
+                }
+                None => return (idx, t),
+            }
+        }
+    }
+
+    fn has_type(&self, idx: usize, value: &Object, t: &Type) -> bool {
+        let (idx, t) = self.expand(idx, t);
+
+        match (&t, value) {
+            (Type::Simple(simple_type), _) => match simple_type {
+                SimpleType::Ignore(_) => true,
+                SimpleType::Empty(_) => matches!(value, Object::Empty),
+                SimpleType::Atomic(_) => matches!(value, Object::Atomic(_)),
+                SimpleType::UInt(_) => matches!(value, Object::UInt(_)),
+                SimpleType::Size(_) => matches!(value, Object::Size(_)),
+                SimpleType::Pointer(_) => matches!(value, Object::Pointer(_)),
+                SimpleType::Int(_) => matches!(value, Object::Int(_)),
+                SimpleType::Float(_) => matches!(value, Object::Float(_)),
+                SimpleType::Char(_) => matches!(value, Object::Char(_)),
+                SimpleType::String(_) | SimpleType::Path(_) => {
+                    matches!(value, Object::String(_))
+                }
+                SimpleType::Ref(_) => matches!(value, Object::Ref(_)),
+                SimpleType::IO(_) => matches!(value, Object::IO),
+                SimpleType::Ctx(_) => matches!(value, Object::Ctx),
+                SimpleType::Builtin(_) => matches!(value, Object::Builtin(_)),
+                _ => false,
+            },
+            (Type::Enum(enum_type), _) => enum_type
+                .elements
+                .iter()
+                .any(|t| self.has_type(idx, value, t)),
+            (Type::Pair(pair_type), Object::Pair(first, second)) => {
+                self.has_type(idx, first, &pair_type.first)
+                    && self.has_type(idx, second, &pair_type.second)
+            }
+            (Type::List(list_type), Object::List(values)) => {
+                self.has_elements(idx, values, &list_type.elements)
+            }
+            (Type::Arr(arr_type), Object::Arr(values)) => {
+                self.has_elements(idx, values, &arr_type.elements)
+            }
+            (Type::Vec(vec_type), Object::Vec(values)) => {
+                self.has_elements(idx, values, &vec_type.elements)
+            }
+            (Type::Map(map_type), Object::Map(entries)) => entries.iter().all(|(key, value)| {
+                map_type.entries.iter().any(|entry| {
+                    self.has_type(idx, key, &entry.first)
+                        && self.has_type(idx, value, &entry.second)
+                })
+            }),
+            (Type::Fun(fun_type), Object::Fun(closure)) => {
+                closure.form.parameters.len() == fun_type.parameters.len()
+            }
+            (Type::Fun(_), Object::Builtin(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn has_elements(&self, idx: usize, values: &[Object], elements: &[Type]) -> bool {
+        if elements.len() == 1 {
+            values
+                .iter()
+                .all(|value| self.has_type(idx, value, &elements[0]))
+        } else {
+            elements.len() == values.len()
+                && values
+                    .iter()
+                    .zip(elements.iter())
+                    .all(|(value, t)| self.has_type(idx, value, t))
+        }
+    }
+
+    fn apply(&mut self, fun: Object, args: Vec<Object>, loc: Option<Loc>) -> Result<Object> {
+        match fun {
+            Object::Fun(closure) => {
+                if closure.form.parameters.len() != args.len() {
+                    return Err(runtime_error(
+                        loc,
+                        format!(
+                            "expected {} arguments for {}, found {}",
+                            closure.form.parameters.len(),
+                            closure.form,
+                            args.len()
+                        ),
+                    ));
+                }
+
+                let mut scope = Scope::new();
+                scope.parent = Some(closure.env.clone());
+
+                for (param, arg) in closure.form.parameters.iter().zip(args) {
+                    bind_parameter(&mut scope, param, arg)?;
+                }
+
+                if self.depth >= self.max_call_depth {
+                    return Err(runtime_error(
+                        loc,
+                        format!("call depth limit of {} exceeded", self.max_call_depth),
+                    ));
+                }
+
+                self.depth += 1;
+
+                let res = self.eval_body(closure.ctx, &Rc::new(scope), &closure.form.body);
+
+                self.depth -= 1;
+
+                res
+            }
+            Object::Builtin(name) => self
+                .builtins
+                .call(&name, args)
+                .map_err(|err| locate(err, loc)),
+            value => Err(runtime_error(
+                loc,
+                format!("cannot apply {}, expected a function", value),
+            )),
+        }
+    }
+
+    fn eval_simple(&mut self, idx: usize, env: &Rc<Scope>, value: &SimpleValue) -> Result<Object> {
+        match value {
+            SimpleValue::Ignore(_) | SimpleValue::Empty(_) => Ok(Object::Empty),
+            SimpleValue::Panic(_) => Err(runtime_error(value.loc(), "panic".into())),
+            SimpleValue::Atomic(_) => Object::from_simple_value(value),
+            SimpleValue::ValueSymbol(_) | SimpleValue::ValuePathSymbol(_) => {
+                self.lookup(idx, env, value)
+            }
+            SimpleValue::ValueKeyword(_) => Err(runtime_error(
+                value.loc(),
+                format!("unexpected keyword {}", value),
+            )),
+            SimpleValue::TypeKeyword(_)
+            | SimpleValue::TypeSymbol(_)
+            | SimpleValue::TypePathSymbol(_) => Err(runtime_error(
+                value.loc(),
+                format!("cannot evaluate type {}", value),
+            )),
+        }
+    }
+
+    fn eval_val(&mut self, idx: usize, env: &Rc<Scope>, form: &ValForm) -> Result<Object> {
+        match &form.value {
+            ValFormValue::Atomic(SimpleValue::ValueKeyword(_)) => Ok(Object::Atomic(format!(
+                "{}.{}",
+                self.contexts[idx].path, form.name
+            ))),
+            ValFormValue::Builtin(_) => Ok(Object::Builtin(format!(
+                "{}.{}",
+                self.contexts[idx].path, form.name
+            ))),
+            ValFormValue::Empty(value)
+            | ValFormValue::Panic(value)
+            | ValFormValue::Atomic(value)
+            | ValFormValue::ValueSymbol(value) => self.eval_simple(idx, env, value),
+            ValFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            ValFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            ValFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            ValFormValue::AppForm(form) => self.eval_app(idx, env, form),
+            ValFormValue::MemForm(form) => self.eval_mem(idx, env, form),
+            ValFormValue::LinearForm(form) => self.eval_linear(idx, env, form),
+            ValFormValue::CastForm(form) => self.eval_cast(idx, env, form),
+            ValFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+        }
+    }
+
+    fn eval_fun(&mut self, idx: usize, env: &Rc<Scope>, form: &FunForm) -> Object {
+        Object::Fun(Rc::new(Closure {
+            ctx: idx,
+            form: form.clone(),
+            env: env.clone(),
+        }))
+    }
+
+    fn eval_body(&mut self, idx: usize, env: &Rc<Scope>, body: &FunFormBody) -> Result<Object> {
+        match body {
+            FunFormBody::Empty(value)
+            | FunFormBody::Panic(value)
+            | FunFormBody::Atomic(value)
+            | FunFormBody::ValueSymbol(value)
+            | FunFormBody::ValuePathSymbol(value) => self.eval_simple(idx, env, value),
+            FunFormBody::MapForm(form) => self.eval_map(idx, env, form),
+            FunFormBody::VecForm(form) => self.eval_vec(idx, env, form),
+            FunFormBody::ArrForm(form) => self.eval_arr(idx, env, form),
+            FunFormBody::ListForm(form) => self.eval_list(idx, env, form),
+            FunFormBody::PairForm(form) => self.eval_pair(idx, env, form),
+            FunFormBody::AppForm(form) => self.eval_app(idx, env, form),
+            FunFormBody::MemForm(form) => self.eval_mem(idx, env, form),
+            FunFormBody::LinearForm(form) => self.eval_linear(idx, env, form),
+            FunFormBody::CastForm(form) => self.eval_cast(idx, env, form),
+            FunFormBody::LetForm(form) => self.eval_let(idx, env, form),
+            FunFormBody::CaseForm(form) => self.eval_case(idx, env, form),
+            FunFormBody::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+        }
+    }
+
+    fn eval_app_value(
+        &mut self,
+        idx: usize,
+        env: &Rc<Scope>,
+        value: &AppFormValue,
+    ) -> Result<Object> {
+        match value {
+            AppFormValue::Ignore(value)
+            | AppFormValue::Empty(value)
+            | AppFormValue::Panic(value)
+            | AppFormValue::Atomic(value)
+            | AppFormValue::ValueSymbol(value)
+            | AppFormValue::ValuePathSymbol(value) => self.eval_simple(idx, env, value),
+            AppFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            AppFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            AppFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            AppFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+            AppFormValue::AppForm(form) => self.eval_app(idx, env, form),
+            AppFormValue::MemForm(form) => self.eval_mem(idx, env, form),
+            AppFormValue::LinearForm(form) => self.eval_linear(idx, env, form),
+            AppFormValue::CastForm(form) => self.eval_cast(idx, env, form),
+        }
+    }
+
+    fn eval_app(&mut self, idx: usize, env: &Rc<Scope>, form: &AppForm) -> Result<Object> {
+        let fun = self.eval_simple(idx, env, &form.name)?;
+
+        let mut args = vec![];
+
+        for variable in form.variables.iter() {
+            args.push(self.eval_app_value(idx, env, variable)?);
+        }
+
+        self.apply(fun, args, form.loc())
+    }
+
+    fn eval_linear(&mut self, idx: usize, env: &Rc<Scope>, form: &LinearForm) -> Result<Object> {
+        let value = self.eval_app_value(idx, env, &form.value)?;
+
+        match form.kind {
+            LinearFormKind::Dup => Ok(Object::Pair(Box::new(value.clone()), Box::new(value))),
+            LinearFormKind::Drop => Ok(Object::Empty),
+        }
+    }
+
+    fn eval_mem(&mut self, idx: usize, env: &Rc<Scope>, form: &MemForm) -> Result<Object> {
+        match form.kind {
+            MemFormKind::Ref => {
+                let value = self.eval_app_value(idx, env, &form.values[0])?;
+                Ok(Object::new_ref(value))
+            }
+            MemFormKind::Deref => match self.eval_app_value(idx, env, &form.values[0])? {
+                Object::Ref(value) => Ok(value.borrow().clone()),
+                value => Err(runtime_error(
+                    form.loc(),
+                    format!("cannot deref {}, expected a Ref", value),
+                )),
+            },
+            _ => Err(runtime_error(
+                form.loc(),
+                format!("{} is not supported at runtime", form.kind),
+            )),
+        }
+    }
+
+    fn eval_cast(&mut self, idx: usize, env: &Rc<Scope>, form: &CastForm) -> Result<Object> {
+        let value = self.eval_app_value(idx, env, &form.value)?;
+
+        let (_, target) = self.expand(idx, &form.target);
+
+        let to = match CastType::from_type(&target) {
+            None => {
+                return Err(runtime_error(
+                    form.loc(),
+                    format!("cannot cast {} to {}", value, form.target),
+                ));
+            }
+            Some(to) => to,
+        };
+
+        let from = match value {
+            Object::UInt(n) => CastValue::UInt(n),
+            Object::Int(n) => CastValue::Int(n),
+            Object::Float(n) => CastValue::Float(n),
+            Object::Size(n) => CastValue::Size(n),
+            Object::Pointer(n) => CastValue::Pointer(n),
+            Object::Char(c) => CastValue::Char(c),
+            value => {
+                return Err(runtime_error(
+                    form.loc(),
+                    format!("cannot cast {} to {}", value, form.target),
+                ));
+            }
+        };
+
+        let object = match from.cast(to).map_err(|err| locate(err, form.loc()))? {
+            CastValue::UInt(n) => Object::UInt(n),
+            CastValue::Int(n) => Object::Int(n),
+            CastValue::Float(n) => Object::Float(n),
+            CastValue::Size(n) => Object::Size(n),
+            CastValue::Pointer(n) => Object::Pointer(n),
+            CastValue::Char(c) => Object::Char(c),
+        };
+
+        Ok(object)
+    }
+
+    fn eval_let(&mut self, idx: usize, env: &Rc<Scope>, form: &LetForm) -> Result<Object> {
+        let mut scope = Scope::new();
+        scope.parent = Some(env.clone());
+
+        for entry in form.entries.iter() {
+            match entry {
+                LetFormEntry::ValForm(val) => {
+                    let value = self.eval_val(idx, &Rc::new(scope.clone()), val)?;
+                    scope.values.insert(val.name.to_string(), value);
+                }
+                LetFormEntry::ImportForm(import) => {
+                    return Err(runtime_error(
+                        import.loc(),
+                        "imports in let forms are not supported at runtime".into(),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        self.eval_app_value(idx, &Rc::new(scope), &form.value)
+    }
+
+    fn eval_case(&mut self, idx: usize, env: &Rc<Scope>, form: &CaseForm) -> Result<Object> {
+        let value = match &form.variable {
+            CaseFormVariable::Empty(value)
+            | CaseFormVariable::Atomic(value)
+            | CaseFormVariable::ValueSymbol(value) => self.eval_simple(idx, env, value)?,
+            CaseFormVariable::AppForm(form) => self.eval_app(idx, env, form)?,
+            CaseFormVariable::MemForm(form) => self.eval_mem(idx, env, form)?,
+            CaseFormVariable::LinearForm(form) => self.eval_linear(idx, env, form)?,
+            CaseFormVariable::CastForm(form) => self.eval_cast(idx, env, form)?,
+            CaseFormVariable::LetForm(form) => self.eval_let(idx, env, form)?,
+            CaseFormVariable::CaseForm(form) => self.eval_case(idx, env, form)?,
+        };
+
+        for case_match in form.matches.iter() {
+            let matched = match &case_match.case {
+                CaseFormMatchCase::ValueKeyword(_) => true,
+                CaseFormMatchCase::Empty(_) => value == Object::Empty,
+                CaseFormMatchCase::Atomic(case) => Object::from_simple_value(case)? == value,
+                CaseFormMatchCase::ValueSymbol(case) | CaseFormMatchCase::ValuePathSymbol(case) => {
+                    self.lookup(idx, env, case)? == value
+                }
+                CaseFormMatchCase::TypeKeyword(case)
+                | CaseFormMatchCase::TypeSymbol(case)
+                | CaseFormMatchCase::TypePathSymbol(case) => {
+                    let t = Type::from_simple_value(case)?;
+                    self.has_type(idx, &value, &t)
+                }
+            };
+
+            if !matched {
+                continue;
+            }
+
+            return match &case_match.action {
+                CaseFormMatchAction::ValueKeyword(action) if action.to_string() == "id" => {
+                    Ok(value)
+                }
+                CaseFormMatchAction::Ignore(action)
+                | CaseFormMatchAction::Empty(action)
+                | CaseFormMatchAction::Panic(action)
+                | CaseFormMatchAction::Atomic(action)
+                | CaseFormMatchAction::ValueKeyword(action)
+                | CaseFormMatchAction::ValueSymbol(action)
+                | CaseFormMatchAction::ValuePathSymbol(action) => {
+                    self.eval_simple(idx, env, action)
+                }
+                CaseFormMatchAction::PairForm(form) => self.eval_pair(idx, env, form),
+                CaseFormMatchAction::FunForm(form) => {
+                    let fun = self.eval_fun(idx, env, form);
+                    self.apply(fun, vec![value], form.loc())
+                }
+                CaseFormMatchAction::LetForm(form) => self.eval_let(idx, env, form),
+            };
+        }
+
+        Err(runtime_error(
+            form.loc(),
+            format!("no match for value {}", value),
+        ))
+    }
+
+    fn eval_pair_value(
+        &mut self,
+        idx: usize,
+        env: &Rc<Scope>,
+        value: &PairFormValue,
+    ) -> Result<Object> {
+        match value {
+            PairFormValue::Ignore(value)
+            | PairFormValue::Empty(value)
+            | PairFormValue::Panic(value)
+            | PairFormValue::Atomic(value)
+            | PairFormValue::ValueKeyword(value)
+            | PairFormValue::TypeKeyword(value)
+            | PairFormValue::ValueSymbol(value)
+            | PairFormValue::TypeSymbol(value)
+            | PairFormValue::ValuePathSymbol(value)
+            | PairFormValue::TypePathSymbol(value) => self.eval_simple(idx, env, value),
+            PairFormValue::Type(form) => Err(runtime_error(
+                form.loc(),
+                format!("cannot evaluate type {}", form),
+            )),
+            PairFormValue::MapForm(form) => self.eval_map(idx, env, form),
+            PairFormValue::VecForm(form) => self.eval_vec(idx, env, form),
+            PairFormValue::ArrForm(form) => self.eval_arr(idx, env, form),
+            PairFormValue::ListForm(form) => self.eval_list(idx, env, form),
+            PairFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            PairFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            PairFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+            PairFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            PairFormValue::AppForm(form) => self.eval_app(idx, env, form),
+        }
+    }
+
+    fn eval_pair(&mut self, idx: usize, env: &Rc<Scope>, form: &PairForm) -> Result<Object> {
+        let first = self.eval_pair_value(idx, env, &form.first)?;
+        let second = self.eval_pair_value(idx, env, &form.second)?;
+
+        Ok(Object::Pair(Box::new(first), Box::new(second)))
+    }
+
+    fn eval_list_value(
+        &mut self,
+        idx: usize,
+        env: &Rc<Scope>,
+        value: &ListFormValue,
+    ) -> Result<Object> {
+        match value {
+            ListFormValue::Ignore(value)
+            | ListFormValue::Empty(value)
+            | ListFormValue::Panic(value)
+            | ListFormValue::Atomic(value)
+            | ListFormValue::ValueKeyword(value)
+            | ListFormValue::TypeKeyword(value)
+            | ListFormValue::ValueSymbol(value)
+            | ListFormValue::TypeSymbol(value)
+            | ListFormValue::ValuePathSymbol(value)
+            | ListFormValue::TypePathSymbol(value) => self.eval_simple(idx, env, value),
+            ListFormValue::Type(form) => Err(runtime_error(
+                form.loc(),
+                format!("cannot evaluate type {}", form),
+            )),
+            ListFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            ListFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+            ListFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            ListFormValue::AppForm(form) => self.eval_app(idx, env, form),
+            ListFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            ListFormValue::ArrForm(form) => self.eval_arr(idx, env, form),
+            ListFormValue::VecForm(form) => self.eval_vec(idx, env, form),
+            ListFormValue::MapForm(form) => self.eval_map(idx, env, form),
+            ListFormValue::ListForm(form) => self.eval_list(idx, env, form),
+        }
+    }
+
+    fn eval_list(&mut self, idx: usize, env: &Rc<Scope>, form: &ListForm) -> Result<Object> {
+        let mut values = vec![];
+
+        for value in form.values.iter() {
+            values.push(self.eval_list_value(idx, env, value)?);
+        }
+
+        Ok(Object::List(values))
+    }
+
+    fn eval_arr_value(
+        &mut self,
+        idx: usize,
+        env: &Rc<Scope>,
+        value: &ArrFormValue,
+    ) -> Result<Object> {
+        match value {
+            ArrFormValue::Ignore(value)
+            | ArrFormValue::Empty(value)
+            | ArrFormValue::Panic(value)
+            | ArrFormValue::Atomic(value)
+            | ArrFormValue::ValueKeyword(value)
+            | ArrFormValue::TypeKeyword(value)
+            | ArrFormValue::ValueSymbol(value)
+            | ArrFormValue::TypeSymbol(value)
+            | ArrFormValue::ValuePathSymbol(value)
+            | ArrFormValue::TypePathSymbol(value) => self.eval_simple(idx, env, value),
+            ArrFormValue::Type(form) => Err(runtime_error(
+                form.loc(),
+                format!("cannot evaluate type {}", form),
+            )),
+            ArrFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            ArrFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+            ArrFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            ArrFormValue::AppForm(form) => self.eval_app(idx, env, form),
+            ArrFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            ArrFormValue::ListForm(form) => self.eval_list(idx, env, form),
+            ArrFormValue::VecForm(form) => self.eval_vec(idx, env, form),
+            ArrFormValue::MapForm(form) => self.eval_map(idx, env, form),
+            ArrFormValue::ArrForm(form) => self.eval_arr(idx, env, form),
+        }
+    }
+
+    fn eval_arr(&mut self, idx: usize, env: &Rc<Scope>, form: &ArrForm) -> Result<Object> {
+        let mut values = vec![];
+
+        for value in form.values.iter() {
+            values.push(self.eval_arr_value(idx, env, value)?);
+        }
+
+        Ok(Object::Arr(values))
+    }
+
+    fn eval_vec_value(
+        &mut self,
+        idx: usize,
+        env: &Rc<Scope>,
+        value: &VecFormValue,
+    ) -> Result<Object> {
+        match value {
+            VecFormValue::Ignore(value)
+            | VecFormValue::Empty(value)
+            | VecFormValue::Panic(value)
+            | VecFormValue::Atomic(value)
+            | VecFormValue::ValueKeyword(value)
+            | VecFormValue::TypeKeyword(value)
+            | VecFormValue::ValueSymbol(value)
+            | VecFormValue::TypeSymbol(value)
+            | VecFormValue::ValuePathSymbol(value)
+            | VecFormValue::TypePathSymbol(value) => self.eval_simple(idx, env, value),
+            VecFormValue::Type(form) => Err(runtime_error(
+                form.loc(),
+                format!("cannot evaluate type {}", form),
+            )),
+            VecFormValue::FunForm(form) => Ok(self.eval_fun(idx, env, form)),
+            VecFormValue::CaseForm(form) => self.eval_case(idx, env, form),
+            VecFormValue::LetForm(form) => self.eval_let(idx, env, form),
+            VecFormValue::AppForm(form) => self.eval_app(idx, env, form),
+            VecFormValue::PairForm(form) => self.eval_pair(idx, env, form),
+            VecFormValue::ListForm(form) => self.eval_list(idx, env, form),
+            VecFormValue::ArrForm(form) => self.eval_arr(idx, env, form),
+            VecFormValue::MapForm(form) => self.eval_map(idx, env, form),
+            VecFormValue::VecForm(form) => self.eval_vec(idx, env, form),
+        }
+    }
+
+    fn eval_vec(&mut self, idx: usize, env: &Rc<Scope>, form: &VecForm) -> Result<Object> {
+        let mut values = vec![];
+
+        for value in form.values.iter() {
+            values.push(self.eval_vec_value(idx, env, value)?);
+        }
+
+        Ok(Object::Vec(values))
+    }
+
+    fn eval_map(&mut self, idx: usize, env: &Rc<Scope>, form: &MapForm) -> Result<Object> {
+        let mut entries = vec![];
+
+        for entry in form.entries.iter() {
+            if let MapFormEntry::PairForm(pair) = entry {
+                let key = self.eval_pair_value(idx, env, &pair.first)?;
+                let value = self.eval_pair_value(idx, env, &pair.second)?;
+
+                entries.push((key, value));
+            }
+        }
+
+        Ok(Object::Map(entries))
+    }
+}
+
+fn bind_parameter(scope: &mut Scope, param: &FunFormParameter, value: Object) -> Result<()> {
+    match (param, value) {
+        (FunFormParameter::Empty(_), _) => {}
+        (FunFormParameter::ValueSymbol(name), value) => {
+            scope.values.insert(name.to_string(), value);
+        }
+        (FunFormParameter::PairForm(form), value) => bind_pair(scope, form, value)?,
+        (FunFormParameter::ListForm(form), Object::List(values))
+            if form.values.len() == values.len() =>
+        {
+            for (pattern, value) in form.values.iter().zip(values) {
+                match pattern {
+                    ListFormValue::ValueSymbol(name) => {
+                        scope.values.insert(name.to_string(), value);
+                    }
+                    ListFormValue::PairForm(form) => bind_pair(scope, form, value)?,
+                    _ => {}
+                }
+            }
+        }
+        (FunFormParameter::ArrForm(form), Object::Arr(values))
+            if form.values.len() == values.len() =>
+        {
+            for (pattern, value) in form.values.iter().zip(values) {
+                match pattern {
+                    ArrFormValue::ValueSymbol(name) => {
+                        scope.values.insert(name.to_string(), value);
+                    }
+                    ArrFormValue::PairForm(form) => bind_pair(scope, form, value)?,
+                    _ => {}
+                }
+            }
+        }
+        (FunFormParameter::VecForm(form), Object::Vec(values))
+            if form.values.len() == values.len() =>
+        {
+            for (pattern, value) in form.values.iter().zip(values) {
+                match pattern {
+                    VecFormValue::ValueSymbol(name) => {
+                        scope.values.insert(name.to_string(), value);
+                    }
+                    VecFormValue::PairForm(form) => bind_pair(scope, form, value)?,
+                    _ => {}
+                }
+            }
+        }
+        (FunFormParameter::MapForm(form), Object::Map(entries))
+            if form.entries.len() == entries.len() =>
+        {
+            for (pattern, (key, value)) in form.entries.iter().zip(entries) {
+                if let MapFormEntry::PairForm(form) = pattern {
+                    bind_pair(scope, form, Object::Pair(Box::new(key), Box::new(value)))?;
+                }
+            }
+        }
+        (param, value) => {
+            return Err(runtime_error(
+                param.loc(),
+                format!("cannot destructure {} with {}", value, param),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn bind_pair(scope: &mut Scope, form: &PairForm, value: Object) -> Result<()> {
+    let (first, second) = match value {
+        Object::Pair(first, second) => (*first, *second),
+        value => {
+            return Err(runtime_error(
+                form.loc(),
+                format!("cannot destructure {} with {}", value, form),
+            ));
+        }
+    };
+
+    for (pattern, value) in [(&form.first, first), (&form.second, second)] {
+        match pattern {
+            PairFormValue::ValueSymbol(name) => {
+                scope.values.insert(name.to_string(), value);
+            }
+            PairFormValue::PairForm(form) => bind_pair(scope, form, value)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn runtime_error(loc: Option<Loc>, desc: String) -> Error {
+    Error::Runtime(RuntimeError { loc, desc })
+}
+
+fn locate(err: Error, loc: Option<Loc>) -> Error {
+    match err {
+        Error::Runtime(RuntimeError { loc: None, desc }) => runtime_error(loc, desc),
+        Error::Semantic(SemanticError { loc: None, desc }) => {
+            Error::Semantic(SemanticError { loc, desc })
+        }
+        err => err,
+    }
+}
+
+fn builtin_args(name: &str, args: Vec<Object>, len: usize) -> Result<Vec<Object>> {
+    if args.len() != len {
+        return Err(runtime_error(
+            None,
+            format!(
+                "builtin {} expected {} arguments, found {}",
+                name,
+                len,
+                args.len()
+            ),
+        ));
+    }
+
+    Ok(args)
+}
+
+fn builtin_mismatch(name: &str, expected: &str, found: &Object) -> Error {
+    runtime_error(
+        None,
+        format!("builtin {} expected {}, found {}", name, expected, found),
+    )
+}
+
+fn printf_args(value: Object) -> Vec<Object> {
+    match value {
+        Object::Empty => vec![],
+        Object::Pair(first, second) => {
+            let mut args = printf_args(*first);
+            args.extend(printf_args(*second));
+            args
+        }
+        value => vec![value],
+    }
+}
+
+fn std_print(
+    output: &Rc<RefCell<dyn Write>>,
+    name: &str,
+    args: Vec<Object>,
+    newline: bool,
+) -> Result<Object> {
+    let args = builtin_args(name, args, 2)?;
+
+    if args[0] != Object::IO {
+        return Err(builtin_mismatch(name, "an IO token", &args[0]));
+    }
+
+    let mut output = output.borrow_mut();
+    write!(output, "{}", args[1].to_output())?;
+
+    if newline {
+        writeln!(output)?;
+    }
+
+    Ok(Object::IO)
+}
+
+fn std_printf(output: &Rc<RefCell<dyn Write>>, name: &str, args: Vec<Object>) -> Result<Object> {
+    let mut args = builtin_args(name, args, 3)?;
+
+    if args[0] != Object::IO {
+        return Err(builtin_mismatch(name, "an IO token", &args[0]));
+    }
+
+    let values = printf_args(args.remove(2));
+
+    let format = match &args[1] {
+        Object::String(format) => format,
+        value => return Err(builtin_mismatch(name, "a String", value)),
+    };
+
+    let pieces = format.split("{}").collect::<Vec<&str>>();
+
+    if pieces.len() - 1 != values.len() {
+        return Err(runtime_error(
+            None,
+            format!(
+                "builtin {} expected {} format arguments, found {}",
+                name,
+                pieces.len() - 1,
+                values.len()
+            ),
+        ));
+    }
+
+    let mut s = String::new();
+
+    for (idx, piece) in pieces.iter().enumerate() {
+        s.push_str(piece);
+
+        if let Some(value) = values.get(idx) {
+            s.push_str(&value.to_output());
+        }
+    }
+
+    write!(output.borrow_mut(), "{}", s)?;
+
+    Ok(Object::IO)
+}
+
+fn std_arith(name: &str, args: Vec<Object>, op: fn(u64, u64) -> Option<u64>) -> Result<Object> {
+    let args = builtin_args(name, args, 2)?;
+
+    match (&args[0], &args[1]) {
+        (Object::UInt(_), Object::UInt(0)) if name.ends_with('/') || name.ends_with('%') => Err(
+            runtime_error(None, format!("builtin {}: division by zero", name)),
+        ),
+        (Object::UInt(a), Object::UInt(b)) => op(*a, *b).map(Object::UInt).ok_or_else(|| {
+            runtime_error(
+                None,
+                format!("builtin {}: overflow with {} and {}", name, a, b),
+            )
+        }),
+        (Object::UInt(_), value) | (value, _) => Err(builtin_mismatch(name, "a UInt", value)),
+    }
+}
+
+fn std_length(name: &str, args: Vec<Object>) -> Result<Object> {
+    let args = builtin_args(name, args, 1)?;
+
+    match &args[0] {
+        Object::String(s) => Ok(Object::Size(s.chars().count() as u64)),
+        value => Err(builtin_mismatch(name, "a String", value)),
+    }
+}
+
+fn std_concat(name: &str, args: Vec<Object>) -> Result<Object> {
+    let args = builtin_args(name, args, 2)?;
+
+    match (&args[0], &args[1]) {
+        (Object::String(a), Object::String(b)) => Ok(Object::String(format!("{}{}", a, b))),
+        (Object::String(_), value) | (value, _) => Err(builtin_mismatch(name, "a String", value)),
+    }
+}
+
+fn std_remove_all(name: &str, args: Vec<Object>) -> Result<Object> {
+    let args = builtin_args(name, args, 2)?;
+
+    match (&args[0], &args[1]) {
+        (Object::Char(c), Object::String(s)) => {
+            Ok(Object::String(s.chars().filter(|x| x != c).collect()))
+        }
+        (Object::Char(_), value) => Err(builtin_mismatch(name, "a String", value)),
+        (value, _) => Err(builtin_mismatch(name, "a Char", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn interpreter_run() {
+        use super::{Interpreter, Object};
+        use crate::program::Program;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let examples = [
+            ("hello_world", "Hello World!\n"),
+            ("hello_world_2", "Hello World!\n"),
+            ("sum", "3\n"),
+            ("result", "Hello, World!\n"),
+            ("boolean", "false as uint: 0\n"),
+            ("destructuring", "Hello Alice and Bob!\n"),
+        ];
+
+        for (name, expected) in examples.iter() {
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let mut interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+            let program = Program::from_file(format!("examples/{}.at", name)).unwrap();
+            let res = interpreter.run_program(&program, "main");
+
+            assert!(res.is_ok());
+            assert_eq!(res.unwrap(), Object::IO);
+            assert_eq!(
+                String::from_utf8(output.borrow().clone()).unwrap(),
+                expected.to_string()
+            );
+        }
+
+        let s = "
+            (module main (block
+                (import std.io)
+
+                (sig nonZero (Fun UInt UInt))
+                (val nonZero (fun n (case n (match 0 panic) (match others id))))
+
+                (sig main (Fun IO IO))
+                (val main (fun io (println io (nonZero 0))))))";
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let mut interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+        let program = Program::from_str(s).unwrap();
+        let mut res = interpreter.run_program(&program, "main");
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("runtime error"));
+        assert!(output.borrow().is_empty());
+
+        let s = "
+            (module main (block
+                (sig main (Fun UInt UInt))
+                (val main (fun n n))))";
+
+        interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+        let program = Program::from_str(s).unwrap();
+        res = interpreter.run_program(&program, "main");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("main must have type (Fun IO IO), found (Fun UInt UInt)"));
+
+        let s = "
+            (module main (block
+                (import std.io)
+
+                (sig main (Fun IO IO))
+                (val main (fun io (println io (deref (ref (pair 1 'a'))))))))";
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+        let program = Program::from_str(s).unwrap();
+        res = interpreter.run_program(&program, "main");
+
+        assert!(res.is_ok());
+        assert_eq!(
+            String::from_utf8(output.borrow().clone()).unwrap(),
+            "(pair 1 'a')\n".to_string()
+        );
+    }
+
+    #[test]
+    fn interpreter_check() {
+        use super::Interpreter;
+        use crate::program::Program;
+
+        let programs = [
+            (
+                "(import std.io) (val main (fun io (println io 1 2 3)))",
+                "expected 2 arguments for println of type (Fun IO _ IO), found 4",
+            ),
+            (
+                "(import std.io _ () io) (val main (fun io (io.println 5 io)))",
+                "expected type IO, found type UInt",
+            ),
+            (
+                "(import std.math) (import std.io) (val main (fun io (println io (+ \"a\" 'c'))))",
+                "expected type UInt, found type String",
+            ),
+            (
+                "(val main (fun io (println io 1)))",
+                "undefined name println",
+            ),
+        ];
+
+        for (body, expected) in programs.iter() {
+            let s = format!("(module main (block (sig main (Fun IO IO)) {}))", body);
+
+            let mut interpreter = Interpreter::new().unwrap();
+            let program = Program::from_str(&s).unwrap();
+
+            assert!(interpreter.loader.load_program(&program).is_ok());
+
+            let res = interpreter.check();
+
+            assert!(res.is_err());
+            assert!(res.unwrap_err().to_string().contains(expected));
+        }
+
+        let generics = [
+            ("(match T id) (match E panic)", None),
+            (
+                "(match T id)",
+                Some("non-exhaustive case over Result: missing variants E"),
+            ),
+            (
+                "(match T id) (match E panic) (match others panic)",
+                Some("unreachable match arm others: all variants of Result are already covered"),
+            ),
+        ];
+
+        for (arms, expected) in generics.iter() {
+            let s = format!(
+                "(module result (list T E) (block
+                    (type Result (Enum T E))
+                    (sig unwrap (Fun Result T))
+                    (val unwrap (fun res (case res {})))))
+                (module main (block
+                    (import result (list String String) unwrap)
+                    (sig main (Fun IO IO))
+                    (val main (fun io io))))",
+                arms
+            );
+
+            let mut interpreter = Interpreter::new().unwrap();
+            let program = Program::from_str(&s).unwrap();
+
+            assert!(interpreter.loader.load_program(&program).is_ok());
+
+            let res = interpreter.check();
+
+            match expected {
+                None => assert!(res.is_ok()),
+                Some(expected) => {
+                    assert!(res.is_err());
+                    assert!(res.unwrap_err().to_string().contains(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpreter_cast() {
+        use super::Interpreter;
+        use crate::error::Error;
+        use crate::program::Program;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let programs = [
+            (
+                "(cast Size 3.7)",
+                Ok("3\n"),
+                "lossy cast from Float to Size",
+            ),
+            (
+                "(cast Pointer (cast Size 16))",
+                Ok("0x10\n"),
+                "unsafe cast from Size to Pointer",
+            ),
+            (
+                "(cast UInt -1)",
+                Err("cast overflow: -1 does not fit in UInt"),
+                "lossy cast from Int to UInt",
+            ),
+            (
+                "(cast Int 10000000000000000000.0)",
+                Err("cast overflow: 10000000000000000000 does not fit in Int"),
+                "lossy cast from Float to Int",
+            ),
+            (
+                "(cast Char 1114112)",
+                Err("cast overflow: 1114112 is not a valid Char"),
+                "lossy cast from UInt to Char",
+            ),
+        ];
+
+        for (value, expected, warning) in programs.iter() {
+            let s = format!(
+                "(module main (block
+                    (import std.io)
+
+                    (sig main (Fun IO IO))
+                    (val main (fun io (println io {})))))",
+                value
+            );
+
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let mut interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+            let program = Program::from_str(&s).unwrap();
+
+            assert!(interpreter.loader.load_program(&program).is_ok());
+
+            let warnings = interpreter.check().unwrap();
+
+            assert_eq!(warnings.len(), 1);
+            assert!(warnings[0].to_string().contains(warning));
+
+            let res = interpreter.run("main");
+
+            match expected {
+                Ok(expected) => {
+                    assert!(res.is_ok());
+                    assert_eq!(
+                        String::from_utf8(output.borrow().clone()).unwrap(),
+                        expected.to_string()
+                    );
+                }
+                Err(expected) => {
+                    let err = res.unwrap_err();
+
+                    assert!(matches!(err, Error::Runtime(_)));
+                    assert!(err.to_string().contains(expected));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpreter_builtins() {
+        use super::{Interpreter, Object};
+        use crate::stdlib;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let output = Rc::new(RefCell::new(Vec::new()));
+        let interpreter = Interpreter::with_output(output).unwrap();
+
+        let names = stdlib::builtins()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<String>>();
+
+        assert_eq!(interpreter.builtins.names().len(), names.len());
+
+        for name in names.iter() {
+            let args = match name.as_str() {
+                "std.io.print" | "std.io.println" => vec![Object::IO, Object::UInt(1)],
+                "std.io.printf" => vec![Object::IO, Object::String("{}".into()), Object::UInt(1)],
+                "std.string.length" => vec![Object::String("abc".into())],
+                "std.string.concat" => {
+                    vec![Object::String("a".into()), Object::String("b".into())]
+                }
+                "std.string.removeAll" => vec![Object::Char('a'), Object::String("abc".into())],
+                _ => vec![Object::UInt(6), Object::UInt(3)],
+            };
+
+            let builtin = interpreter.builtins.get(name).unwrap();
+            let value = builtin.call(args).unwrap();
+
+            assert!(
+                interpreter.has_type(0, &value, &builtin.sig.body),
+                "{} returned {}, declared {}",
+                name,
+                value,
+                builtin.sig
+            );
+        }
+
+        let mut interpreter = Interpreter::new().unwrap();
+        let res = interpreter.register_std(vec![("std.io.flush".into(), "(Fun IO IO)".into())]);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("builtin std.io.flush has no implementation"));
+        assert!(interpreter.builtins.get("std.io.flush").is_none());
+    }
+
+    #[test]
+    fn interpreter_eval() {
+        use super::Interpreter;
+        use crate::error::Error;
+        use crate::program::Program;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let programs = [
+            (
+                "(sig scale (Fun UInt UInt))
+                (val scale (fun n
+                    (case n (match 0 10) (match 1 20) (match others (fun m (* m 2))))))
+                (sig main (Fun IO IO))
+                (val main (fun io
+                    (printf io \"{} {} {}\\n\" (pair (scale 0) (pair (scale 1) (scale 7))))))",
+                Ok("10 20 14\n"),
+            ),
+            (
+                "(sig main (Fun IO IO))
+                (val main (fun io (let
+                    (sig x UInt)
+                    (val x (+ 1 2))
+                    (sig y UInt)
+                    (val y (* x 4))
+                    (println io y))))",
+                Ok("12\n"),
+            ),
+            (
+                "(sig adder (Fun UInt (Fun UInt UInt)))
+                (val adder (fun a (fun b (+ a b))))
+                (sig main (Fun IO IO))
+                (val main (fun io (let
+                    (sig addTwo (Fun UInt UInt))
+                    (val addTwo (adder 2))
+                    (println io (addTwo 3)))))",
+                Ok("5\n"),
+            ),
+            (
+                "(sig main (Fun IO IO))
+                (val main (fun io (printf io \"{} + {} = {}\\n\" (pair 1 (pair 2 (+ 1 2))))))",
+                Ok("1 + 2 = 3\n"),
+            ),
+            (
+                "(sig main (Fun IO IO))
+                (val main (fun io (printf io \"{}\\n\" (pair 1 2))))",
+                Err("builtin std.io.printf expected 1 format arguments, found 2"),
+            ),
+            (
+                "(sig main (Fun IO IO))
+                (val main (fun io (println io (/ 1 0))))",
+                Err("builtin std.math./: division by zero"),
+            ),
+            (
+                "(sig main (Fun IO IO))
+                (val main (fun io (println io (- 1 2))))",
+                Err("builtin std.math.-: overflow with 1 and 2"),
+            ),
+        ];
+
+        for (body, expected) in programs.iter() {
+            let s = format!(
+                "(module main (block (import std.io) (import std.math) {}))",
+                body
+            );
+
+            let output = Rc::new(RefCell::new(Vec::new()));
+            let mut interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+            let program = Program::from_str(&s).unwrap();
+            let res = interpreter.run_program(&program, "main");
+
+            match expected {
+                Ok(expected) => {
+                    assert!(res.is_ok(), "{}", res.unwrap_err());
+                    assert_eq!(
+                        String::from_utf8(output.borrow().clone()).unwrap(),
+                        expected.to_string()
+                    );
+                }
+                Err(expected) => {
+                    let err = res.unwrap_err();
+
+                    assert!(matches!(err, Error::Runtime(_)));
+                    assert!(err.to_string().contains(expected), "{}", err);
+                    assert!(output.borrow().is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn interpreter_call_depth() {
+        use super::{stack_size, Interpreter};
+        use crate::program::Program;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+        use std::thread;
+
+        let s = "(module main (block
+            (import std.io)
+            (import std.math)
+
+            (sig count (Fun UInt UInt))
+            (val count (fun n (case n (match 0 0) (match others (fun m (count (- m 1)))))))
+
+            (sig main (Fun IO IO))
+            (val main (fun io (println io (count 10000))))))";
+
+        let program = Program::from_str(s).unwrap();
+
+        let handle = thread::Builder::new()
+            .stack_size(stack_size(25_000))
+            .spawn(move || {
+                let output = Rc::new(RefCell::new(Vec::new()));
+                let mut interpreter = Interpreter::with_output(output.clone()).unwrap();
+
+                interpreter
+                    .run_program(&program, "main")
+                    .map(|_| String::from_utf8(output.borrow().clone()).unwrap())
+            })
+            .unwrap();
+
+        let res = handle.join().unwrap();
+
+        assert!(res.is_ok(), "{}", res.unwrap_err());
+        assert_eq!(res.unwrap(), "0\n".to_string());
+
+        let s = "(module main (block
+            (sig main (Fun IO IO))
+            (val main (fun io (main io)))))";
+
+        let mut interpreter = Interpreter::new().unwrap();
+        interpreter.max_call_depth = 100;
+
+        let program = Program::from_str(s).unwrap();
+        let res = interpreter.run_program(&program, "main");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("call depth limit of 100 exceeded"));
+    }
+}
@@ -1,7 +1,17 @@
+pub mod builtin;
 pub mod chunk;
+pub mod cst;
+pub mod doc;
 pub mod error;
+pub mod formatter;
+pub mod interpreter;
+pub mod loader;
 pub mod loc;
+pub mod program;
 pub mod result;
+pub mod semantic;
+pub mod span;
+pub mod stdlib;
 pub mod syntax;
 pub mod token;
 pub mod value;
@@ -0,0 +1,711 @@
+use crate::error::{Error, SemanticError};
+use crate::loc::Loc;
+use crate::program::Program;
+use crate::result::Result;
+use crate::semantic::checker::ModuleTypes;
+use crate::semantic::resolver::Resolver;
+use crate::stdlib;
+use crate::syntax::symbol_name;
+use crate::syntax::SYMBOL_PATH_SEPARATOR;
+use crate::token::Tokens;
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::types::Type;
+use crate::value::visit::Visitor;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const MODULE_FILE_EXTENSION: &str = "at";
+
+#[derive(Debug, Default)]
+struct Imports {
+    imports: Vec<ImportForm>,
+}
+
+impl Visitor for Imports {
+    fn visit_import_form(&mut self, node: &ImportForm) {
+        self.imports.push(node.clone());
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct Loader {
+    pub roots: Vec<PathBuf>,
+    pub sources: BTreeMap<String, String>,
+    pub forms: BTreeMap<String, ModuleForm>,
+    pub modules: BTreeMap<String, ModuleForm>,
+    pub instances: BTreeMap<(String, Vec<String>), ModuleForm>,
+    loading: Vec<String>,
+}
+
+impl Loader {
+    pub fn new() -> Loader {
+        Loader::default()
+    }
+
+    pub fn add_root<P: AsRef<Path>>(&mut self, root: P) {
+        self.roots.push(root.as_ref().to_path_buf());
+    }
+
+    pub fn add_source(&mut self, module: &str, source: &str) {
+        self.sources.insert(module.into(), source.into());
+    }
+
+    pub fn add_module(&mut self, module: &str, form: ModuleForm) {
+        self.forms.insert(module.into(), form);
+    }
+
+    pub fn add_program(&mut self, program: &Program) {
+        for module in program.modules.iter() {
+            self.add_module(&module.name.to_string(), module.clone());
+        }
+    }
+
+    pub fn is_loaded(&self, module: &str) -> bool {
+        self.modules.contains_key(module)
+    }
+
+    pub fn module(&self, module: &str) -> Option<ModuleForm> {
+        self.modules.get(module).cloned()
+    }
+
+    pub fn module_path(module: &str) -> PathBuf {
+        let mut path = module.split(SYMBOL_PATH_SEPARATOR).collect::<PathBuf>();
+        path.set_extension(MODULE_FILE_EXTENSION);
+
+        path
+    }
+
+    pub fn module_file(&self, module: &str) -> Option<PathBuf> {
+        let path = Loader::module_path(module);
+
+        self.roots
+            .iter()
+            .map(|root| root.join(&path))
+            .find(|file| file.is_file())
+    }
+
+    pub fn module_imports(module: &ModuleForm) -> Vec<ImportForm> {
+        let mut imports = Imports::default();
+        imports.visit_module_form(module);
+
+        imports.imports
+    }
+
+    fn parse_module(&self, module: &str, loc: Option<Loc>) -> Result<ModuleForm> {
+        if let Some(form) = self.forms.get(module) {
+            return Ok(form.clone());
+        }
+
+        let tokens = if let Some(source) = self.sources.get(module) {
+            Tokens::from_str(source)?
+        } else if let Some(file) = self.module_file(module) {
+            Tokens::from_file(file)?
+        } else if let Some(source) = stdlib::source(module) {
+            Tokens::from_str(source)?
+        } else {
+            let roots = self
+                .roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            return Err(Error::Semantic(SemanticError {
+                loc,
+                desc: format!(
+                    "module {} not found (searched {} in: {})",
+                    module,
+                    Loader::module_path(module).display(),
+                    roots
+                ),
+            }));
+        };
+
+        let program = Program::from_tokens(&tokens)?;
+        let name = symbol_name(module);
+
+        program.module(&name).ok_or_else(|| {
+            Error::Semantic(SemanticError {
+                loc,
+                desc: format!("expected a module named {} in module {}", name, module),
+            })
+        })
+    }
+
+    pub fn load(&mut self, module: &str) -> Result<ModuleForm> {
+        self.load_from(module, None)
+    }
+
+    fn load_from(&mut self, module: &str, loc: Option<Loc>) -> Result<ModuleForm> {
+        if let Some(form) = self.modules.get(module) {
+            return Ok(form.clone());
+        }
+
+        if let Some(idx) = self.loading.iter().position(|m| m == module) {
+            let mut chain = self.loading[idx..].to_vec();
+            chain.push(module.into());
+
+            return Err(Error::Semantic(SemanticError {
+                loc,
+                desc: format!("import cycle: {}", chain.join(" -> ")),
+            }));
+        }
+
+        let form = self.parse_module(module, loc)?;
+
+        self.loading.push(module.into());
+        let res = self.load_imports(&form);
+        self.loading.pop();
+
+        res?;
+
+        self.modules.insert(module.into(), form.clone());
+
+        Ok(form)
+    }
+
+    pub fn load_imports(&mut self, module: &ModuleForm) -> Result<()> {
+        for import in Loader::module_imports(module) {
+            self.load_from(&import.module.to_string(), import.loc())?;
+            self.instantiate(module, &import)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn instance(&self, module: &str, args: &[Type]) -> Option<ModuleForm> {
+        let key = (
+            module.to_string(),
+            args.iter()
+                .map(|arg| arg.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        self.instances.get(&key).cloned()
+    }
+
+    pub fn type_aliases(&self, module: &ModuleForm) -> Result<BTreeMap<String, Type>> {
+        let mut aliases = module.type_aliases();
+
+        for import in Loader::module_imports(module) {
+            let path = import.module.to_string();
+            let args = import
+                .type_arguments()
+                .iter()
+                .map(|arg| arg.resolve(&aliases))
+                .collect::<Vec<Type>>();
+
+            let instance = match self.instance(&path, &args) {
+                Some(instance) => instance,
+                None => continue,
+            };
+
+            let target_aliases = self.type_aliases(&instance)?;
+            let qualifier = import.qualifier_name();
+
+            for name in instance.exports()?.iter().map(|e| e.to_string()) {
+                let alias = match target_aliases.get(&name) {
+                    Some(alias) => alias.resolve(&target_aliases),
+                    None => continue,
+                };
+
+                let imported = !import.is_qualified_only()
+                    && (import.imports_all()
+                        || import.defs.iter().any(|def| def.to_string() == name));
+
+                if imported {
+                    aliases.entry(name.clone()).or_insert_with(|| alias.clone());
+                }
+
+                aliases.insert(format!("{}.{}", qualifier, name), alias);
+            }
+        }
+
+        Ok(aliases)
+    }
+
+    pub fn type_arguments(&self, module: &ModuleForm, import: &ImportForm) -> Result<Vec<Type>> {
+        let aliases = self.type_aliases(module)?;
+
+        let args = import
+            .type_arguments()
+            .iter()
+            .map(|arg| arg.resolve(&aliases))
+            .collect::<Vec<Type>>();
+
+        Ok(args)
+    }
+
+    pub fn import_types(&self, module: &ModuleForm, import: &ImportForm) -> Result<ModuleTypes> {
+        let path = import.module.to_string();
+        let args = self.type_arguments(module, import)?;
+
+        let instance = self.instance(&path, &args).ok_or_else(|| {
+            Error::Semantic(SemanticError {
+                loc: import.loc(),
+                desc: format!("module {} is not loaded", path),
+            })
+        })?;
+
+        let aliases = self.type_aliases(&instance)?;
+
+        let exports = instance
+            .exports()?
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+
+        let mut types = ModuleTypes::new();
+        types.path = path;
+
+        for name in exports.iter() {
+            if let Some(alias) = aliases.get(name) {
+                types.aliases.insert(name.clone(), alias.resolve(&aliases));
+            }
+        }
+
+        for entry in instance.block_entries() {
+            if let BlockFormEntry::SigForm(form) = entry {
+                let name = form.name.to_string();
+
+                if exports.contains(&name) {
+                    types.values.insert(name, form.value.resolve(&aliases));
+                }
+            }
+        }
+
+        Ok(types)
+    }
+
+    pub fn module_import_types(
+        &self,
+        module: &ModuleForm,
+    ) -> Result<BTreeMap<ImportForm, ModuleTypes>> {
+        let mut imports = BTreeMap::new();
+
+        for import in Loader::module_imports(module) {
+            let types = self.import_types(module, &import)?;
+            imports.insert(import, types);
+        }
+
+        Ok(imports)
+    }
+
+    pub fn instantiate(&mut self, module: &ModuleForm, import: &ImportForm) -> Result<ModuleForm> {
+        let path = import.module.to_string();
+        let args = self.type_arguments(module, import)?;
+
+        if let Some(instance) = self.instance(&path, &args) {
+            return Ok(instance);
+        }
+
+        let target = self.loaded_module(&path, import.loc())?;
+        let instance = target.instantiate(&args).map_err(|err| match err {
+            Error::Semantic(SemanticError { loc, desc }) => Error::Semantic(SemanticError {
+                loc: import.loc().or(loc),
+                desc,
+            }),
+            err => err,
+        })?;
+
+        let key = (
+            path,
+            args.iter()
+                .map(|arg| arg.to_string())
+                .collect::<Vec<String>>(),
+        );
+
+        self.instances.insert(key, instance.clone());
+
+        if !target.type_parameters.is_empty() {
+            self.load_imports(&instance)?;
+        }
+
+        Ok(instance)
+    }
+
+    pub fn exports(&self, module: &str) -> Result<Vec<String>> {
+        let form = self.loaded_module(module, None)?;
+
+        let exports = form
+            .exports()?
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<String>>();
+
+        Ok(exports)
+    }
+
+    fn loaded_module(&self, module: &str, loc: Option<Loc>) -> Result<ModuleForm> {
+        self.module(module).ok_or_else(|| {
+            Error::Semantic(SemanticError {
+                loc,
+                desc: format!("module {} is not loaded", module),
+            })
+        })
+    }
+
+    pub fn check_imports(&self, module: &ModuleForm) -> Result<()> {
+        for import in Loader::module_imports(module) {
+            let path = import.module.to_string();
+            let target = self.loaded_module(&path, import.loc())?;
+            let exports = target.exports()?;
+
+            for def in import.defs.iter() {
+                let value = match def {
+                    ImportFormDef::ValueSymbol(value) | ImportFormDef::TypeSymbol(value) => value,
+                    ImportFormDef::Ignore(_) | ImportFormDef::Empty(_) => continue,
+                };
+
+                let name = value.to_string();
+
+                if exports.iter().any(|e| e.to_string() == name) {
+                    continue;
+                }
+
+                let desc = if target.is_defined(&name) {
+                    format!("cannot import private name {} from module {}", name, path)
+                } else {
+                    format!("module {} does not define {}", path, name)
+                };
+
+                return Err(Error::Semantic(SemanticError {
+                    loc: value.loc(),
+                    desc,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check(&self) -> Result<()> {
+        for module in self.modules.values() {
+            module.exports()?;
+            self.check_imports(module)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn resolver(&self) -> Result<Resolver> {
+        let mut resolver = Resolver::new();
+
+        for module in self.modules.keys() {
+            resolver.add_exports(module, self.exports(module)?);
+        }
+
+        Ok(resolver)
+    }
+
+    pub fn load_program(&mut self, program: &Program) -> Result<()> {
+        self.add_program(program);
+
+        for module in program.modules.iter() {
+            self.load_from(&module.name.to_string(), module.loc())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn loader_load() {
+        use super::Loader;
+        use std::path::Path;
+
+        assert_eq!(
+            Loader::module_path("std.io"),
+            Path::new("std").join("io.at")
+        );
+
+        let mut loader = Loader::new();
+        loader.add_source("a", "(module a (block (import b.c) (val x 1)))");
+        loader.add_source("b.c", "(module c (block (val y 2)))");
+
+        let mut res = loader.load("a");
+
+        assert!(res.is_ok());
+        assert!(loader.is_loaded("a"));
+        assert!(loader.is_loaded("b.c"));
+        assert_eq!(loader.module("b.c").unwrap().name.to_string(), "c");
+
+        loader = Loader::new();
+        loader.add_source("a", "(module a (block (import b) (val x 1)))");
+        loader.add_source("b", "(module b (block (import c) (val y 1)))");
+        loader.add_source("c", "(module c (block (import a) (val z 1)))");
+
+        res = loader.load("a");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("import cycle: a -> b -> c -> a"));
+
+        loader = Loader::new();
+        loader.add_source("a", "(module a (block (import missing) (val x 1)))");
+
+        res = loader.load("a");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("module missing not found"));
+
+        loader = Loader::new();
+        loader.add_source("a", "(module a (block (val x (let (import b) y))))");
+        loader.add_source("b", "(module b (block (val y (let (import missing) 1))))");
+
+        res = loader.load("a");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("module missing not found"));
+        assert!(!loader.is_loaded("a"));
+    }
+
+    #[test]
+    fn loader_load_from_roots() {
+        use super::Loader;
+        use crate::program::Program;
+
+        let mut loader = Loader::new();
+        loader.add_root("./examples");
+
+        let mut res = loader.load("missing");
+
+        assert!(res.is_err());
+
+        res = loader.load("hello_world");
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected a module named hello_world in module hello_world"));
+
+        res = loader.load("result");
+
+        assert!(res.is_ok());
+
+        let module = res.unwrap();
+
+        assert_eq!(module.name.to_string(), "result");
+        assert_eq!(
+            module.loc().unwrap().to_string(),
+            "(file: ./examples/result.at, line: 0, pos: 0)".to_string()
+        );
+
+        let program = Program::from_file("./examples/result.at").unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert!(loader.is_loaded("result"));
+        assert!(loader.is_loaded("main"));
+        assert!(loader.is_loaded("std.io"));
+        assert!(loader.module("std.io").unwrap().is_defined("println"));
+    }
+
+    #[test]
+    fn loader_check() {
+        use super::Loader;
+        use crate::program::Program;
+
+        let io = "(module io (block (export println) (val println ()) (val print ())))";
+
+        let mut loader = Loader::new();
+        loader.add_source("std.io", io);
+
+        let mut program =
+            Program::from_str("(module main (block (import std.io _ priln)))").unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+
+        let mut res = loader.check();
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("module std.io does not define priln"));
+
+        loader = Loader::new();
+        loader.add_source("std.io", io);
+
+        program = Program::from_str(
+            "(module main (block (val main (fun io (let (import std.io _ priln) (priln io))))))",
+        )
+        .unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert!(loader.is_loaded("std.io"));
+
+        res = loader.check();
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("module std.io does not define priln"));
+
+        loader = Loader::new();
+        loader.add_source("std.io", io);
+
+        program = Program::from_str("(module main (block (import std.io _ print)))").unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+
+        res = loader.check();
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cannot import private name print from module std.io"));
+
+        loader = Loader::new();
+        loader.add_source("std.io", io);
+
+        program = Program::from_str(
+            "(module main (block (import std.io) (import std.io _ () io) (import std.io _ println)))",
+        )
+        .unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert!(loader.check().is_ok());
+
+        let resolver = loader.resolver().unwrap();
+
+        assert_eq!(
+            resolver.exports.get("std.io"),
+            Some(&vec!["println".to_string()])
+        );
+    }
+
+    #[test]
+    fn loader_instantiate() {
+        use super::Loader;
+        use crate::program::Program;
+
+        let mut loader = Loader::new();
+        loader.add_source("std.io", "(module io (block (val println ())))");
+
+        let mut program = Program::from_file("./examples/result.at").unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert_eq!(loader.instances.len(), 2);
+
+        let main = loader.module("main").unwrap();
+        let args = loader
+            .type_arguments(&main, &Loader::module_imports(&main)[0])
+            .unwrap();
+        let instance = loader.instance("result", &args).unwrap();
+
+        assert!(instance
+            .to_string()
+            .contains("(type Result (Enum String String))"));
+
+        program = Program::from_str(
+            "(module other (block (import result (list Int Int)) (import result (list Int Int))))",
+        )
+        .unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert_eq!(loader.instances.len(), 3);
+
+        program = Program::from_str(
+            "(module a (block (type Error Int) (import result (list Int Error))))
+            (module b (block (type Error Char) (import result (list Int Error))))
+            (module c (block (type Code Int) (import result (list Int Code))))",
+        )
+        .unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+        assert_eq!(loader.instances.len(), 4);
+
+        for (name, expected) in [
+            ("a", "(Enum Int Int)"),
+            ("b", "(Enum Int Char)"),
+            ("c", "(Enum Int Int)"),
+        ] {
+            let module = loader.module(name).unwrap();
+            let args = loader
+                .type_arguments(&module, &Loader::module_imports(&module)[0])
+                .unwrap();
+
+            assert!(loader
+                .instance("result", &args)
+                .unwrap()
+                .to_string()
+                .contains(expected));
+        }
+
+        loader = Loader::new();
+        loader.add_source(
+            "box",
+            "(module box T (block
+                (type Box (Pair T T))
+                (sig unbox (Fun Box T))
+                (val unbox (fun b (let (sig x T) (val x (cast T 0)) x)))))",
+        );
+        loader.add_source(
+            "wrap",
+            "(module wrap (block (import box UInt Box) (type Boxes (List Box))))",
+        );
+
+        program =
+            Program::from_str("(module main (block (import wrap) (import box Boxes)))").unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+
+        let main = loader.module("main").unwrap();
+        let args = loader
+            .type_arguments(&main, &Loader::module_imports(&main)[1])
+            .unwrap();
+
+        assert_eq!(args[0].to_string(), "(List (Pair UInt UInt))".to_string());
+
+        let instance = loader.instance("box", &args).unwrap().to_string();
+
+        assert!(instance.contains("(sig x (List (Pair UInt UInt)))"));
+        assert!(instance.contains("(cast (List (Pair UInt UInt)) 0)"));
+        assert!(!instance.contains(" T"));
+
+        program = Program::from_str(
+            "(module main (block (val x (let (import wrap) (import box Boxes) 1))))",
+        )
+        .unwrap();
+
+        assert!(loader.load_program(&program).is_ok());
+
+        let main = loader.module("main").unwrap();
+        let args = loader
+            .type_arguments(&main, &Loader::module_imports(&main)[1])
+            .unwrap();
+
+        assert_eq!(args[0].to_string(), "(List (Pair UInt UInt))".to_string());
+
+        loader = Loader::new();
+        loader.add_source(
+            "result",
+            "(module result (list T E) (block (type Result (Enum T E))))",
+        );
+
+        program = Program::from_str("(module main (block (import result Int)))").unwrap();
+
+        let res = loader.load_program(&program);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected 2 type arguments for module result, found 1"));
+    }
+}
@@ -5,6 +5,7 @@ pub struct Loc {
     pub file: Option<String>,
     pub line: usize,
     pub pos: usize,
+    pub offset: usize,
 }
 
 impl Loc {
@@ -12,6 +13,23 @@ impl Loc {
         Loc::default()
     }
 
+    pub fn advance(&self, s: &str) -> Loc {
+        let mut loc = self.clone();
+
+        for c in s.chars() {
+            if c == '\n' {
+                loc.line += 1;
+                loc.pos = 0;
+            } else {
+                loc.pos += 1;
+            }
+
+            loc.offset += c.len_utf8();
+        }
+
+        loc
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         let file = self.file.clone().unwrap_or_else(|| "none".into());
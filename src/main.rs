@@ -0,0 +1,197 @@
+use aster::doc::{DocFormat, DocGenerator};
+use aster::formatter::Formatter;
+use aster::interpreter::{stack_size, Interpreter, MAX_CALL_DEPTH};
+use aster::program::Program;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process;
+use std::thread;
+
+const USAGE: &str = "usage:
+    aster fmt [--check] [--width N] [FILE...]
+    aster doc [--html] [--out DIR] FILE...
+    aster run [--max-call-depth N] FILE";
+
+fn fmt(args: &[String]) -> Result<bool, String> {
+    let mut formatter = Formatter::new();
+    let mut check = false;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--width" => {
+                formatter.width = args
+                    .next()
+                    .and_then(|width| width.parse().ok())
+                    .ok_or_else(|| "expected a width after --width".to_string())?;
+            }
+            _ => paths.push(arg.clone()),
+        }
+    }
+
+    if paths.is_empty() {
+        let mut s = String::new();
+        io::stdin()
+            .read_to_string(&mut s)
+            .map_err(|err| err.to_string())?;
+
+        let formatted = formatter.format_str(&s).map_err(|err| err.to_string())?;
+
+        if check {
+            return Ok(formatted == s);
+        }
+
+        print!("{}", formatted);
+
+        return Ok(true);
+    }
+
+    let mut formatted_all = true;
+
+    for path in paths.iter() {
+        let s = fs::read_to_string(path).map_err(|err| format!("{}: {}", path, err))?;
+        let formatted = formatter
+            .format_str(&s)
+            .map_err(|err| format!("{}: {}", path, err))?;
+
+        if formatted == s {
+            continue;
+        }
+
+        if check {
+            eprintln!("{} is not formatted", path);
+            formatted_all = false;
+        } else {
+            fs::write(path, formatted).map_err(|err| format!("{}: {}", path, err))?;
+        }
+    }
+
+    Ok(formatted_all)
+}
+
+fn doc(args: &[String]) -> Result<bool, String> {
+    let mut generator = DocGenerator::new();
+    let mut out = None;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--html" => generator.format = DocFormat::Html,
+            "--out" => {
+                out = Some(
+                    args.next()
+                        .cloned()
+                        .ok_or_else(|| "expected a directory after --out".to_string())?,
+                );
+            }
+            _ => paths.push(arg.clone()),
+        }
+    }
+
+    if paths.is_empty() {
+        return Err(USAGE.to_string());
+    }
+
+    if let Some(out) = out.as_ref() {
+        fs::create_dir_all(out).map_err(|err| format!("{}: {}", out, err))?;
+    }
+
+    for path in paths.iter() {
+        let program = Program::from_file(path).map_err(|err| format!("{}: {}", path, err))?;
+        let pages = generator
+            .generate(&program)
+            .map_err(|err| format!("{}: {}", path, err))?;
+
+        for page in pages.iter() {
+            let out = match out.as_ref() {
+                Some(out) => out,
+                None => {
+                    print!("{}", page.content);
+                    continue;
+                }
+            };
+
+            let file = Path::new(out).join(&page.file);
+
+            fs::write(&file, &page.content)
+                .map_err(|err| format!("{}: {}", file.display(), err))?;
+        }
+    }
+
+    Ok(true)
+}
+
+fn run(args: &[String]) -> Result<bool, String> {
+    let mut max_call_depth = MAX_CALL_DEPTH;
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--max-call-depth" => {
+                max_call_depth = args
+                    .next()
+                    .and_then(|depth| depth.parse().ok())
+                    .ok_or_else(|| "expected a depth after --max-call-depth".to_string())?;
+            }
+            _ => paths.push(arg.clone()),
+        }
+    }
+
+    if paths.len() != 1 {
+        return Err(USAGE.to_string());
+    }
+
+    let path = paths.remove(0);
+    let program = Program::from_file(&path).map_err(|err| format!("{}: {}", path, err))?;
+    let root = Path::new(&path)
+        .parent()
+        .map(|root| root.to_path_buf())
+        .unwrap_or_default();
+
+    let handle = thread::Builder::new()
+        .stack_size(stack_size(max_call_depth))
+        .spawn(move || {
+            let mut interpreter = Interpreter::new().map_err(|err| err.to_string())?;
+            interpreter.max_call_depth = max_call_depth;
+            interpreter.loader.add_root(root);
+
+            interpreter
+                .run_program(&program, "main")
+                .map(|_| true)
+                .map_err(|err| format!("{}: {}", path, err))
+        })
+        .map_err(|err| err.to_string())?;
+
+    handle
+        .join()
+        .map_err(|_| "the interpreter panicked".to_string())?
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let res = match args.first().map(|arg| arg.as_str()) {
+        Some("fmt") => fmt(&args[1..]),
+        Some("doc") => doc(&args[1..]),
+        Some("run") => run(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match res {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use crate::error::{Error, SemanticError, SyntacticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::span::Span;
+use crate::syntax::EMPTY;
+use crate::token::{doc_text, join_docs, leading_docs, token_index};
+use crate::token::{TokenKind, Tokens};
+use crate::value::forms::form::Form;
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::SimpleValue;
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct Program {
+    pub tokens: Box<Tokens>,
+    pub modules: Vec<ModuleForm>,
+}
+
+impl Program {
+    pub fn new() -> Program {
+        Program::default()
+    }
+
+    pub fn file(&self) -> String {
+        if !self.tokens.is_empty() {
+            self.tokens[0].file()
+        } else {
+            EMPTY.into()
+        }
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        if !self.tokens.is_empty() {
+            self.tokens[0].loc()
+        } else {
+            None
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    pub fn module(&self, name: &str) -> Option<ModuleForm> {
+        self.modules
+            .iter()
+            .find(|m| m.name.to_string() == name)
+            .map(|m| m.to_owned())
+    }
+
+    pub fn module_names(&self) -> Vec<String> {
+        self.modules
+            .iter()
+            .map(|m| m.name.to_string())
+            .collect::<Vec<String>>()
+    }
+
+    pub fn push(&mut self, module: ModuleForm) -> Result<()> {
+        let name = module.name.to_string();
+
+        if let Some(prev) = self.module(&name) {
+            return Err(Error::Semantic(SemanticError {
+                loc: module.loc(),
+                desc: format!(
+                    "duplicate module name {} (first defined at {})",
+                    name,
+                    prev.loc().unwrap_or_default()
+                ),
+            }));
+        }
+
+        self.modules.push(module);
+
+        Ok(())
+    }
+
+    pub fn split_forms(tokens: &Tokens) -> Result<Vec<Tokens>> {
+        let (forms, errors) = Program::split_forms_recovering(tokens);
+
+        errors.into_iter().next().map_or(Ok(forms), Err)
+    }
+
+    pub fn split_forms_recovering(tokens: &Tokens) -> (Vec<Tokens>, Vec<Error>) {
+        let len = tokens.len();
+        let mut idx = 0;
+
+        let mut forms = vec![];
+        let mut errors = vec![];
+
+        while idx < len {
+            match tokens[idx].kind {
+                TokenKind::Comment | TokenKind::DocComment => {
+                    idx += 1;
+                }
+                TokenKind::FormStart => {
+                    let mut count = 0;
+                    let mut form_tokens = Tokens::new();
+
+                    while idx < len {
+                        let token = tokens[idx].clone();
+                        form_tokens.push(token.clone());
+                        idx += 1;
+
+                        if token.kind == TokenKind::FormStart {
+                            count += 1;
+                        } else if token.kind == TokenKind::FormEnd {
+                            count -= 1;
+
+                            if count == 0 {
+                                break;
+                            }
+                        }
+                    }
+
+                    if count != 0 {
+                        errors.push(Error::Syntactic(SyntacticError {
+                            loc: form_tokens[0].loc(),
+                            desc: "unclosed form".into(),
+                        }));
+                    } else {
+                        forms.push(form_tokens);
+                    }
+                }
+                _ => {
+                    errors.push(Error::Syntactic(SyntacticError {
+                        loc: tokens[idx].loc(),
+                        desc: format!("unexpected top-level value: {}", tokens[idx]),
+                    }));
+
+                    idx += 1;
+                }
+            }
+        }
+
+        (forms, errors)
+    }
+
+    fn module_docs(tokens: &Tokens, form_tokens: &Tokens) -> Option<String> {
+        let idx = form_tokens[0]
+            .loc()
+            .and_then(|loc| token_index(tokens, &loc))?;
+
+        doc_text(&leading_docs(tokens, idx))
+    }
+
+    pub fn from_tokens(tokens: &Tokens) -> Result<Program> {
+        let mut program = Program::new();
+        program.tokens = Box::new(tokens.to_owned());
+
+        for form_tokens in Program::split_forms(tokens)? {
+            let form = Form::from_tokens(&form_tokens)?;
+
+            if form.head.to_string() != "module" {
+                return Err(Error::Syntactic(SyntacticError {
+                    loc: form.loc(),
+                    desc: format!("expected a module form, found a {} form", form.head),
+                }));
+            }
+
+            let mut module = ModuleForm::from_form(&form)?;
+            module.doc = join_docs(Program::module_docs(tokens, &form_tokens), module.doc);
+
+            program.push(module)?;
+        }
+
+        Ok(program)
+    }
+
+    pub fn from_tokens_recovering(tokens: &Tokens) -> (Program, Vec<Error>) {
+        let mut program = Program::new();
+        program.tokens = Box::new(tokens.to_owned());
+
+        let (forms, mut errors) = Program::split_forms_recovering(tokens);
+
+        for form_tokens in forms.iter() {
+            let (mut module, module_errors) = ModuleForm::from_tokens_recovering(form_tokens);
+            module.doc = join_docs(Program::module_docs(tokens, form_tokens), module.doc);
+            let failed = matches!(module.name, SimpleValue::Empty(_));
+
+            errors.extend(module_errors);
+
+            if failed {
+                continue;
+            }
+
+            if let Err(err) = program.push(module) {
+                errors.push(err);
+            }
+        }
+
+        (program, errors)
+    }
+
+    pub fn from_str_recovering(s: &str) -> (Program, Vec<Error>) {
+        let (tokens, mut errors) = Tokens::from_str_recovering(s);
+        let (program, program_errors) = Program::from_tokens_recovering(&tokens);
+
+        errors.extend(program_errors);
+
+        (program, errors)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<Program> {
+        let tokens = Tokens::from_str(s)?;
+
+        Program::from_tokens(&tokens)
+    }
+
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Program> {
+        let tokens = Tokens::from_file(path)?;
+
+        Program::from_tokens(&tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        self.modules
+            .iter()
+            .map(|m| m.to_string())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl std::str::FromStr for Program {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn program_from_str() {
+        use super::Program;
+
+        let mut s = "
+        # a comment
+        (module a (block (val x 1)))
+        #! a doc comment
+        (module b (block (import a) (val y x)))";
+
+        let mut res = Program::from_str(s);
+
+        assert!(res.is_ok());
+
+        let program = res.unwrap();
+
+        assert_eq!(program.len(), 2);
+        assert_eq!(
+            program.module_names(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        assert!(program.module("b").is_some());
+        assert!(program.module("c").is_none());
+
+        s = "(module a (block (val x 1))) (module a (block (val y 2)))";
+
+        res = Program::from_str(s);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("duplicate module name a"));
+
+        s = "(module a (block (val x 1))) (val y 2)";
+
+        res = Program::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(module a (block (val x 1))) y";
+
+        res = Program::from_str(s);
+
+        assert!(res.is_err());
+
+        let program = Program::new();
+
+        assert!(program.is_empty());
+        assert_eq!(program.file(), "".to_string());
+        assert!(program.loc().is_none());
+
+        res = Program::from_str("");
+
+        assert!(res.is_ok());
+        assert!(res.unwrap().loc().is_none());
+    }
+
+    #[test]
+    fn program_from_str_recovering() {
+        use super::Program;
+        use crate::value::forms::block_form::BlockFormEntry;
+
+        let mut s = "
+        (module a (block
+            (val x 1)
+            (sig)
+            (val y 99999999999999999999)
+            (type T UInt)))
+        y
+        (module b (block (foo bar) (val z 2))
+        ";
+
+        let (program, errors) = Program::from_str_recovering(s);
+
+        let descs = errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(errors.len(), 5);
+        assert!(descs[0].contains("UInt literal 99999999999999999999 exceeds 64 bits"));
+        assert!(descs[1].contains("line: 7, pos: 8): form not closed"));
+        assert!(descs[2].contains("unexpected top-level value: y"));
+        assert!(descs[3].contains("line: 3, pos: 12): expected a name and a type"));
+        assert!(descs[4].contains("line: 7, pos: 25): unexpected form"));
+
+        assert_eq!(
+            program.module_names(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+
+        let entries = program.module("a").unwrap().block_entries();
+
+        assert_eq!(entries.len(), 4);
+        assert!(matches!(entries[1], BlockFormEntry::ErrorForm(_)));
+        assert!(matches!(entries[2], BlockFormEntry::ValForm(_)));
+        assert!(matches!(entries[3], BlockFormEntry::TypeForm(_)));
+        assert_eq!(entries[1].to_string(), "(sig)".to_string());
+
+        let entries = program.module("b").unwrap().block_entries();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].to_string(), "(foo bar)".to_string());
+        assert_eq!(entries[1].to_string(), "(val z 2)".to_string());
+
+        s = "(module a (block (val x 1)))";
+
+        let (program, errors) = Program::from_str_recovering(s);
+
+        assert!(errors.is_empty());
+        assert_eq!(program, Program::from_str(s).unwrap());
+    }
+
+    #[test]
+    fn program_from_file() {
+        use super::Program;
+        use std::path::Path;
+
+        let path = Path::new("./examples/result.at");
+
+        let res = Program::from_file(path);
+
+        assert!(res.is_ok());
+
+        let program = res.unwrap();
+
+        assert_eq!(
+            program.module_names(),
+            vec!["result".to_string(), "main".to_string()]
+        );
+        assert_eq!(program.modules[0].type_parameters.len(), 2);
+    }
+}
@@ -0,0 +1,295 @@
+use crate::error::{Error, RuntimeError};
+use crate::result::Result;
+use crate::value::types::{SimpleType, Type};
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+pub enum CastType {
+    UInt,
+    Int,
+    Float,
+    Size,
+    Char,
+    Pointer,
+}
+
+impl CastType {
+    pub fn from_type(t: &Type) -> Option<CastType> {
+        match t {
+            Type::Simple(SimpleType::UInt(_)) => Some(CastType::UInt),
+            Type::Simple(SimpleType::Int(_)) => Some(CastType::Int),
+            Type::Simple(SimpleType::Float(_)) => Some(CastType::Float),
+            Type::Simple(SimpleType::Size(_)) => Some(CastType::Size),
+            Type::Simple(SimpleType::Char(_)) => Some(CastType::Char),
+            Type::Simple(SimpleType::Pointer(_)) => Some(CastType::Pointer),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CastType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastType::UInt => write!(f, "UInt"),
+            CastType::Int => write!(f, "Int"),
+            CastType::Float => write!(f, "Float"),
+            CastType::Size => write!(f, "Size"),
+            CastType::Char => write!(f, "Char"),
+            CastType::Pointer => write!(f, "Pointer"),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+pub enum CastSafety {
+    Lossless,
+    Lossy,
+    Unsafe,
+}
+
+pub fn cast_safety(from: CastType, to: CastType) -> Option<CastSafety> {
+    use CastType::*;
+
+    if from == to {
+        return Some(CastSafety::Lossless);
+    }
+
+    match (from, to) {
+        (UInt, Size) | (Size, UInt) => Some(CastSafety::Lossless),
+        (Char, UInt) | (Char, Int) | (Char, Size) | (Char, Float) => Some(CastSafety::Lossless),
+        (UInt, Int) | (UInt, Float) | (UInt, Char) => Some(CastSafety::Lossy),
+        (Int, UInt) | (Int, Size) | (Int, Float) | (Int, Char) => Some(CastSafety::Lossy),
+        (Float, UInt) | (Float, Int) | (Float, Size) => Some(CastSafety::Lossy),
+        (Size, Int) | (Size, Float) | (Size, Char) => Some(CastSafety::Lossy),
+        (UInt, Pointer) | (Size, Pointer) => Some(CastSafety::Unsafe),
+        (Pointer, UInt) | (Pointer, Size) => Some(CastSafety::Unsafe),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum CastValue {
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Size(u64),
+    Char(char),
+    Pointer(u64),
+}
+
+impl CastValue {
+    pub fn cast_type(&self) -> CastType {
+        match self {
+            CastValue::UInt(_) => CastType::UInt,
+            CastValue::Int(_) => CastType::Int,
+            CastValue::Float(_) => CastType::Float,
+            CastValue::Size(_) => CastType::Size,
+            CastValue::Char(_) => CastType::Char,
+            CastValue::Pointer(_) => CastType::Pointer,
+        }
+    }
+
+    // Casts never wrap or saturate: out of range values are an overflow error.
+    pub fn cast(&self, to: CastType) -> Result<CastValue> {
+        let from = self.cast_type();
+
+        if cast_safety(from, to).is_none() {
+            return Err(Error::Runtime(RuntimeError {
+                loc: None,
+                desc: format!("invalid cast from {} to {}", from, to),
+            }));
+        }
+
+        let value = match *self {
+            CastValue::Float(n) => match to {
+                CastType::Float => CastValue::Float(n),
+                CastType::Int => CastValue::Int(int_from_float(n)?),
+                to => from_unsigned(uint_from_float(n, to)?, to)?,
+            },
+            CastValue::Int(n) => match to {
+                CastType::Int => CastValue::Int(n),
+                CastType::Float => CastValue::Float(n as f64),
+                to => from_unsigned(u64::try_from(n).map_err(|_| overflow(n, to))?, to)?,
+            },
+            CastValue::UInt(n) | CastValue::Size(n) | CastValue::Pointer(n) => {
+                from_unsigned(n, to)?
+            }
+            CastValue::Char(c) => from_unsigned(u64::from(u32::from(c)), to)?,
+        };
+
+        Ok(value)
+    }
+}
+
+fn overflow<T: fmt::Display>(n: T, to: CastType) -> Error {
+    Error::Runtime(RuntimeError {
+        loc: None,
+        desc: format!("cast overflow: {} does not fit in {}", n, to),
+    })
+}
+
+fn from_unsigned(n: u64, to: CastType) -> Result<CastValue> {
+    let value = match to {
+        CastType::UInt => CastValue::UInt(n),
+        CastType::Size => CastValue::Size(n),
+        CastType::Pointer => CastValue::Pointer(n),
+        CastType::Char => CastValue::Char(char_from(n)?),
+        CastType::Int => CastValue::Int(i64::try_from(n).map_err(|_| overflow(n, to))?),
+        CastType::Float => CastValue::Float(n as f64),
+    };
+
+    Ok(value)
+}
+
+fn uint_from_float(n: f64, to: CastType) -> Result<u64> {
+    let t = n.trunc();
+
+    // 2^64 is exactly representable, so the exclusive bound is precise.
+    if (0.0..18_446_744_073_709_551_616.0).contains(&t) {
+        Ok(t as u64)
+    } else {
+        Err(overflow(n, to))
+    }
+}
+
+fn int_from_float(n: f64) -> Result<i64> {
+    let t = n.trunc();
+
+    if (-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&t) {
+        Ok(t as i64)
+    } else {
+        Err(overflow(n, CastType::Int))
+    }
+}
+
+fn char_from(n: u64) -> Result<char> {
+    u32::try_from(n)
+        .ok()
+        .and_then(std::char::from_u32)
+        .ok_or_else(|| {
+            Error::Runtime(RuntimeError {
+                loc: None,
+                desc: format!("cast overflow: {} is not a valid Char", n),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cast_value_cast() {
+        use super::{cast_safety, CastSafety, CastType, CastValue};
+
+        assert_eq!(
+            cast_safety(CastType::Char, CastType::UInt),
+            Some(CastSafety::Lossless)
+        );
+        assert_eq!(
+            cast_safety(CastType::Int, CastType::UInt),
+            Some(CastSafety::Lossy)
+        );
+        assert_eq!(
+            cast_safety(CastType::UInt, CastType::Pointer),
+            Some(CastSafety::Unsafe)
+        );
+        assert_eq!(cast_safety(CastType::Float, CastType::Char), None);
+
+        assert_eq!(
+            CastValue::Int(42).cast(CastType::UInt).unwrap(),
+            CastValue::UInt(42)
+        );
+        assert_eq!(
+            CastValue::Float(-3.7).cast(CastType::Int).unwrap(),
+            CastValue::Int(-3)
+        );
+        assert_eq!(
+            CastValue::Float(3.7).cast(CastType::Size).unwrap(),
+            CastValue::Size(3)
+        );
+        assert_eq!(
+            CastValue::Size(8).cast(CastType::Pointer).unwrap(),
+            CastValue::Pointer(8)
+        );
+        assert_eq!(
+            CastValue::UInt(u64::MAX).cast(CastType::Pointer).unwrap(),
+            CastValue::Pointer(u64::MAX)
+        );
+
+        let overflows = [
+            (
+                CastValue::Int(-1),
+                CastType::UInt,
+                "-1 does not fit in UInt",
+            ),
+            (
+                CastValue::UInt(u64::MAX),
+                CastType::Int,
+                "18446744073709551615 does not fit in Int",
+            ),
+            (
+                CastValue::Float(-3.7),
+                CastType::UInt,
+                "-3.7 does not fit in UInt",
+            ),
+            (
+                CastValue::Float(1e30),
+                CastType::Int,
+                "1000000000000000000000000000000 does not fit in Int",
+            ),
+            (
+                CastValue::Float(f64::NAN),
+                CastType::Size,
+                "NaN does not fit in Size",
+            ),
+            (
+                CastValue::Float(f64::INFINITY),
+                CastType::UInt,
+                "inf does not fit in UInt",
+            ),
+            (
+                CastValue::Int(1_114_112),
+                CastType::Char,
+                "1114112 is not a valid Char",
+            ),
+        ];
+
+        for (value, to, expected) in overflows.iter() {
+            let res = value.cast(*to);
+
+            assert!(res.is_err());
+
+            let err = res.unwrap_err();
+
+            assert!(matches!(err, crate::error::Error::Runtime(_)));
+            assert!(err
+                .to_string()
+                .contains(&format!("cast overflow: {}", expected)));
+        }
+
+        assert_eq!(
+            CastValue::UInt(97).cast(CastType::Char).unwrap(),
+            CastValue::Char('a')
+        );
+        assert_eq!(
+            CastValue::Char('a').cast(CastType::Float).unwrap(),
+            CastValue::Float(97.0)
+        );
+
+        let mut res = CastValue::UInt(0xD800).cast(CastType::Char);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cast overflow: 55296 is not a valid Char"));
+
+        res = CastValue::Pointer(8).cast(CastType::Float);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("invalid cast from Pointer to Float"));
+    }
+}
@@ -0,0 +1,2049 @@
+use crate::error::{Error, SemanticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::semantic::cast::{cast_safety, CastSafety, CastType};
+use crate::semantic::resolver::fun_parameters;
+use crate::token::TokenKind;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::attrs_form::{AttrsForm, AttrsFormValue};
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::case_form::CaseFormVariable;
+use crate::value::forms::case_form::{CaseForm, CaseFormMatchAction, CaseFormMatchCase};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::ImportForm;
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::{LinearForm, LinearFormKind};
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::{MemForm, MemFormKind};
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::types::{ArrType, FunType, ListType, MapType, PairType, VecType};
+use crate::value::types::{SimpleType, Type};
+use crate::value::SimpleValue;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+struct TypeScope {
+    aliases: BTreeMap<String, Type>,
+    attrs: BTreeMap<String, Vec<String>>,
+    values: BTreeMap<String, Type>,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct ModuleTypes {
+    pub path: String,
+    pub aliases: BTreeMap<String, Type>,
+    pub values: BTreeMap<String, Type>,
+}
+
+impl ModuleTypes {
+    pub fn new() -> ModuleTypes {
+        ModuleTypes::default()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct Checker {
+    pub builtins: BTreeMap<String, FunType>,
+    pub imports: BTreeMap<ImportForm, ModuleTypes>,
+    pub types: BTreeMap<String, Type>,
+    pub warnings: Vec<SemanticError>,
+    pub skip_coverage: bool,
+    pub droppable: Vec<Loc>,
+    module: String,
+    type_parameters: Vec<String>,
+    scopes: Vec<TypeScope>,
+}
+
+impl Checker {
+    pub fn new() -> Checker {
+        Checker::default()
+    }
+
+    pub fn check(&mut self, module: &ModuleForm) -> Result<BTreeMap<String, Type>> {
+        self.check_as(&module.name.to_string(), module)
+    }
+
+    pub fn check_as(&mut self, path: &str, module: &ModuleForm) -> Result<BTreeMap<String, Type>> {
+        let (types, errors) = self.check_as_recovering(path, module);
+
+        errors.into_iter().next().map_or(Ok(types), Err)
+    }
+
+    pub fn check_as_recovering(
+        &mut self,
+        path: &str,
+        module: &ModuleForm,
+    ) -> (BTreeMap<String, Type>, Vec<Error>) {
+        self.types.clear();
+        self.warnings.clear();
+        self.droppable.clear();
+        self.scopes.clear();
+
+        self.module = path.into();
+        self.type_parameters = module
+            .type_parameters
+            .iter()
+            .map(|param| param.to_string())
+            .collect();
+
+        self.scopes.push(TypeScope::default());
+
+        let mut imports = vec![];
+        let mut attrs = vec![];
+        let mut types = vec![];
+        let mut sigs = vec![];
+        let mut vals = vec![];
+        let mut errors = vec![];
+
+        for entry in module.block_entries() {
+            match entry {
+                BlockFormEntry::ImportForm(form) => imports.push(*form),
+                BlockFormEntry::AttrsForm(form) => attrs.push(*form),
+                BlockFormEntry::TypeForm(form) => types.push(*form),
+                BlockFormEntry::SigForm(form) => sigs.push(*form),
+                BlockFormEntry::ValForm(form) => vals.push(*form),
+                BlockFormEntry::ErrorForm(form) => {
+                    errors.push(Error::Syntactic(form.error.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        if errors.is_empty() {
+            match self.check_imports(&imports) {
+                Ok(_) => {
+                    self.scopes.push(TypeScope::default());
+                    errors = self.check_definitions(&attrs, &types, &sigs, &vals);
+                }
+                Err(err) => errors.push(err),
+            }
+        }
+
+        if let Some(scope) = self.scopes.pop() {
+            self.types = scope.values;
+        }
+
+        self.scopes.clear();
+
+        (self.types.clone(), errors)
+    }
+
+    fn check_imports(&mut self, imports: &[ImportForm]) -> Result<()> {
+        for import in imports.iter() {
+            let module = match self.imports.get(import) {
+                Some(module) => module.clone(),
+                None => {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: import.loc(),
+                        desc: format!("module {} is not loaded", import.module),
+                    }));
+                }
+            };
+
+            let names = if import.is_qualified_only() {
+                vec![]
+            } else if import.imports_all() {
+                module
+                    .aliases
+                    .keys()
+                    .chain(module.values.keys())
+                    .cloned()
+                    .collect::<Vec<String>>()
+            } else {
+                import
+                    .defs
+                    .iter()
+                    .map(|def| def.to_string())
+                    .collect::<Vec<String>>()
+            };
+
+            let qualifier = import.qualifier_name();
+
+            for (name, t) in module.aliases.iter() {
+                let scope = self.current_scope();
+
+                if names.contains(name) {
+                    scope.aliases.insert(name.clone(), t.clone());
+                }
+
+                scope
+                    .aliases
+                    .insert(format!("{}.{}", qualifier, name), t.clone());
+            }
+
+            for (name, t) in module.values.iter() {
+                let t = match t {
+                    Type::Simple(SimpleType::Builtin(_)) => {
+                        match self.builtins.get(&format!("{}.{}", module.path, name)) {
+                            Some(sig) => Type::Fun(Box::new(sig.clone())),
+                            None => unknown_type(),
+                        }
+                    }
+                    t => t.clone(),
+                };
+
+                let scope = self.current_scope();
+
+                if names.contains(name) {
+                    scope.values.insert(name.clone(), t.clone());
+                }
+
+                scope.values.insert(format!("{}.{}", qualifier, name), t);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn lookup_name(&self, value: &SimpleValue) -> Result<Type> {
+        self.lookup_value(&value.to_string()).ok_or_else(|| {
+            Error::Semantic(SemanticError {
+                loc: value.loc(),
+                desc: format!("undefined name {}", value),
+            })
+        })
+    }
+
+    fn current_scope(&mut self) -> &mut TypeScope {
+        let idx = self.scopes.len() - 1;
+        &mut self.scopes[idx]
+    }
+
+    fn lookup_alias(&self, name: &str) -> Option<Type> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.aliases.get(name).cloned())
+    }
+
+    fn lookup_value(&self, name: &str) -> Option<Type> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.values.get(name).cloned())
+    }
+
+    fn lookup_attrs(&self, name: &str) -> Vec<String> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.attrs.get(name).cloned())
+            .unwrap_or_default()
+    }
+
+    fn define_value(&mut self, value: &SimpleValue, t: &Type) {
+        self.record_droppable(value, t);
+
+        self.current_scope()
+            .values
+            .insert(value.to_string(), t.clone());
+    }
+
+    fn record_droppable(&mut self, value: &SimpleValue, t: &Type) {
+        let droppable = ["droppable", "duplicable"]
+            .iter()
+            .any(|attr| self.has_capability(t, attr, &mut vec![]).unwrap_or(false));
+
+        if let Some(loc) = value.loc().filter(|_| droppable) {
+            self.droppable.push(loc);
+        }
+    }
+
+    pub fn expand(&self, t: &Type) -> Type {
+        let mut t = t.clone();
+        let mut seen: Vec<String> = vec![];
+
+        while let Type::Simple(SimpleType::Symbol(value))
+        | Type::Simple(SimpleType::PathSymbol(value)) = &t
+        {
+            let name = value.to_string();
+
+            if seen.contains(&name) {
+                break;
+            }
+
+            match self.lookup_alias(&name) {
+                Some(alias) => {
+                    seen.push(name);
+                    t = alias;
+                }
+                None => break,
+            }
+        }
+
+        t
+    }
+
+    pub fn accepts(&self, expected: &Type, actual: &Type) -> bool {
+        if expected.to_string() == actual.to_string() {
+            return true;
+        }
+
+        let expected = self.expand(expected);
+        let actual = self.expand(actual);
+
+        if is_unknown(&expected) || is_unknown(&actual) {
+            return true;
+        }
+
+        if expected.to_string() == actual.to_string() {
+            return true;
+        }
+
+        match (&expected, &actual) {
+            (Type::Enum(_), Type::Enum(actual_enum)) => actual_enum
+                .elements
+                .iter()
+                .all(|t| self.accepts(&expected, t)),
+            (Type::Enum(expected_enum), _) => expected_enum
+                .elements
+                .iter()
+                .any(|t| self.accepts(t, &actual)),
+            (Type::Pair(expected_pair), Type::Pair(actual_pair)) => {
+                self.accepts(&expected_pair.first, &actual_pair.first)
+                    && self.accepts(&expected_pair.second, &actual_pair.second)
+            }
+            (Type::List(expected_list), Type::List(actual_list)) => {
+                self.accepts_elements(&expected_list.elements, &actual_list.elements)
+            }
+            (Type::Arr(expected_arr), Type::Arr(actual_arr)) => {
+                self.accepts_elements(&expected_arr.elements, &actual_arr.elements)
+            }
+            (Type::Vec(expected_vec), Type::Vec(actual_vec)) => {
+                self.accepts_elements(&expected_vec.elements, &actual_vec.elements)
+            }
+            (Type::Map(expected_map), Type::Map(actual_map)) => {
+                self.accepts_elements(&map_entry_types(expected_map), &map_entry_types(actual_map))
+            }
+            (Type::Fun(expected_fun), Type::Fun(actual_fun)) => {
+                expected_fun.parameters.len() == actual_fun.parameters.len()
+                    && expected_fun
+                        .parameters
+                        .iter()
+                        .zip(actual_fun.parameters.iter())
+                        .all(|(e, a)| self.accepts(a, e))
+                    && self.accepts(&expected_fun.body, &actual_fun.body)
+            }
+            _ => false,
+        }
+    }
+
+    fn accepts_elements(&self, expected: &[Type], actual: &[Type]) -> bool {
+        if expected.len() == 1 {
+            actual.iter().all(|t| self.accepts(&expected[0], t))
+        } else {
+            expected.len() == actual.len()
+                && expected
+                    .iter()
+                    .zip(actual.iter())
+                    .all(|(e, a)| self.accepts(e, a))
+        }
+    }
+
+    pub fn has_capability(&self, t: &Type, attr: &str, seen: &mut Vec<String>) -> Result<bool> {
+        if let Type::Simple(SimpleType::Symbol(value))
+        | Type::Simple(SimpleType::PathSymbol(value)) = t
+        {
+            let name = value.to_string();
+
+            if seen.contains(&name) || self.lookup_attrs(&name).iter().any(|a| a == attr) {
+                return Ok(true);
+            }
+
+            seen.push(name.clone());
+
+            return match self.lookup_alias(&name) {
+                Some(alias) => self.has_capability(&alias, attr, seen),
+                None if self.type_parameters.contains(&name) => Ok(false),
+                None => Err(Error::Semantic(SemanticError {
+                    loc: None,
+                    desc: format!("undefined type {}", name),
+                })),
+            };
+        }
+
+        let res = match t {
+            Type::Simple(simple) => match simple {
+                SimpleType::Ignore(_) => {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: None,
+                        desc: format!("cannot tell if unknown type {} is {}", t, attr),
+                    }));
+                }
+                SimpleType::Empty(_)
+                | SimpleType::Atomic(_)
+                | SimpleType::UInt(_)
+                | SimpleType::Int(_)
+                | SimpleType::Float(_)
+                | SimpleType::Size(_)
+                | SimpleType::Char(_)
+                | SimpleType::Type(_) => true,
+                SimpleType::String(_) | SimpleType::Path(_) => attr == "droppable",
+                _ => false,
+            },
+            Type::Enum(enum_type) => self.all_have_capability(&enum_type.elements, attr, seen)?,
+            Type::Pair(pair_type) => {
+                self.has_capability(&pair_type.first, attr, seen)?
+                    && self.has_capability(&pair_type.second, attr, seen)?
+            }
+            Type::List(list_type) => self.all_have_capability(&list_type.elements, attr, seen)?,
+            Type::Arr(arr_type) => self.all_have_capability(&arr_type.elements, attr, seen)?,
+            Type::Vec(vec_type) => self.all_have_capability(&vec_type.elements, attr, seen)?,
+            Type::Map(map_type) => {
+                self.all_have_capability(&map_entry_types(map_type), attr, seen)?
+            }
+            Type::Fun(_) => false,
+        };
+
+        Ok(res)
+    }
+
+    fn all_have_capability(
+        &self,
+        types: &[Type],
+        attr: &str,
+        seen: &mut Vec<String>,
+    ) -> Result<bool> {
+        for t in types.iter() {
+            if !self.has_capability(t, attr, seen)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn expect(&self, expected: &Type, actual: Type, loc: Option<Loc>) -> Result<Type> {
+        if !self.accepts(expected, &actual) {
+            return Err(mismatch(expected, &actual, loc));
+        }
+
+        if is_unknown(expected) {
+            Ok(actual)
+        } else {
+            Ok(expected.clone())
+        }
+    }
+
+    fn check_definitions(
+        &mut self,
+        attrs: &[AttrsForm],
+        types: &[TypeForm],
+        sigs: &[SigForm],
+        vals: &[ValForm],
+    ) -> Vec<Error> {
+        let mut errors = vec![];
+
+        for form in attrs.iter().filter(|form| form.is_type_attributes()) {
+            if let Err(err) = check_attrs_values(form) {
+                errors.push(err);
+                continue;
+            }
+
+            let names = attrs_names(form);
+
+            self.current_scope()
+                .attrs
+                .entry(form.name.to_string())
+                .or_default()
+                .extend(names);
+        }
+
+        for form in types.iter() {
+            self.current_scope()
+                .aliases
+                .insert(form.name.to_string(), *form.value.clone());
+        }
+
+        for sig in sigs.iter() {
+            if !vals
+                .iter()
+                .any(|val| val.name.to_string() == sig.name.to_string())
+            {
+                errors.push(Error::Semantic(SemanticError {
+                    loc: sig.loc(),
+                    desc: format!("sig {} has no val", sig.name),
+                }));
+            }
+        }
+
+        for val in vals.iter() {
+            if !sigs
+                .iter()
+                .any(|sig| sig.name.to_string() == val.name.to_string())
+            {
+                errors.push(Error::Semantic(SemanticError {
+                    loc: val.loc(),
+                    desc: format!("val {} has no sig", val.name),
+                }));
+            }
+        }
+
+        for sig in sigs.iter() {
+            let prev = self
+                .current_scope()
+                .values
+                .get(&sig.name.to_string())
+                .cloned();
+
+            match prev {
+                Some(prev) if prev.to_string() != sig.value.to_string() => {
+                    errors.push(Error::Semantic(SemanticError {
+                        loc: sig.loc(),
+                        desc: format!(
+                            "conflicting sig {}: expected type {}, found type {}",
+                            sig.name, prev, sig.value
+                        ),
+                    }));
+                }
+                Some(_) => {}
+                None => self.define_value(&sig.name, &sig.value),
+            }
+        }
+
+        let builtins = vals.iter().filter(|val| val.is_builtin());
+        let others = vals.iter().filter(|val| !val.is_builtin());
+
+        for val in builtins.chain(others) {
+            let expected = match self.current_scope().values.get(&val.name.to_string()) {
+                Some(expected) => expected.clone(),
+                None => continue,
+            };
+
+            self.record_droppable(&val.name, &expected);
+
+            if let Err(err) = self.check_val(val, &expected) {
+                errors.push(err);
+            }
+        }
+
+        errors
+    }
+
+    fn check_val(&mut self, form: &ValForm, expected: &Type) -> Result<Type> {
+        match &form.value {
+            ValFormValue::Empty(value)
+            | ValFormValue::Panic(value)
+            | ValFormValue::Atomic(value)
+            | ValFormValue::ValueSymbol(value) => self.check_simple(value, expected),
+            ValFormValue::Builtin(_) => self.check_builtin(form, expected),
+            ValFormValue::PairForm(form) => self.check_pair(form, expected),
+            ValFormValue::FunForm(form) => self.check_fun(form, expected),
+            ValFormValue::LetForm(form) => self.check_let(form, expected),
+            ValFormValue::AppForm(form) => self.check_app(form, expected),
+            ValFormValue::MemForm(form) => self.check_mem(form, expected),
+            ValFormValue::LinearForm(form) => self.check_linear(form, expected),
+            ValFormValue::CastForm(form) => self.check_cast(form, expected),
+            ValFormValue::CaseForm(form) => self.check_case(form, expected),
+        }
+    }
+
+    fn check_builtin(&mut self, form: &ValForm, expected: &Type) -> Result<Type> {
+        let name = format!("{}.{}", self.module, form.name);
+
+        let actual = match self.builtins.get(&name) {
+            Some(sig) => Type::Fun(Box::new(sig.clone())),
+            None => {
+                return Err(Error::Semantic(SemanticError {
+                    loc: form.loc(),
+                    desc: format!("builtin {} has no registered implementation", name),
+                }));
+            }
+        };
+
+        if let Type::Simple(SimpleType::Builtin(_)) = self.expand(expected) {
+            self.define_value(&form.name, &actual);
+
+            return Ok(actual);
+        }
+
+        if !self.accepts(expected, &actual) {
+            return Err(Error::Semantic(SemanticError {
+                loc: form.loc(),
+                desc: format!(
+                    "builtin {} has type {}, expected type {}",
+                    name, actual, expected
+                ),
+            }));
+        }
+
+        Ok(expected.clone())
+    }
+
+    fn check_simple(&mut self, value: &SimpleValue, expected: &Type) -> Result<Type> {
+        let actual = match value {
+            SimpleValue::Panic(_) => return Ok(expected.clone()),
+            SimpleValue::Empty(_) => simple_type("Empty"),
+            SimpleValue::Atomic(_) => literal_type(value),
+            SimpleValue::ValueSymbol(_) | SimpleValue::ValuePathSymbol(_) => {
+                self.lookup_name(value)?
+            }
+            SimpleValue::TypeKeyword(_)
+            | SimpleValue::TypeSymbol(_)
+            | SimpleValue::TypePathSymbol(_) => simple_type("Type"),
+            SimpleValue::ValueKeyword(_) if value.to_string() == "atomic" => simple_type("Atomic"),
+            SimpleValue::Ignore(_) | SimpleValue::ValueKeyword(_) => unknown_type(),
+        };
+
+        self.expect(expected, actual, value.loc())
+    }
+
+    fn bind_parameter(&mut self, param: &FunFormParameter, t: &Type) {
+        match param {
+            FunFormParameter::Empty(_) => {}
+            FunFormParameter::ValueSymbol(value) => self.define_value(value, t),
+            FunFormParameter::PairForm(form) => self.bind_pair(form, t),
+            FunFormParameter::ListForm(form) => {
+                let elements = match self.expand(t) {
+                    Type::List(list_type) => list_type.elements,
+                    _ => vec![],
+                };
+
+                for (idx, value) in form.values.iter().enumerate() {
+                    let t = element_type(&elements, idx, form.values.len());
+
+                    match value {
+                        ListFormValue::ValueSymbol(value) => self.define_value(value, &t),
+                        ListFormValue::PairForm(form) => self.bind_pair(form, &t),
+                        _ => {}
+                    }
+                }
+            }
+            FunFormParameter::ArrForm(form) => {
+                let elements = match self.expand(t) {
+                    Type::Arr(arr_type) => arr_type.elements,
+                    _ => vec![],
+                };
+
+                for (idx, value) in form.values.iter().enumerate() {
+                    let t = element_type(&elements, idx, form.values.len());
+
+                    match value {
+                        ArrFormValue::ValueSymbol(value) => self.define_value(value, &t),
+                        ArrFormValue::PairForm(form) => self.bind_pair(form, &t),
+                        _ => {}
+                    }
+                }
+            }
+            FunFormParameter::VecForm(form) => {
+                let elements = match self.expand(t) {
+                    Type::Vec(vec_type) => vec_type.elements,
+                    _ => vec![],
+                };
+
+                for (idx, value) in form.values.iter().enumerate() {
+                    let t = element_type(&elements, idx, form.values.len());
+
+                    match value {
+                        VecFormValue::ValueSymbol(value) => self.define_value(value, &t),
+                        VecFormValue::PairForm(form) => self.bind_pair(form, &t),
+                        _ => {}
+                    }
+                }
+            }
+            FunFormParameter::MapForm(form) => {
+                let entries = match self.expand(t) {
+                    Type::Map(map_type) => map_entry_types(&map_type),
+                    _ => vec![],
+                };
+
+                for (idx, entry) in form.entries.iter().enumerate() {
+                    if let MapFormEntry::PairForm(pair) = entry {
+                        let t = element_type(&entries, idx, form.entries.len());
+                        self.bind_pair(pair, &t);
+                    }
+                }
+            }
+        }
+    }
+
+    fn bind_pair(&mut self, form: &PairForm, t: &Type) {
+        let (first, second) = match self.expand(t) {
+            Type::Pair(pair_type) => (*pair_type.first, *pair_type.second),
+            _ => (unknown_type(), unknown_type()),
+        };
+
+        for (value, t) in [(&form.first, first), (&form.second, second)].iter() {
+            match value {
+                PairFormValue::ValueSymbol(value) => self.define_value(value, t),
+                PairFormValue::PairForm(form) => self.bind_pair(form, t),
+                _ => {}
+            }
+        }
+    }
+
+    fn check_fun(&mut self, form: &FunForm, expected: &Type) -> Result<Type> {
+        let fun_type = match self.expand(expected) {
+            Type::Fun(fun_type) => {
+                if fun_type.parameters.len() != form.parameters.len() {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: form.loc(),
+                        desc: format!(
+                            "expected type {}, found a function of {} parameters",
+                            expected,
+                            form.parameters.len()
+                        ),
+                    }));
+                }
+
+                *fun_type
+            }
+            _ => {
+                let mut fun_type = FunType::new();
+                fun_type.parameters = form.parameters.iter().map(|_| unknown_type()).collect();
+                fun_type.body = Box::new(unknown_type());
+
+                fun_type
+            }
+        };
+
+        let mut scope = TypeScope::default();
+
+        for param in fun_parameters(form) {
+            scope.values.insert(param.to_string(), unknown_type());
+        }
+
+        self.scopes.push(scope);
+
+        for (param, t) in form.parameters.iter().zip(fun_type.parameters.iter()) {
+            self.bind_parameter(param, t);
+        }
+
+        let body_type = &fun_type.body;
+
+        let res = match &form.body {
+            FunFormBody::Empty(value)
+            | FunFormBody::Panic(value)
+            | FunFormBody::Atomic(value)
+            | FunFormBody::ValueSymbol(value)
+            | FunFormBody::ValuePathSymbol(value) => self.check_simple(value, body_type),
+            FunFormBody::MapForm(form) => self.check_map(form, body_type),
+            FunFormBody::VecForm(form) => self.check_vec(form, body_type),
+            FunFormBody::ArrForm(form) => self.check_arr(form, body_type),
+            FunFormBody::ListForm(form) => self.check_list(form, body_type),
+            FunFormBody::PairForm(form) => self.check_pair(form, body_type),
+            FunFormBody::AppForm(form) => self.check_app(form, body_type),
+            FunFormBody::MemForm(form) => self.check_mem(form, body_type),
+            FunFormBody::LinearForm(form) => self.check_linear(form, body_type),
+            FunFormBody::CastForm(form) => self.check_cast(form, body_type),
+            FunFormBody::LetForm(form) => self.check_let(form, body_type),
+            FunFormBody::CaseForm(form) => self.check_case(form, body_type),
+            FunFormBody::FunForm(form) => self.check_fun(form, body_type),
+        };
+
+        self.scopes.pop();
+
+        let mut actual = fun_type.clone();
+        actual.body = Box::new(res?);
+
+        self.expect(expected, Type::Fun(Box::new(actual)), form.loc())
+    }
+
+    fn check_app_value(&mut self, value: &AppFormValue, expected: &Type) -> Result<Type> {
+        match value {
+            AppFormValue::Ignore(value)
+            | AppFormValue::Empty(value)
+            | AppFormValue::Panic(value)
+            | AppFormValue::Atomic(value)
+            | AppFormValue::ValueSymbol(value)
+            | AppFormValue::ValuePathSymbol(value) => self.check_simple(value, expected),
+            AppFormValue::PairForm(form) => self.check_pair(form, expected),
+            AppFormValue::FunForm(form) => self.check_fun(form, expected),
+            AppFormValue::LetForm(form) => self.check_let(form, expected),
+            AppFormValue::CaseForm(form) => self.check_case(form, expected),
+            AppFormValue::AppForm(form) => self.check_app(form, expected),
+            AppFormValue::MemForm(form) => self.check_mem(form, expected),
+            AppFormValue::LinearForm(form) => self.check_linear(form, expected),
+            AppFormValue::CastForm(form) => self.check_cast(form, expected),
+        }
+    }
+
+    fn check_app(&mut self, form: &AppForm, expected: &Type) -> Result<Type> {
+        let fun_type = match &form.name {
+            SimpleValue::ValueSymbol(_) | SimpleValue::ValuePathSymbol(_) => {
+                self.lookup_name(&form.name)?
+            }
+            _ => unknown_type(),
+        };
+
+        let fun_type = match self.expand(&fun_type) {
+            Type::Fun(fun_type) => *fun_type,
+            t if is_unknown(&t) => {
+                for variable in form.variables.iter() {
+                    self.check_app_value(variable, &unknown_type())?;
+                }
+
+                return match form.name {
+                    SimpleValue::Panic(_) => Ok(expected.clone()),
+                    _ => Ok(unknown_type()),
+                };
+            }
+            _ => {
+                return Err(Error::Semantic(SemanticError {
+                    loc: form.name.loc(),
+                    desc: format!(
+                        "cannot apply {} of type {}, expected a function",
+                        form.name, fun_type
+                    ),
+                }));
+            }
+        };
+
+        if fun_type.parameters.len() != form.variables.len() {
+            return Err(Error::Semantic(SemanticError {
+                loc: form.loc(),
+                desc: format!(
+                    "expected {} arguments for {} of type {}, found {}",
+                    fun_type.parameters.len(),
+                    form.name,
+                    fun_type,
+                    form.variables.len()
+                ),
+            }));
+        }
+
+        for (variable, t) in form.variables.iter().zip(fun_type.parameters.iter()) {
+            self.check_app_value(variable, t)?;
+        }
+
+        self.expect(expected, *fun_type.body, form.loc())
+    }
+
+    fn check_mem(&mut self, form: &MemForm, expected: &Type) -> Result<Type> {
+        let operands = match form.kind {
+            MemFormKind::Ref | MemFormKind::Size => vec![unknown_type()],
+            MemFormKind::Deref => vec![simple_type("Ref")],
+            MemFormKind::Load => vec![simple_type("Pointer")],
+            MemFormKind::Store => vec![simple_type("Pointer"), simple_type("Mem")],
+        };
+
+        for (value, t) in form.values.iter().zip(operands.iter()) {
+            self.check_app_value(value, t)?;
+        }
+
+        let actual = match form.kind {
+            MemFormKind::Ref => simple_type("Ref"),
+            MemFormKind::Deref => return Ok(expected.clone()),
+            MemFormKind::Load => simple_type("Mem"),
+            MemFormKind::Store => simple_type("Empty"),
+            MemFormKind::Size => simple_type("Size"),
+        };
+
+        self.expect(expected, actual, form.loc())
+    }
+
+    fn check_linear(&mut self, form: &LinearForm, expected: &Type) -> Result<Type> {
+        let t = self.check_app_value(&form.value, &unknown_type())?;
+
+        let capability = match form.kind {
+            LinearFormKind::Dup => "duplicable",
+            LinearFormKind::Drop => "droppable",
+        };
+
+        let capable =
+            self.has_capability(&t, capability, &mut vec![])
+                .map_err(|err| match err {
+                    Error::Semantic(err) => Error::Semantic(SemanticError {
+                        loc: form.loc(),
+                        desc: format!("cannot {} {}: {}", form.kind, form.value, err.desc),
+                    }),
+                    err => err,
+                })?;
+
+        if !capable {
+            return Err(Error::Semantic(SemanticError {
+                loc: form.loc(),
+                desc: format!(
+                    "cannot {} {}: type {} is not {}",
+                    form.kind, form.value, t, capability
+                ),
+            }));
+        }
+
+        let actual = match form.kind {
+            LinearFormKind::Dup => {
+                let mut pair_type = PairType::new();
+                pair_type.first = Box::new(t.clone());
+                pair_type.second = Box::new(t);
+
+                Type::Pair(Box::new(pair_type))
+            }
+            LinearFormKind::Drop => simple_type("Empty"),
+        };
+
+        self.expect(expected, actual, form.loc())
+    }
+
+    fn check_cast(&mut self, form: &CastForm, expected: &Type) -> Result<Type> {
+        let actual = self.check_app_value(&form.value, &unknown_type())?;
+
+        let from = self.expand(&actual);
+        let to = self.expand(&form.target);
+
+        if !is_unknown(&from) && from.to_string() != to.to_string() {
+            let safety = match (CastType::from_type(&from), CastType::from_type(&to)) {
+                (Some(from), Some(to)) => cast_safety(from, to),
+                _ => None,
+            };
+
+            match safety {
+                Some(CastSafety::Lossless) => {}
+                Some(CastSafety::Lossy) => self.warnings.push(SemanticError {
+                    loc: form.loc(),
+                    desc: format!("lossy cast from {} to {}", actual, form.target),
+                }),
+                Some(CastSafety::Unsafe) => self.warnings.push(SemanticError {
+                    loc: form.loc(),
+                    desc: format!("unsafe cast from {} to {}", actual, form.target),
+                }),
+                None => {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: form.loc(),
+                        desc: format!("invalid cast from {} to {}", actual, form.target),
+                    }));
+                }
+            }
+        }
+
+        self.expect(expected, form.target.clone(), form.loc())
+    }
+
+    fn check_let(&mut self, form: &LetForm, expected: &Type) -> Result<Type> {
+        let mut imports = vec![];
+        let mut attrs = vec![];
+        let mut types = vec![];
+        let mut sigs = vec![];
+        let mut vals = vec![];
+
+        for entry in form.entries.iter() {
+            match entry {
+                LetFormEntry::ImportForm(form) => imports.push(*form.clone()),
+                LetFormEntry::AttrsForm(form) => attrs.push(*form.clone()),
+                LetFormEntry::TypeForm(form) => types.push(*form.clone()),
+                LetFormEntry::SigForm(form) => sigs.push(*form.clone()),
+                LetFormEntry::ValForm(form) => vals.push(*form.clone()),
+                _ => {}
+            }
+        }
+
+        self.scopes.push(TypeScope::default());
+
+        let res = self.check_imports(&imports).and_then(|_| {
+            self.scopes.push(TypeScope::default());
+
+            let errors = self.check_definitions(&attrs, &types, &sigs, &vals);
+
+            let res = match errors.into_iter().next() {
+                Some(err) => Err(err),
+                None => self.check_app_value(&form.value, expected),
+            };
+
+            self.scopes.pop();
+
+            res
+        });
+
+        self.scopes.pop();
+
+        res
+    }
+
+    fn check_case(&mut self, form: &CaseForm, expected: &Type) -> Result<Type> {
+        let unknown = unknown_type();
+
+        let variable_type = match &form.variable {
+            CaseFormVariable::Empty(value)
+            | CaseFormVariable::Atomic(value)
+            | CaseFormVariable::ValueSymbol(value) => self.check_simple(value, &unknown)?,
+            CaseFormVariable::AppForm(form) => self.check_app(form, &unknown)?,
+            CaseFormVariable::MemForm(form) => self.check_mem(form, &unknown)?,
+            CaseFormVariable::LinearForm(form) => self.check_linear(form, &unknown)?,
+            CaseFormVariable::CastForm(form) => self.check_cast(form, &unknown)?,
+            CaseFormVariable::LetForm(form) => self.check_let(form, &unknown)?,
+            CaseFormVariable::CaseForm(form) => self.check_case(form, &unknown)?,
+        };
+
+        let mut result = expected.clone();
+        let mut case_types = vec![];
+
+        for case_match in form.matches.iter() {
+            let case_type = match &case_match.case {
+                CaseFormMatchCase::ValueKeyword(_) => variable_type.clone(),
+                CaseFormMatchCase::Empty(value)
+                | CaseFormMatchCase::Atomic(value)
+                | CaseFormMatchCase::ValueSymbol(value)
+                | CaseFormMatchCase::ValuePathSymbol(value) => {
+                    self.check_simple(value, &unknown)?
+                }
+                CaseFormMatchCase::TypeKeyword(value)
+                | CaseFormMatchCase::TypeSymbol(value)
+                | CaseFormMatchCase::TypePathSymbol(value) => {
+                    Type::from_simple_value(value).unwrap_or_else(|_| unknown_type())
+                }
+            };
+
+            if !self.accepts(&variable_type, &case_type) {
+                return Err(mismatch(&variable_type, &case_type, case_match.case.loc()));
+            }
+
+            case_types.push(case_type.clone());
+
+            let action_type = match &case_match.action {
+                CaseFormMatchAction::ValueKeyword(value) if value.to_string() == "id" => {
+                    self.expect(&result, case_type, value.loc())?
+                }
+                CaseFormMatchAction::Ignore(value)
+                | CaseFormMatchAction::Empty(value)
+                | CaseFormMatchAction::Panic(value)
+                | CaseFormMatchAction::Atomic(value)
+                | CaseFormMatchAction::ValueKeyword(value)
+                | CaseFormMatchAction::ValueSymbol(value)
+                | CaseFormMatchAction::ValuePathSymbol(value) => {
+                    self.check_simple(value, &result)?
+                }
+                CaseFormMatchAction::PairForm(form) => self.check_pair(form, &result)?,
+                CaseFormMatchAction::FunForm(form) => {
+                    let mut fun_type = FunType::new();
+                    fun_type.parameters = vec![case_type];
+                    fun_type.body = Box::new(result.clone());
+
+                    match self.check_fun(form, &Type::Fun(Box::new(fun_type)))? {
+                        Type::Fun(fun_type) => *fun_type.body,
+                        _ => unknown_type(),
+                    }
+                }
+                CaseFormMatchAction::LetForm(form) => self.check_let(form, &result)?,
+            };
+
+            if is_unknown(&result) {
+                result = action_type;
+            }
+        }
+
+        if !self.skip_coverage {
+            self.check_case_coverage(form, &variable_type, &case_types)?;
+        }
+
+        Ok(result)
+    }
+
+    fn covered_variant(
+        &self,
+        variants: &[Type],
+        case: &CaseFormMatchCase,
+        case_type: &Type,
+    ) -> Option<String> {
+        let variant = variants
+            .iter()
+            .find(|v| v.to_string() == case_type.to_string())?;
+
+        match case {
+            CaseFormMatchCase::Atomic(_) | CaseFormMatchCase::ValueKeyword(_) => None,
+            CaseFormMatchCase::ValueSymbol(_) | CaseFormMatchCase::ValuePathSymbol(_) => {
+                match self.expand(variant) {
+                    Type::Simple(SimpleType::Atomic(_)) => Some(variant.to_string()),
+                    _ => None,
+                }
+            }
+            _ => Some(variant.to_string()),
+        }
+    }
+
+    fn check_case_coverage(
+        &self,
+        form: &CaseForm,
+        variable_type: &Type,
+        case_types: &[Type],
+    ) -> Result<()> {
+        let variants = match self.expand(variable_type) {
+            Type::Enum(enum_type) => enum_type.elements,
+            _ => vec![],
+        };
+
+        let mut patterns: Vec<String> = vec![];
+        let mut covered: Vec<String> = vec![];
+        let mut catch_all: Option<String> = None;
+        let mut is_complete = true;
+
+        for (case_match, case_type) in form.matches.iter().zip(case_types.iter()) {
+            let pattern = case_match.case.to_string();
+
+            let unreachable = if let Some(catch_all) = &catch_all {
+                Some(format!("already covered by {}", catch_all))
+            } else if patterns.contains(&pattern) {
+                Some(format!("{} is already matched", pattern))
+            } else if case_match.case.is_catch_all()
+                && !variants.is_empty()
+                && variants.iter().all(|v| covered.contains(&v.to_string()))
+            {
+                Some(format!(
+                    "all variants of {} are already covered",
+                    variable_type
+                ))
+            } else {
+                None
+            };
+
+            if let Some(reason) = unreachable {
+                return Err(Error::Semantic(SemanticError {
+                    loc: case_match.loc(),
+                    desc: format!("unreachable match arm {}: {}", pattern, reason),
+                }));
+            }
+
+            patterns.push(pattern.clone());
+
+            if case_match.case.is_catch_all() {
+                catch_all = Some(pattern);
+                continue;
+            }
+
+            if is_unknown(case_type) {
+                is_complete = false;
+            }
+
+            if let Some(variant) = self.covered_variant(&variants, &case_match.case, case_type) {
+                if covered.contains(&variant) {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: case_match.loc(),
+                        desc: format!(
+                            "unreachable match arm {}: variant {} is already covered",
+                            pattern, variant
+                        ),
+                    }));
+                }
+
+                covered.push(variant);
+            }
+        }
+
+        if catch_all.is_some() || !is_complete {
+            return Ok(());
+        }
+
+        let missing = variants
+            .iter()
+            .map(|v| v.to_string())
+            .filter(|v| !covered.contains(v))
+            .collect::<Vec<String>>();
+
+        if !missing.is_empty() {
+            return Err(Error::Semantic(SemanticError {
+                loc: form.loc(),
+                desc: format!(
+                    "non-exhaustive case over {}: missing variants {}",
+                    variable_type,
+                    missing.join(", ")
+                ),
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn check_pair_value(&mut self, value: &PairFormValue, expected: &Type) -> Result<Type> {
+        match value {
+            PairFormValue::Ignore(value)
+            | PairFormValue::Empty(value)
+            | PairFormValue::Panic(value)
+            | PairFormValue::Atomic(value)
+            | PairFormValue::ValueKeyword(value)
+            | PairFormValue::TypeKeyword(value)
+            | PairFormValue::ValueSymbol(value)
+            | PairFormValue::TypeSymbol(value)
+            | PairFormValue::ValuePathSymbol(value)
+            | PairFormValue::TypePathSymbol(value) => self.check_simple(value, expected),
+            PairFormValue::Type(form) => self.expect(expected, simple_type("Type"), form.loc()),
+            PairFormValue::MapForm(form) => self.check_map(form, expected),
+            PairFormValue::VecForm(form) => self.check_vec(form, expected),
+            PairFormValue::ArrForm(form) => self.check_arr(form, expected),
+            PairFormValue::ListForm(form) => self.check_list(form, expected),
+            PairFormValue::PairForm(form) => self.check_pair(form, expected),
+            PairFormValue::FunForm(form) => self.check_fun(form, expected),
+            PairFormValue::CaseForm(form) => self.check_case(form, expected),
+            PairFormValue::LetForm(form) => self.check_let(form, expected),
+            PairFormValue::AppForm(form) => self.check_app(form, expected),
+        }
+    }
+
+    fn check_pair(&mut self, form: &PairForm, expected: &Type) -> Result<Type> {
+        let (first, second) = match self.expand(expected) {
+            Type::Pair(pair_type) => (*pair_type.first, *pair_type.second),
+            _ => (unknown_type(), unknown_type()),
+        };
+
+        let mut pair_type = PairType::new();
+        pair_type.first = Box::new(self.check_pair_value(&form.first, &first)?);
+        pair_type.second = Box::new(self.check_pair_value(&form.second, &second)?);
+
+        self.expect(expected, Type::Pair(Box::new(pair_type)), form.loc())
+    }
+
+    fn check_list_value(&mut self, value: &ListFormValue, expected: &Type) -> Result<Type> {
+        match value {
+            ListFormValue::Ignore(value)
+            | ListFormValue::Empty(value)
+            | ListFormValue::Panic(value)
+            | ListFormValue::Atomic(value)
+            | ListFormValue::ValueKeyword(value)
+            | ListFormValue::TypeKeyword(value)
+            | ListFormValue::ValueSymbol(value)
+            | ListFormValue::TypeSymbol(value)
+            | ListFormValue::ValuePathSymbol(value)
+            | ListFormValue::TypePathSymbol(value) => self.check_simple(value, expected),
+            ListFormValue::Type(form) => self.expect(expected, simple_type("Type"), form.loc()),
+            ListFormValue::FunForm(form) => self.check_fun(form, expected),
+            ListFormValue::CaseForm(form) => self.check_case(form, expected),
+            ListFormValue::LetForm(form) => self.check_let(form, expected),
+            ListFormValue::AppForm(form) => self.check_app(form, expected),
+            ListFormValue::PairForm(form) => self.check_pair(form, expected),
+            ListFormValue::ArrForm(form) => self.check_arr(form, expected),
+            ListFormValue::VecForm(form) => self.check_vec(form, expected),
+            ListFormValue::MapForm(form) => self.check_map(form, expected),
+            ListFormValue::ListForm(form) => self.check_list(form, expected),
+        }
+    }
+
+    fn check_list(&mut self, form: &ListForm, expected: &Type) -> Result<Type> {
+        let elements = match self.expand(expected) {
+            Type::List(list_type) => list_type.elements,
+            _ => vec![],
+        };
+
+        let mut types = vec![];
+
+        for (idx, value) in form.values.iter().enumerate() {
+            let t = element_type(&elements, idx, form.values.len());
+            types.push(self.check_list_value(value, &t)?);
+        }
+
+        let mut list_type = ListType::new();
+        list_type.elements = collapse_elements(types);
+
+        self.expect(expected, Type::List(Box::new(list_type)), form.loc())
+    }
+
+    fn check_arr_value(&mut self, value: &ArrFormValue, expected: &Type) -> Result<Type> {
+        match value {
+            ArrFormValue::Ignore(value)
+            | ArrFormValue::Empty(value)
+            | ArrFormValue::Panic(value)
+            | ArrFormValue::Atomic(value)
+            | ArrFormValue::ValueKeyword(value)
+            | ArrFormValue::TypeKeyword(value)
+            | ArrFormValue::ValueSymbol(value)
+            | ArrFormValue::TypeSymbol(value)
+            | ArrFormValue::ValuePathSymbol(value)
+            | ArrFormValue::TypePathSymbol(value) => self.check_simple(value, expected),
+            ArrFormValue::Type(form) => self.expect(expected, simple_type("Type"), form.loc()),
+            ArrFormValue::FunForm(form) => self.check_fun(form, expected),
+            ArrFormValue::CaseForm(form) => self.check_case(form, expected),
+            ArrFormValue::LetForm(form) => self.check_let(form, expected),
+            ArrFormValue::AppForm(form) => self.check_app(form, expected),
+            ArrFormValue::PairForm(form) => self.check_pair(form, expected),
+            ArrFormValue::ListForm(form) => self.check_list(form, expected),
+            ArrFormValue::VecForm(form) => self.check_vec(form, expected),
+            ArrFormValue::MapForm(form) => self.check_map(form, expected),
+            ArrFormValue::ArrForm(form) => self.check_arr(form, expected),
+        }
+    }
+
+    fn check_arr(&mut self, form: &ArrForm, expected: &Type) -> Result<Type> {
+        let elements = match self.expand(expected) {
+            Type::Arr(arr_type) => arr_type.elements,
+            _ => vec![],
+        };
+
+        let mut types = vec![];
+
+        for (idx, value) in form.values.iter().enumerate() {
+            let t = element_type(&elements, idx, form.values.len());
+            types.push(self.check_arr_value(value, &t)?);
+        }
+
+        let mut arr_type = ArrType::new();
+        arr_type.elements = collapse_elements(types);
+
+        self.expect(expected, Type::Arr(Box::new(arr_type)), form.loc())
+    }
+
+    fn check_vec_value(&mut self, value: &VecFormValue, expected: &Type) -> Result<Type> {
+        match value {
+            VecFormValue::Ignore(value)
+            | VecFormValue::Empty(value)
+            | VecFormValue::Panic(value)
+            | VecFormValue::Atomic(value)
+            | VecFormValue::ValueKeyword(value)
+            | VecFormValue::TypeKeyword(value)
+            | VecFormValue::ValueSymbol(value)
+            | VecFormValue::TypeSymbol(value)
+            | VecFormValue::ValuePathSymbol(value)
+            | VecFormValue::TypePathSymbol(value) => self.check_simple(value, expected),
+            VecFormValue::Type(form) => self.expect(expected, simple_type("Type"), form.loc()),
+            VecFormValue::FunForm(form) => self.check_fun(form, expected),
+            VecFormValue::CaseForm(form) => self.check_case(form, expected),
+            VecFormValue::LetForm(form) => self.check_let(form, expected),
+            VecFormValue::AppForm(form) => self.check_app(form, expected),
+            VecFormValue::PairForm(form) => self.check_pair(form, expected),
+            VecFormValue::ListForm(form) => self.check_list(form, expected),
+            VecFormValue::ArrForm(form) => self.check_arr(form, expected),
+            VecFormValue::MapForm(form) => self.check_map(form, expected),
+            VecFormValue::VecForm(form) => self.check_vec(form, expected),
+        }
+    }
+
+    fn check_vec(&mut self, form: &VecForm, expected: &Type) -> Result<Type> {
+        let elements = match self.expand(expected) {
+            Type::Vec(vec_type) => vec_type.elements,
+            _ => vec![],
+        };
+
+        let mut types = vec![];
+
+        for (idx, value) in form.values.iter().enumerate() {
+            let t = element_type(&elements, idx, form.values.len());
+            types.push(self.check_vec_value(value, &t)?);
+        }
+
+        let mut vec_type = VecType::new();
+        vec_type.elements = collapse_elements(types);
+
+        self.expect(expected, Type::Vec(Box::new(vec_type)), form.loc())
+    }
+
+    fn check_map(&mut self, form: &MapForm, expected: &Type) -> Result<Type> {
+        let entries = match self.expand(expected) {
+            Type::Map(map_type) => map_entry_types(&map_type),
+            _ => vec![],
+        };
+
+        let mut types = vec![];
+
+        for (idx, entry) in form.entries.iter().enumerate() {
+            if let MapFormEntry::PairForm(pair) = entry {
+                let t = element_type(&entries, idx, form.entries.len());
+                types.push(self.check_pair(pair, &t)?);
+            }
+        }
+
+        let mut map_type = MapType::new();
+        map_type.entries = collapse_elements(types)
+            .into_iter()
+            .filter_map(|t| match t {
+                Type::Pair(pair_type) => Some(*pair_type),
+                _ => None,
+            })
+            .collect::<Vec<PairType>>();
+
+        self.expect(expected, Type::Map(Box::new(map_type)), form.loc())
+    }
+}
+
+fn mismatch(expected: &Type, actual: &Type, loc: Option<Loc>) -> Error {
+    Error::Semantic(SemanticError {
+        loc,
+        desc: format!("expected type {}, found type {}", expected, actual),
+    })
+}
+
+fn attrs_names(form: &AttrsForm) -> Vec<String> {
+    let mut names = vec![];
+
+    for value in form.values.iter() {
+        match value {
+            AttrsFormValue::ValueSymbol(value) => names.push(value.to_string()),
+            AttrsFormValue::Map(map) => {
+                for entry in map.entries.iter() {
+                    if let MapFormEntry::PairForm(pair) = entry {
+                        if let PairFormValue::ValueSymbol(value) = &pair.first {
+                            names.push(value.to_string());
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+fn check_attrs_values(form: &AttrsForm) -> Result<()> {
+    for value in form.values.iter() {
+        if let AttrsFormValue::Map(map) = value {
+            for entry in map.entries.iter() {
+                if let MapFormEntry::PairForm(pair) = entry {
+                    if pair.first.to_string() != "asSize" {
+                        continue;
+                    }
+
+                    let size = match &pair.second {
+                        PairFormValue::Atomic(value) => {
+                            let token = value.token();
+
+                            token
+                                .to_uint()
+                                .or_else(|| token.to_int().filter(|n| *n >= 0).map(|n| n as u64))
+                        }
+                        _ => None,
+                    };
+
+                    if size.is_none() {
+                        return Err(Error::Semantic(SemanticError {
+                            loc: pair.second.loc(),
+                            desc: format!(
+                                "asSize attribute of {} must be a non-negative integer, found {}",
+                                form.name,
+                                pair.second.to_string()
+                            ),
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn simple_type(name: &str) -> Type {
+    Type::Simple(SimpleType::from_str(name).unwrap())
+}
+
+fn unknown_type() -> Type {
+    simple_type("_")
+}
+
+fn is_unknown(t: &Type) -> bool {
+    matches!(t, Type::Simple(SimpleType::Ignore(_)))
+}
+
+fn literal_type(value: &SimpleValue) -> Type {
+    match value.token().kind {
+        TokenKind::EmptyLiteral => simple_type("Empty"),
+        TokenKind::UIntLiteral => simple_type("UInt"),
+        TokenKind::IntLiteral => simple_type("Int"),
+        TokenKind::FloatLiteral => simple_type("Float"),
+        TokenKind::CharLiteral => simple_type("Char"),
+        TokenKind::StringLiteral => simple_type("String"),
+        _ => unknown_type(),
+    }
+}
+
+fn map_entry_types(map_type: &MapType) -> Vec<Type> {
+    map_type
+        .entries
+        .iter()
+        .map(|entry| Type::Pair(Box::new(entry.clone())))
+        .collect::<Vec<Type>>()
+}
+
+fn element_type(elements: &[Type], idx: usize, len: usize) -> Type {
+    if elements.len() == 1 {
+        elements[0].clone()
+    } else if elements.len() == len {
+        elements[idx].clone()
+    } else {
+        unknown_type()
+    }
+}
+
+fn collapse_elements(types: Vec<Type>) -> Vec<Type> {
+    match types.first() {
+        Some(first) if types.iter().all(|t| t.to_string() == first.to_string()) => {
+            vec![first.clone()]
+        }
+        _ => types,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn checker_check() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "(module m (block
+            (type Name String)
+            (type True Atomic)
+            (type False Atomic)
+            (type Bool (Enum True False))
+            (sig greet (Fun Name String))
+            (val greet (fun n n))
+            (sig first (Fun (Pair UInt String) UInt))
+            (val first (fun (pair a b) a))
+            (sig toUInt (Fun Bool UInt))
+            (val toUInt (fun b (case b (match True (fun t 1)) (match False (fun f 0)))))
+            (sig isTrue (Fun True UInt))
+            (val isTrue (fun t (toUInt t)))
+            (sig main (Fun IO IO))
+            (val main (fun io (let
+                (sig msg String)
+                (val msg (greet \"hello\"))
+                io)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap().get("first").map(|t| t.to_string()),
+            Some("(Fun (Pair UInt String) UInt)".to_string())
+        );
+
+        s = "(module m (block (sig first (Fun (Pair UInt String) UInt)) (val first (fun (pair a b) b))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type UInt, found type String"));
+
+        s = "(module m (block (sig f (Fun UInt UInt)) (val f (fun x x)) (sig g UInt) (val g (f 1 2))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected 1 arguments for f of type (Fun UInt UInt), found 2"));
+
+        s = "(module m (block (sig f (Fun UInt UInt)) (val f (fun x x)) (sig g UInt) (val g (f 'a'))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type UInt, found type Char"));
+
+        s = "(module m (block (sig f (Fun UInt UInt)) (val f (fun x y x))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type (Fun UInt UInt), found a function of 2 parameters"));
+
+        s = "(module m (block (sig f UInt) (val g 1)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("sig f has no val"));
+
+        s = "(module m (block (sig f UInt) (val f 1) (val g 1)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("val g has no sig"));
+
+        s = "(module m (block (sig f UInt) (sig f UInt) (val f 1) (sig g UInt) (sig g Int) (val g 1)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("conflicting sig g: expected type UInt, found type Int"));
+
+        s = "(module m (block (sig f UInt) (val f 'a') (sig g UInt) (val h 1)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+
+        let (_, errors) = checker.check_as_recovering("m", &form);
+
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].to_string().contains("sig g has no val"));
+        assert!(errors[1].to_string().contains("val h has no sig"));
+        assert!(errors[2]
+            .to_string()
+            .contains("expected type UInt, found type Char"));
+    }
+
+    #[test]
+    fn checker_check_case() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let types = "(type True Atomic) (type False Atomic) (type Bool (Enum True False))
+            (type Maybe (Enum Bool Empty))";
+
+        let mut s = format!(
+            "(module m (block {} (sig f (Fun Bool UInt)) (val f (fun b (case b (match True (fun t 1)) (match False (fun f 0)))))))",
+            types
+        );
+
+        let mut form = ModuleForm::from_str(&s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+
+        s = format!(
+            "(module m (block {} (sig f (Fun Maybe UInt)) (val f (fun m (case m (match () 0) (match others 1))))))",
+            types
+        );
+
+        form = ModuleForm::from_str(&s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_ok());
+
+        s = format!(
+            "(module m (block {} (sig f (Fun Maybe UInt)) (val f (fun m (case m (match Empty 0))))))",
+            types
+        );
+
+        form = ModuleForm::from_str(&s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-exhaustive case over Maybe: missing variants Bool"));
+
+        s = format!(
+            "(module m (block {} (sig f (Fun Bool UInt)) (val f (fun b (case b (match True 1) (match True 0) (match False 0))))))",
+            types
+        );
+
+        form = ModuleForm::from_str(&s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("unreachable match arm True: True is already matched"));
+
+        s = format!(
+            "(module m (block {} (sig f (Fun Bool UInt)) (val f (fun b (case b (match True 1) (match False 0) (match default 2))))))",
+            types
+        );
+
+        form = ModuleForm::from_str(&s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("unreachable match arm default: all variants of Bool are already covered"));
+
+        s = format!(
+            "(module m (block {} (sig f (Fun Bool UInt)) (val f (fun b (case b (match others 1) (match False 0))))))",
+            types
+        );
+
+        form = ModuleForm::from_str(&s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("unreachable match arm False: already covered by others"));
+    }
+
+    #[test]
+    fn checker_check_mem() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "(module m (block
+            (sig sizeOf (Fun UInt Size))
+            (val sizeOf (fun x (size x)))
+            (sig copy (Fun Pointer Pointer Empty))
+            (val copy (fun src dst (store dst (load src))))
+            (sig borrow (Fun UInt Ref))
+            (val borrow (fun x (ref x)))
+            (sig read (Fun Ref UInt))
+            (val read (fun r (deref r)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+
+        s = "(module m (block (sig f (Fun Ref Mem)) (val f (fun r (load r)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type Pointer, found type Ref"));
+
+        s = "(module m (block (sig f (Fun UInt UInt)) (val f (fun x (ref x)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type UInt, found type Ref"));
+    }
+
+    #[test]
+    fn checker_check_dup_drop() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "(module m (block
+            (type Name String)
+            (attrs Name duplicable)
+            (type Handle IO)
+            (attrs Handle (map (pair duplicable ()) (pair droppable ())))
+            (sig twice (Fun UInt (Pair UInt UInt)))
+            (val twice (fun x (dup x)))
+            (sig copyName (Fun Name (Pair Name Name)))
+            (val copyName (fun n (dup n)))
+            (sig forget (Fun String Empty))
+            (val forget (fun s (drop s)))
+            (sig copyHandle (Fun Handle (Pair Handle Handle)))
+            (val copyHandle (fun h (dup h)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+
+        s = "(module m (block (sig f (Fun String (Pair String String))) (val f (fun s (dup s)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cannot dup s: type String is not duplicable"));
+
+        s = "(module m (block (sig f (Fun IO Empty)) (val f (fun io (drop io)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cannot drop io: type IO is not droppable"));
+
+        s = "(module m (block (sig f (Fun UInt UInt)) (val f (fun x (dup x)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected type UInt, found type (Pair UInt UInt)"));
+
+        s = "(module m (block (sig f (Fun Strng (Pair Strng Strng))) (val f (fun s (dup s)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cannot dup s: undefined type Strng"));
+
+        s = "(module m (block (sig f (Fun _ Empty)) (val f (fun x (drop x)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("cannot drop x: cannot tell if unknown type _ is droppable"));
+    }
+
+    #[test]
+    fn checker_check_attrs() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "(module m (block
+            (type Byte UInt)
+            (attrs Byte (map (pair asSize 1)))
+            (type Word UInt)
+            (attrs Word (map (pair asSize +8)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+
+        s = "(module m (block (type Byte UInt) (attrs Byte (map (pair asSize -1)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("asSize attribute of Byte must be a non-negative integer, found -1"));
+
+        s = "(module m (block (type Byte UInt) (attrs Byte (map (pair asSize 1.5)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("found 1.5"));
+    }
+
+    #[test]
+    fn checker_check_cast() {
+        use super::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "(module m (block
+            (sig code (Fun Char UInt))
+            (val code (fun c (cast UInt c)))
+            (sig same (Fun Int Int))
+            (val same (fun n (cast Int n)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut checker = Checker::new();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+        assert!(checker.warnings.is_empty());
+
+        s = "(module m (block
+            (sig abs (Fun Int UInt))
+            (val abs (fun n (cast UInt n)))
+            (sig addr (Fun Size Pointer))
+            (val addr (fun n (cast Pointer n)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_ok());
+        assert_eq!(checker.warnings.len(), 2);
+        assert!(checker.warnings[0]
+            .to_string()
+            .contains("lossy cast from Int to UInt"));
+        assert!(checker.warnings[1]
+            .to_string()
+            .contains("unsafe cast from Size to Pointer"));
+
+        s = "(module m (block (sig f (Fun Float Char)) (val f (fun x (cast Char x)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("invalid cast from Float to Char"));
+
+        s = "(module m (block (sig f (Fun String UInt)) (val f (fun x (cast UInt x)))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("invalid cast from String to UInt"));
+    }
+
+    #[test]
+    fn checker_check_builtin() {
+        use super::Checker;
+        use crate::builtin::Builtins;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut builtins: Builtins<()> = Builtins::new();
+        builtins
+            .register("m.add", "(Fun UInt UInt UInt)", |_| Ok(()))
+            .unwrap();
+        builtins
+            .register("m.println", "(Fun IO String IO)", |_| Ok(()))
+            .unwrap();
+
+        let mut checker = Checker::new();
+        checker.builtins = builtins.signatures();
+
+        let mut s = "(module m (block
+            (sig add (Fun UInt UInt UInt))
+            (val add builtin)
+            (sig main (Fun IO IO))
+            (val main (fun io (println io \"three\")))
+            (sig println Builtin)
+            (val println builtin)))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+        let mut res = checker.check(&form);
+
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap().get("println").map(|t| t.to_string()),
+            Some("(Fun IO String IO)".to_string())
+        );
+
+        s = "(module m (block (sig sub (Fun UInt UInt UInt)) (val sub builtin)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("builtin m.sub has no registered implementation"));
+
+        s = "(module m (block (sig add (Fun Int Int Int)) (val add builtin)))";
+
+        form = ModuleForm::from_str(s).unwrap();
+        res = checker.check(&form);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains(
+            "builtin m.add has type (Fun UInt UInt UInt), expected type (Fun Int Int Int)"
+        ));
+    }
+
+    #[test]
+    fn checker_check_imports() {
+        use super::{Checker, ModuleTypes};
+        use crate::loader::Loader;
+        use crate::value::forms::module_form::ModuleForm;
+        use crate::value::types::Type;
+
+        let mut io = ModuleTypes::new();
+        io.path = "std.io".into();
+        io.values
+            .insert("println".into(), Type::from_str("(Fun IO _ IO)").unwrap());
+
+        let mut math = ModuleTypes::new();
+        math.path = "std.math".into();
+        math.values
+            .insert("+".into(), Type::from_str("(Fun UInt UInt UInt)").unwrap());
+
+        let check = |s: &str| {
+            let form = ModuleForm::from_str(s).unwrap();
+            let mut checker = Checker::new();
+
+            for import in Loader::module_imports(&form) {
+                let types = match import.module.to_string().as_str() {
+                    "std.io" => io.clone(),
+                    _ => math.clone(),
+                };
+
+                checker.imports.insert(import, types);
+            }
+
+            checker.check(&form).map_err(|err| err.to_string())
+        };
+
+        let mut res = check(
+            "(module main (block (import std.io) (import std.math _ () m)
+                (sig main (Fun IO IO))
+                (val main (fun io (std.io.println io (m.+ 1 2))))))",
+        );
+
+        assert!(res.is_ok());
+
+        res = check(
+            "(module main (block (import std.io)
+                (sig main (Fun IO IO))
+                (val main (fun io (println io 1 2 3)))))",
+        );
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .contains("expected 2 arguments for println of type (Fun IO _ IO), found 4"));
+
+        res = check(
+            "(module main (block (import std.io _ () io)
+                (sig main (Fun IO IO))
+                (val main (fun x (io.println 5 x)))))",
+        );
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .contains("expected type IO, found type UInt"));
+
+        res = check(
+            "(module main (block (import std.math)
+                (sig x UInt)
+                (val x (+ \"a\" 'c'))))",
+        );
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .contains("expected type UInt, found type String"));
+
+        res = check(
+            "(module main (block (sig main (Fun IO IO)) (val main (fun io (println io 1)))))",
+        );
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().contains("undefined name println"));
+
+        res = check(
+            "(module main (block (sig main (Fun IO IO))
+                (val main (fun io (let (import std.io _ println) (println io 1))))))",
+        );
+
+        assert!(res.is_ok());
+
+        let mut checker = Checker::new();
+        let form = ModuleForm::from_str("(module main (block (import std.io)))").unwrap();
+
+        assert!(checker
+            .check(&form)
+            .unwrap_err()
+            .to_string()
+            .contains("module std.io is not loaded"));
+    }
+}
@@ -0,0 +1,786 @@
+use crate::error::{Error, SemanticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::semantic::resolver::fun_parameters;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::case_form::CaseFormVariable;
+use crate::value::forms::case_form::{CaseForm, CaseFormMatchAction};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::fun_form::{FunForm, FunFormBody};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::{LinearForm, LinearFormKind};
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::MemForm;
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::SimpleValue;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
+enum ResourceState {
+    Unused,
+    Consumed,
+    Dropped,
+}
+
+impl ResourceState {
+    fn is_spent(&self) -> bool {
+        matches!(self, ResourceState::Consumed | ResourceState::Dropped)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+enum ResourceKind {
+    Parameter,
+    Value,
+    Closure,
+}
+
+impl ResourceKind {
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    fn to_string(&self) -> String {
+        match self {
+            ResourceKind::Parameter => "parameter".into(),
+            ResourceKind::Value => "let-bound value".into(),
+            ResourceKind::Closure => "closure".into(),
+        }
+    }
+}
+
+impl fmt::Display for ResourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+struct Resource {
+    name: SimpleValue,
+    kind: ResourceKind,
+    state: ResourceState,
+    droppable: bool,
+}
+
+impl Resource {
+    fn is_pending(&self) -> bool {
+        self.state == ResourceState::Unused && !self.droppable
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+struct Frame {
+    resources: Vec<Resource>,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct LinearityChecker {
+    pub droppable: Vec<Loc>,
+    frames: Vec<Frame>,
+}
+
+impl LinearityChecker {
+    pub fn new() -> LinearityChecker {
+        LinearityChecker::default()
+    }
+
+    pub fn check(&mut self, module: &ModuleForm) -> Result<()> {
+        self.frames.clear();
+
+        for entry in module.block_entries() {
+            if let BlockFormEntry::ValForm(form) = entry {
+                self.check_val(&form)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_fun(&mut self, form: &FunForm) -> Result<()> {
+        let resources = fun_parameters(form)
+            .into_iter()
+            .map(|name| self.resource(name, ResourceKind::Parameter))
+            .collect::<Vec<Resource>>();
+
+        self.frames.push(Frame { resources });
+
+        let res = self.check_fun_body(&form.body);
+
+        let frame = self.frames.pop().unwrap_or_default();
+
+        res?;
+
+        check_frame_consumed(&frame)
+    }
+
+    fn resource(&self, name: SimpleValue, kind: ResourceKind) -> Resource {
+        let droppable = name
+            .loc()
+            .map_or(false, |loc| self.droppable.contains(&loc));
+
+        Resource {
+            name,
+            kind,
+            state: ResourceState::Unused,
+            droppable,
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<(usize, usize)> {
+        for (frame_idx, frame) in self.frames.iter().enumerate().rev() {
+            if let Some(idx) = frame
+                .resources
+                .iter()
+                .rposition(|r| r.name.to_string() == name)
+            {
+                return Some((frame_idx, idx));
+            }
+        }
+
+        None
+    }
+
+    fn consume(&mut self, symbol: &SimpleValue, state: ResourceState) -> Result<()> {
+        let (frame_idx, idx) = match self.find(&symbol.to_string()) {
+            Some(position) => position,
+            None => return Ok(()),
+        };
+
+        let kind = self.frames[frame_idx].resources[idx].kind.clone();
+
+        match self.frames[frame_idx].resources[idx].state {
+            ResourceState::Consumed => {
+                return Err(Error::Semantic(SemanticError {
+                    loc: symbol.loc(),
+                    desc: format!("non-linear use of {} {}: already consumed", kind, symbol),
+                }));
+            }
+            ResourceState::Dropped => {
+                return Err(Error::Semantic(SemanticError {
+                    loc: symbol.loc(),
+                    desc: format!("non-linear use of {} {}: already dropped", kind, symbol),
+                }));
+            }
+            ResourceState::Unused => {}
+        }
+
+        if state == ResourceState::Consumed {
+            let enclosing = match kind {
+                ResourceKind::Parameter => frame_idx,
+                _ => 0,
+            };
+
+            let expected = self.frames[..enclosing]
+                .iter()
+                .flat_map(|frame| frame.resources.iter())
+                .chain(self.frames[frame_idx].resources[..idx].iter())
+                .find(|r| r.is_pending());
+
+            if let Some(expected) = expected {
+                return Err(Error::Semantic(SemanticError {
+                    loc: symbol.loc(),
+                    desc: format!(
+                        "non-ordered use of {} {}: expected {} {}",
+                        kind, symbol, expected.kind, expected.name
+                    ),
+                }));
+            }
+        }
+
+        self.frames[frame_idx].resources[idx].state = state;
+
+        Ok(())
+    }
+
+    fn check_symbol(&mut self, value: &SimpleValue) -> Result<()> {
+        match value {
+            SimpleValue::ValueSymbol(_) => self.consume(value, ResourceState::Consumed),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_val(&mut self, form: &ValForm) -> Result<()> {
+        match &form.value {
+            ValFormValue::Empty(value)
+            | ValFormValue::Panic(value)
+            | ValFormValue::Atomic(value)
+            | ValFormValue::Builtin(value)
+            | ValFormValue::ValueSymbol(value) => self.check_symbol(value),
+            ValFormValue::PairForm(form) => self.check_pair(form),
+            ValFormValue::FunForm(form) => self.check_fun(form),
+            ValFormValue::LetForm(form) => self.check_let(form),
+            ValFormValue::AppForm(form) => self.check_app(form),
+            ValFormValue::MemForm(form) => self.check_mem(form),
+            ValFormValue::LinearForm(form) => self.check_linear(form),
+            ValFormValue::CastForm(form) => self.check_cast(form),
+            ValFormValue::CaseForm(form) => self.check_case(form),
+        }
+    }
+
+    fn check_fun_body(&mut self, body: &FunFormBody) -> Result<()> {
+        match body {
+            FunFormBody::Empty(value)
+            | FunFormBody::Panic(value)
+            | FunFormBody::Atomic(value)
+            | FunFormBody::ValueSymbol(value)
+            | FunFormBody::ValuePathSymbol(value) => self.check_symbol(value),
+            FunFormBody::MapForm(form) => self.check_map(form),
+            FunFormBody::VecForm(form) => self.check_vec(form),
+            FunFormBody::ArrForm(form) => self.check_arr(form),
+            FunFormBody::ListForm(form) => self.check_list(form),
+            FunFormBody::PairForm(form) => self.check_pair(form),
+            FunFormBody::AppForm(form) => self.check_app(form),
+            FunFormBody::MemForm(form) => self.check_mem(form),
+            FunFormBody::LinearForm(form) => self.check_linear(form),
+            FunFormBody::CastForm(form) => self.check_cast(form),
+            FunFormBody::LetForm(form) => self.check_let(form),
+            FunFormBody::CaseForm(form) => self.check_case(form),
+            FunFormBody::FunForm(form) => self.check_fun(form),
+        }
+    }
+
+    fn check_app_value(&mut self, value: &AppFormValue) -> Result<()> {
+        match value {
+            AppFormValue::Ignore(value)
+            | AppFormValue::Empty(value)
+            | AppFormValue::Panic(value)
+            | AppFormValue::Atomic(value)
+            | AppFormValue::ValueSymbol(value)
+            | AppFormValue::ValuePathSymbol(value) => self.check_symbol(value),
+            AppFormValue::PairForm(form) => self.check_pair(form),
+            AppFormValue::FunForm(form) => self.check_fun(form),
+            AppFormValue::LetForm(form) => self.check_let(form),
+            AppFormValue::CaseForm(form) => self.check_case(form),
+            AppFormValue::AppForm(form) => self.check_app(form),
+            AppFormValue::MemForm(form) => self.check_mem(form),
+            AppFormValue::LinearForm(form) => self.check_linear(form),
+            AppFormValue::CastForm(form) => self.check_cast(form),
+        }
+    }
+
+    fn check_app(&mut self, form: &AppForm) -> Result<()> {
+        self.check_symbol(&form.name)?;
+
+        for variable in form.variables.iter() {
+            self.check_app_value(variable)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_mem(&mut self, form: &MemForm) -> Result<()> {
+        for value in form.values.iter() {
+            self.check_app_value(value)?;
+        }
+
+        Ok(())
+    }
+
+    fn check_linear(&mut self, form: &LinearForm) -> Result<()> {
+        let state = match form.kind {
+            LinearFormKind::Dup => ResourceState::Consumed,
+            LinearFormKind::Drop => ResourceState::Dropped,
+        };
+
+        match &form.value {
+            AppFormValue::ValueSymbol(value) => self.consume(value, state),
+            value => self.check_app_value(value),
+        }
+    }
+
+    fn check_cast(&mut self, form: &CastForm) -> Result<()> {
+        self.check_app_value(&form.value)
+    }
+
+    fn check_let(&mut self, form: &LetForm) -> Result<()> {
+        self.frames.push(Frame::default());
+
+        let res = self.check_let_entries(form);
+
+        let frame = self.frames.pop().unwrap_or_default();
+
+        res?;
+
+        check_frame_consumed(&frame)
+    }
+
+    fn check_let_entries(&mut self, form: &LetForm) -> Result<()> {
+        for entry in form.entries.iter() {
+            if let LetFormEntry::ValForm(val) = entry {
+                let before = self.frames.clone();
+
+                self.check_val(val)?;
+
+                let captures = self.frames != before;
+
+                let kind = match val.value {
+                    ValFormValue::FunForm(_) if captures => ResourceKind::Closure,
+                    _ => ResourceKind::Value,
+                };
+
+                let idx = self.frames.len() - 1;
+
+                let resource = self.resource(val.name.clone(), kind);
+
+                self.frames[idx].resources.push(resource);
+            }
+        }
+
+        self.check_app_value(&form.value)
+    }
+
+    fn check_case(&mut self, form: &CaseForm) -> Result<()> {
+        match &form.variable {
+            CaseFormVariable::Empty(value)
+            | CaseFormVariable::Atomic(value)
+            | CaseFormVariable::ValueSymbol(value) => self.check_symbol(value)?,
+            CaseFormVariable::AppForm(form) => self.check_app(form)?,
+            CaseFormVariable::MemForm(form) => self.check_mem(form)?,
+            CaseFormVariable::LinearForm(form) => self.check_linear(form)?,
+            CaseFormVariable::CastForm(form) => self.check_cast(form)?,
+            CaseFormVariable::LetForm(form) => self.check_let(form)?,
+            CaseFormVariable::CaseForm(form) => self.check_case(form)?,
+        }
+
+        let start = self.frames.clone();
+        let mut end: Option<Vec<Frame>> = None;
+
+        for case_match in form.matches.iter() {
+            self.frames = start.clone();
+
+            if let CaseFormMatchAction::Panic(_) = case_match.action {
+                continue;
+            }
+
+            match &case_match.action {
+                CaseFormMatchAction::Ignore(value)
+                | CaseFormMatchAction::Empty(value)
+                | CaseFormMatchAction::Panic(value)
+                | CaseFormMatchAction::Atomic(value)
+                | CaseFormMatchAction::ValueKeyword(value)
+                | CaseFormMatchAction::ValueSymbol(value)
+                | CaseFormMatchAction::ValuePathSymbol(value) => self.check_symbol(value)?,
+                CaseFormMatchAction::PairForm(form) => self.check_pair(form)?,
+                CaseFormMatchAction::FunForm(form) => self.check_fun(form)?,
+                CaseFormMatchAction::LetForm(form) => self.check_let(form)?,
+            }
+
+            let frames = match &end {
+                Some(frames) => frames,
+                None => {
+                    end = Some(self.frames.clone());
+                    continue;
+                }
+            };
+
+            for (end_frame, frame) in frames.iter().zip(self.frames.iter()) {
+                for (end_resource, resource) in
+                    end_frame.resources.iter().zip(frame.resources.iter())
+                {
+                    if end_resource.state.is_spent() != resource.state.is_spent() {
+                        return Err(Error::Semantic(SemanticError {
+                            loc: case_match.loc(),
+                            desc: format!(
+                                "non-linear use of {} {}: consumed in some case branches but not in others",
+                                resource.kind, resource.name
+                            ),
+                        }));
+                    }
+                }
+            }
+        }
+
+        self.frames = end.unwrap_or(start);
+
+        Ok(())
+    }
+
+    fn check_pair_value(&mut self, value: &PairFormValue) -> Result<()> {
+        match value {
+            PairFormValue::Ignore(value)
+            | PairFormValue::Empty(value)
+            | PairFormValue::Panic(value)
+            | PairFormValue::Atomic(value)
+            | PairFormValue::ValueKeyword(value)
+            | PairFormValue::TypeKeyword(value)
+            | PairFormValue::ValueSymbol(value)
+            | PairFormValue::TypeSymbol(value)
+            | PairFormValue::ValuePathSymbol(value)
+            | PairFormValue::TypePathSymbol(value) => self.check_symbol(value),
+            PairFormValue::Type(_) => Ok(()),
+            PairFormValue::MapForm(form) => self.check_map(form),
+            PairFormValue::VecForm(form) => self.check_vec(form),
+            PairFormValue::ArrForm(form) => self.check_arr(form),
+            PairFormValue::ListForm(form) => self.check_list(form),
+            PairFormValue::PairForm(form) => self.check_pair(form),
+            PairFormValue::FunForm(form) => self.check_fun(form),
+            PairFormValue::CaseForm(form) => self.check_case(form),
+            PairFormValue::LetForm(form) => self.check_let(form),
+            PairFormValue::AppForm(form) => self.check_app(form),
+        }
+    }
+
+    fn check_pair(&mut self, form: &PairForm) -> Result<()> {
+        self.check_pair_value(&form.first)?;
+        self.check_pair_value(&form.second)
+    }
+
+    fn check_list(&mut self, form: &ListForm) -> Result<()> {
+        for value in form.values.iter() {
+            match value {
+                ListFormValue::Ignore(value)
+                | ListFormValue::Empty(value)
+                | ListFormValue::Panic(value)
+                | ListFormValue::Atomic(value)
+                | ListFormValue::ValueKeyword(value)
+                | ListFormValue::TypeKeyword(value)
+                | ListFormValue::ValueSymbol(value)
+                | ListFormValue::TypeSymbol(value)
+                | ListFormValue::ValuePathSymbol(value)
+                | ListFormValue::TypePathSymbol(value) => self.check_symbol(value)?,
+                ListFormValue::Type(_) => {}
+                ListFormValue::FunForm(form) => self.check_fun(form)?,
+                ListFormValue::CaseForm(form) => self.check_case(form)?,
+                ListFormValue::LetForm(form) => self.check_let(form)?,
+                ListFormValue::AppForm(form) => self.check_app(form)?,
+                ListFormValue::PairForm(form) => self.check_pair(form)?,
+                ListFormValue::ArrForm(form) => self.check_arr(form)?,
+                ListFormValue::VecForm(form) => self.check_vec(form)?,
+                ListFormValue::MapForm(form) => self.check_map(form)?,
+                ListFormValue::ListForm(form) => self.check_list(form)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_arr(&mut self, form: &ArrForm) -> Result<()> {
+        for value in form.values.iter() {
+            match value {
+                ArrFormValue::Ignore(value)
+                | ArrFormValue::Empty(value)
+                | ArrFormValue::Panic(value)
+                | ArrFormValue::Atomic(value)
+                | ArrFormValue::ValueKeyword(value)
+                | ArrFormValue::TypeKeyword(value)
+                | ArrFormValue::ValueSymbol(value)
+                | ArrFormValue::TypeSymbol(value)
+                | ArrFormValue::ValuePathSymbol(value)
+                | ArrFormValue::TypePathSymbol(value) => self.check_symbol(value)?,
+                ArrFormValue::Type(_) => {}
+                ArrFormValue::FunForm(form) => self.check_fun(form)?,
+                ArrFormValue::CaseForm(form) => self.check_case(form)?,
+                ArrFormValue::LetForm(form) => self.check_let(form)?,
+                ArrFormValue::AppForm(form) => self.check_app(form)?,
+                ArrFormValue::PairForm(form) => self.check_pair(form)?,
+                ArrFormValue::ListForm(form) => self.check_list(form)?,
+                ArrFormValue::VecForm(form) => self.check_vec(form)?,
+                ArrFormValue::MapForm(form) => self.check_map(form)?,
+                ArrFormValue::ArrForm(form) => self.check_arr(form)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_vec(&mut self, form: &VecForm) -> Result<()> {
+        for value in form.values.iter() {
+            match value {
+                VecFormValue::Ignore(value)
+                | VecFormValue::Empty(value)
+                | VecFormValue::Panic(value)
+                | VecFormValue::Atomic(value)
+                | VecFormValue::ValueKeyword(value)
+                | VecFormValue::TypeKeyword(value)
+                | VecFormValue::ValueSymbol(value)
+                | VecFormValue::TypeSymbol(value)
+                | VecFormValue::ValuePathSymbol(value)
+                | VecFormValue::TypePathSymbol(value) => self.check_symbol(value)?,
+                VecFormValue::Type(_) => {}
+                VecFormValue::FunForm(form) => self.check_fun(form)?,
+                VecFormValue::CaseForm(form) => self.check_case(form)?,
+                VecFormValue::LetForm(form) => self.check_let(form)?,
+                VecFormValue::AppForm(form) => self.check_app(form)?,
+                VecFormValue::PairForm(form) => self.check_pair(form)?,
+                VecFormValue::ListForm(form) => self.check_list(form)?,
+                VecFormValue::ArrForm(form) => self.check_arr(form)?,
+                VecFormValue::MapForm(form) => self.check_map(form)?,
+                VecFormValue::VecForm(form) => self.check_vec(form)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_map(&mut self, form: &MapForm) -> Result<()> {
+        for entry in form.entries.iter() {
+            if let MapFormEntry::PairForm(form) = entry {
+                self.check_pair(form)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_frame_consumed(frame: &Frame) -> Result<()> {
+    if let Some(resource) = frame.resources.iter().find(|r| r.is_pending()) {
+        return Err(Error::Semantic(SemanticError {
+            loc: resource.name.loc(),
+            desc: format!(
+                "non-linear use of {} {}: never consumed",
+                resource.kind, resource.name
+            ),
+        }));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn linearity_checker_check_fun() {
+        use super::LinearityChecker;
+        use crate::value::forms::fun_form::FunForm;
+
+        let mut checker = LinearityChecker::new();
+
+        let mut form = FunForm::from_str("(fun a b c (f a (g b) c))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a b (f b a))").unwrap();
+
+        let mut res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+
+        let mut err = res.unwrap_err().to_string();
+
+        assert!(err.contains("line: 0, pos: 12"));
+        assert!(err.contains("non-ordered use of parameter b: expected parameter a"));
+
+        form = FunForm::from_str("(fun a b (f a a))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+
+        err = res.unwrap_err().to_string();
+
+        assert!(err.contains("line: 0, pos: 14"));
+        assert!(err.contains("non-linear use of parameter a: already consumed"));
+
+        form = FunForm::from_str("(fun a b (f a))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of parameter b: never consumed"));
+
+        form = FunForm::from_str("(fun a b (f (dup a) b))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a b (f (dup a) b a a))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of parameter a: already consumed"));
+
+        form = FunForm::from_str("(fun a b (f b (dup a)))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-ordered use of parameter b: expected parameter a"));
+
+        form = FunForm::from_str("(fun a b (f (drop a) b))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a b (f (drop a) a b))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of parameter a: already dropped"));
+
+        form = FunForm::from_str("(fun io (pair a b) (f io (g a b)))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str(
+            "(fun pred io (case pred (match true (fun t (f io t))) (match false (fun f (g io f)))))",
+        )
+        .unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+    }
+
+    #[test]
+    fn linearity_checker_check_branches() {
+        use super::LinearityChecker;
+        use crate::value::forms::fun_form::FunForm;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut checker = LinearityChecker::new();
+
+        let mut form = FunForm::from_str("(fun a b (case a (match T b) (match F ())))").unwrap();
+
+        let mut res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains(
+            "non-linear use of parameter b: consumed in some case branches but not in others"
+        ));
+
+        form = FunForm::from_str("(fun a b (case a (match T b) (match F panic)))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a (let (val x (f a)) (val y 1) (g x y)))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a (let (val x (f a)) (val y 1) (g y x)))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-ordered use of let-bound value y: expected let-bound value x"));
+
+        form = FunForm::from_str("(fun a (let (val x a) ()))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of let-bound value x: never consumed"));
+
+        form = FunForm::from_str("(fun a (let (val a (f a)) a))").unwrap();
+
+        assert!(checker.check_fun(&form).is_ok());
+
+        form = FunForm::from_str("(fun a (let (val g (fun y (f a y))) (h (g 1) (g 2))))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of closure g: already consumed"));
+
+        let module = ModuleForm::from_str(
+            "(module main (block
+                (sig printBool (Fun Bool IO IO))
+                (val printBool (fun pred io
+                    (case pred
+                        (match true (fun t (printf io \"true: {}\" t)))
+                        (match false (fun f (printf io \"false: {}\" f))))))))",
+        )
+        .unwrap();
+
+        assert!(checker.check(&module).is_ok());
+
+        form = FunForm::from_str("(fun a (g (fun x x) a))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-ordered use of parameter x: expected parameter a"));
+
+        form = FunForm::from_str("(fun p io (case p (match T (fun t (f t io)))))").unwrap();
+
+        res = checker.check_fun(&form);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-ordered use of parameter t: expected parameter io"));
+    }
+
+    #[test]
+    fn linearity_checker_check_droppable() {
+        use super::LinearityChecker;
+        use crate::semantic::checker::Checker;
+        use crate::value::forms::module_form::ModuleForm;
+
+        let module = ModuleForm::from_str(
+            "(module main (block
+                (type True Atomic)
+                (type False Atomic)
+                (type Bool (Enum True False))
+                (sig true True)
+                (val true atomic)
+                (sig false False)
+                (val false atomic)
+                (sig toUInt (Fun Bool UInt))
+                (val toUInt (fun pred (case pred (match true (fun p 1)) (match false (fun p 0)))))
+                (sig skip (Fun UInt IO IO))
+                (val skip (fun n io io))))",
+        )
+        .unwrap();
+
+        let mut checker = LinearityChecker::new();
+        let mut res = checker.check(&module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of parameter p: never consumed"));
+
+        let mut types = Checker::new();
+
+        assert!(types.check(&module).is_ok());
+
+        checker.droppable = types.droppable.clone();
+
+        assert!(checker.check(&module).is_ok());
+
+        let module = ModuleForm::from_str(
+            "(module main (block (sig keep (Fun IO IO IO)) (val keep (fun a b a))))",
+        )
+        .unwrap();
+
+        assert!(types.check(&module).is_ok());
+
+        checker.droppable = types.droppable.clone();
+        res = checker.check(&module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("non-linear use of parameter b: never consumed"));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod cast;
+pub mod checker;
+pub mod linearity;
+pub mod resolver;
+
+pub use cast::*;
+pub use checker::*;
+pub use linearity::*;
+pub use resolver::*;
@@ -0,0 +1,985 @@
+use crate::error::{Error, SemanticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::syntax::{symbol_name, symbol_qualifier};
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::attrs_form::AttrsForm;
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::case_form::CaseFormVariable;
+use crate::value::forms::case_form::{CaseForm, CaseFormMatchAction, CaseFormMatchCase};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::export_form::{ExportForm, ExportFormDef};
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::MemForm;
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::types::{SimpleType, Type};
+use crate::value::SimpleValue;
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+pub enum Definition {
+    TypeParameter(SimpleValue),
+    Parameter(SimpleValue),
+    Import(Box<ImportForm>),
+    Type(Box<TypeForm>),
+    Sig(Box<SigForm>),
+    Val(Box<ValForm>),
+}
+
+impl Default for Definition {
+    fn default() -> Definition {
+        Definition::Parameter(SimpleValue::new())
+    }
+}
+
+impl Definition {
+    pub fn file(&self) -> String {
+        match self {
+            Definition::TypeParameter(value) => value.file(),
+            Definition::Parameter(value) => value.file(),
+            Definition::Import(form) => form.file(),
+            Definition::Type(form) => form.file(),
+            Definition::Sig(form) => form.file(),
+            Definition::Val(form) => form.file(),
+        }
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        match self {
+            Definition::TypeParameter(value) => value.loc(),
+            Definition::Parameter(value) => value.loc(),
+            Definition::Import(form) => form.loc(),
+            Definition::Type(form) => form.loc(),
+            Definition::Sig(form) => form.loc(),
+            Definition::Val(form) => form.loc(),
+        }
+    }
+
+    pub fn is_declaration(&self) -> bool {
+        matches!(self, Definition::Sig(_))
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Definition::TypeParameter(value) => value.to_string(),
+            Definition::Parameter(value) => value.to_string(),
+            Definition::Import(form) => form.to_string(),
+            Definition::Type(form) => form.to_string(),
+            Definition::Sig(form) => form.to_string(),
+            Definition::Val(form) => form.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Definition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct Binding {
+    pub symbol: SimpleValue,
+    pub definition: Definition,
+}
+
+impl Binding {
+    pub fn new() -> Binding {
+        Binding::default()
+    }
+
+    pub fn file(&self) -> String {
+        self.symbol.file()
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        self.symbol.loc()
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        format!("{} -> {}", self.symbol, self.definition)
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+struct Scope {
+    definitions: BTreeMap<String, Vec<Definition>>,
+    imports: Vec<ImportForm>,
+    opaque_imports: Vec<ImportForm>,
+}
+
+impl Scope {
+    fn define(&mut self, name: String, definition: Definition) {
+        self.definitions.entry(name).or_default().push(definition);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Resolver {
+    pub exports: BTreeMap<String, Vec<String>>,
+    pub bindings: Vec<Binding>,
+    pub deferred: Vec<SimpleValue>,
+    scopes: Vec<Scope>,
+    errors: Vec<Error>,
+}
+
+impl Resolver {
+    pub fn new() -> Resolver {
+        Resolver::default()
+    }
+
+    pub fn add_exports(&mut self, module: &str, names: Vec<String>) {
+        self.exports.insert(module.into(), names);
+    }
+
+    pub fn binding(&self, symbol: &SimpleValue) -> Option<Binding> {
+        self.bindings
+            .iter()
+            .find(|b| b.symbol == *symbol)
+            .map(|b| b.to_owned())
+    }
+
+    pub fn resolve(&mut self, module: &ModuleForm) -> Result<Vec<Binding>> {
+        let (bindings, mut errors) = self.resolve_recovering(module);
+
+        if errors.is_empty() {
+            Ok(bindings)
+        } else {
+            Err(errors.remove(0))
+        }
+    }
+
+    pub fn resolve_recovering(&mut self, module: &ModuleForm) -> (Vec<Binding>, Vec<Error>) {
+        self.bindings.clear();
+        self.deferred.clear();
+        self.scopes.clear();
+        self.errors.clear();
+
+        let mut scope = Scope::default();
+
+        for param in module.type_parameters.iter() {
+            if let Some(value) = param.as_simple_value() {
+                scope.define(value.to_string(), Definition::TypeParameter(value));
+            }
+        }
+
+        self.scopes.push(scope);
+
+        let entries = module.block_entries();
+
+        for entry in entries.iter() {
+            self.define_block_entry(entry);
+        }
+
+        for entry in entries.iter() {
+            self.resolve_block_entry(entry);
+        }
+
+        self.scopes.pop();
+
+        (self.bindings.clone(), self.errors.drain(..).collect())
+    }
+
+    pub fn deferred_errors(&self) -> Vec<Error> {
+        self.deferred
+            .iter()
+            .map(|symbol| {
+                Error::Semantic(SemanticError {
+                    loc: symbol.loc(),
+                    desc: format!("undefined name {}", symbol),
+                })
+            })
+            .collect()
+    }
+
+    fn current_scope(&mut self) -> &mut Scope {
+        let idx = self.scopes.len() - 1;
+        &mut self.scopes[idx]
+    }
+
+    fn define_import(&mut self, import: &ImportForm) {
+        let module = import.module.to_string();
+        let definition = Definition::Import(Box::new(import.clone()));
+
+        let qualified_only = import.is_qualified_only();
+        let import_all = import.imports_all();
+
+        let exports = self.exports.get(&module).cloned();
+        let scope = self.current_scope();
+
+        scope.imports.push(import.clone());
+
+        if qualified_only {
+            return;
+        }
+
+        if import_all {
+            if let Some(names) = exports {
+                for name in names {
+                    scope.define(name, definition.clone());
+                }
+            } else {
+                scope.opaque_imports.push(import.clone());
+            }
+
+            return;
+        }
+
+        for def in import.defs.iter() {
+            match def {
+                ImportFormDef::ValueSymbol(value) | ImportFormDef::TypeSymbol(value) => {
+                    scope.define(value.to_string(), definition.clone());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn define_block_entry(&mut self, entry: &BlockFormEntry) {
+        match entry {
+            BlockFormEntry::ImportForm(form) => self.define_import(form),
+            BlockFormEntry::TypeForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Type(form.clone())),
+            BlockFormEntry::SigForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Sig(form.clone())),
+            BlockFormEntry::ValForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Val(form.clone())),
+            BlockFormEntry::Empty(_)
+            | BlockFormEntry::ExportForm(_)
+            | BlockFormEntry::AttrsForm(_)
+            | BlockFormEntry::ErrorForm(_) => {}
+        }
+    }
+
+    fn define_let_entry(&mut self, entry: &LetFormEntry) {
+        match entry {
+            LetFormEntry::ImportForm(form) => self.define_import(form),
+            LetFormEntry::TypeForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Type(form.clone())),
+            LetFormEntry::SigForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Sig(form.clone())),
+            LetFormEntry::ValForm(form) => self
+                .current_scope()
+                .define(form.name.to_string(), Definition::Val(form.clone())),
+            LetFormEntry::Empty(_) | LetFormEntry::AttrsForm(_) => {}
+        }
+    }
+
+    fn resolve_block_entry(&mut self, entry: &BlockFormEntry) {
+        match entry {
+            BlockFormEntry::Empty(_) => {}
+            BlockFormEntry::ImportForm(form) => self.resolve_import(form),
+            BlockFormEntry::ExportForm(form) => self.resolve_export(form),
+            BlockFormEntry::AttrsForm(form) => self.resolve_attrs(form),
+            BlockFormEntry::TypeForm(form) => self.resolve_type(&form.value),
+            BlockFormEntry::SigForm(form) => self.resolve_type(&form.value),
+            BlockFormEntry::ValForm(form) => self.resolve_val(form),
+            BlockFormEntry::ErrorForm(form) => {
+                self.errors.push(Error::Syntactic(form.error.clone()))
+            }
+        }
+    }
+
+    fn resolve_let_entry(&mut self, entry: &LetFormEntry) {
+        match entry {
+            LetFormEntry::Empty(_) => {}
+            LetFormEntry::ImportForm(form) => self.resolve_import(form),
+            LetFormEntry::AttrsForm(form) => self.resolve_attrs(form),
+            LetFormEntry::TypeForm(form) => self.resolve_type(&form.value),
+            LetFormEntry::SigForm(form) => self.resolve_type(&form.value),
+            LetFormEntry::ValForm(form) => self.resolve_val(form),
+        }
+    }
+
+    fn lookup(&self, name: &str, symbol: &SimpleValue) -> Result<Option<Definition>> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(candidates) = scope.definitions.get(name) {
+                let definitions = candidates
+                    .iter()
+                    .filter(|d| !d.is_declaration())
+                    .collect::<Vec<&Definition>>();
+
+                let declarations = candidates
+                    .iter()
+                    .filter(|d| d.is_declaration())
+                    .collect::<Vec<&Definition>>();
+
+                if definitions.len() > 1 || (definitions.is_empty() && declarations.len() > 1) {
+                    return Err(Error::Semantic(SemanticError {
+                        loc: symbol.loc(),
+                        desc: format!("ambiguous name {}", symbol),
+                    }));
+                }
+
+                if let Some(definition) = definitions.first() {
+                    return Ok(Some((*definition).clone()));
+                }
+
+                return Ok(declarations.first().map(|d| (*d).clone()));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn lookup_qualifier(&self, qualifier: &str) -> Option<ImportForm> {
+        for scope in self.scopes.iter().rev() {
+            let import = scope
+                .imports
+                .iter()
+                .find(|import| import.qualifier_name() == qualifier);
+
+            if let Some(import) = import {
+                return Some(import.clone());
+            }
+        }
+
+        None
+    }
+
+    fn has_opaque_imports(&self) -> bool {
+        self.scopes.iter().any(|s| !s.opaque_imports.is_empty())
+    }
+
+    fn resolve_symbol(&mut self, symbol: &SimpleValue) {
+        if let Err(err) = self.try_resolve_symbol(symbol) {
+            self.errors.push(err);
+        }
+    }
+
+    fn try_resolve_symbol(&mut self, symbol: &SimpleValue) -> Result<()> {
+        let name = symbol.to_string();
+
+        let definition = match symbol {
+            SimpleValue::ValueSymbol(_) | SimpleValue::TypeSymbol(_) => {
+                self.lookup(&name, symbol)?
+            }
+            SimpleValue::ValuePathSymbol(_) | SimpleValue::TypePathSymbol(_) => {
+                let qualifier = symbol_qualifier(&name);
+                let unqualified = symbol_name(&name);
+
+                let import = match self.lookup_qualifier(&qualifier) {
+                    Some(import) => import,
+                    None => {
+                        return Err(Error::Semantic(SemanticError {
+                            loc: symbol.loc(),
+                            desc: format!("undefined qualifier {} in {}", qualifier, name),
+                        }));
+                    }
+                };
+
+                let module = import.module.to_string();
+
+                if let Some(exports) = self.exports.get(&module) {
+                    if !exports.contains(&unqualified) {
+                        return Err(Error::Semantic(SemanticError {
+                            loc: symbol.loc(),
+                            desc: format!("module {} does not export {}", module, unqualified),
+                        }));
+                    }
+                }
+
+                Some(Definition::Import(Box::new(import)))
+            }
+            _ => return Ok(()),
+        };
+
+        match definition {
+            Some(definition) => {
+                self.bindings.push(Binding {
+                    symbol: symbol.clone(),
+                    definition,
+                });
+            }
+            None if self.has_opaque_imports() => {
+                self.deferred.push(symbol.clone());
+            }
+            None => {
+                return Err(Error::Semantic(SemanticError {
+                    loc: symbol.loc(),
+                    desc: format!("undefined name {}", symbol),
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn resolve_type(&mut self, t: &Type) {
+        for type_var in t.all_type_variables().iter() {
+            match type_var {
+                Type::Simple(SimpleType::Symbol(value))
+                | Type::Simple(SimpleType::PathSymbol(value)) => {
+                    self.resolve_symbol(value);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn resolve_import(&mut self, form: &ImportForm) {
+        let qualifier = form.qualifier_name();
+        let module = form.module.to_string();
+
+        let clash = self
+            .current_scope()
+            .imports
+            .iter()
+            .take_while(|import| *import != form)
+            .find(|import| {
+                import.qualifier_name() == qualifier && import.module.to_string() != module
+            })
+            .cloned();
+
+        if let Some(import) = clash {
+            self.errors.push(Error::Semantic(SemanticError {
+                loc: form.loc(),
+                desc: format!(
+                    "qualifier {} of module {} clashes with the one of module {}",
+                    qualifier, module, import.module
+                ),
+            }));
+        }
+
+        for t in form.type_variables.iter() {
+            self.resolve_type(t);
+        }
+    }
+
+    fn resolve_export(&mut self, form: &ExportForm) {
+        for def in form.defs.iter() {
+            match def {
+                ExportFormDef::ValueSymbol(value) | ExportFormDef::TypeSymbol(value) => {
+                    self.resolve_symbol(value);
+                }
+                ExportFormDef::Empty(_) => {}
+            }
+        }
+    }
+
+    fn resolve_attrs(&mut self, form: &AttrsForm) {
+        self.resolve_symbol(&form.name)
+    }
+
+    fn resolve_val(&mut self, form: &ValForm) {
+        match &form.value {
+            ValFormValue::Empty(_)
+            | ValFormValue::Panic(_)
+            | ValFormValue::Atomic(_)
+            | ValFormValue::Builtin(_) => {}
+            ValFormValue::ValueSymbol(value) => self.resolve_symbol(value),
+            ValFormValue::PairForm(form) => self.resolve_pair(form),
+            ValFormValue::FunForm(form) => self.resolve_fun(form),
+            ValFormValue::LetForm(form) => self.resolve_let(form),
+            ValFormValue::AppForm(form) => self.resolve_app(form),
+            ValFormValue::MemForm(form) => self.resolve_mem(form),
+            ValFormValue::LinearForm(form) => self.resolve_linear(form),
+            ValFormValue::CastForm(form) => self.resolve_cast(form),
+            ValFormValue::CaseForm(form) => self.resolve_case(form),
+        }
+    }
+
+    fn resolve_fun(&mut self, form: &FunForm) {
+        let mut scope = Scope::default();
+
+        for param in fun_parameters(form) {
+            scope.define(param.to_string(), Definition::Parameter(param));
+        }
+
+        self.scopes.push(scope);
+
+        match &form.body {
+            FunFormBody::Empty(_) | FunFormBody::Panic(_) | FunFormBody::Atomic(_) => {}
+            FunFormBody::ValueSymbol(value) => self.resolve_symbol(value),
+            FunFormBody::ValuePathSymbol(value) => self.resolve_symbol(value),
+            FunFormBody::MapForm(form) => self.resolve_map(form),
+            FunFormBody::VecForm(form) => self.resolve_vec(form),
+            FunFormBody::ArrForm(form) => self.resolve_arr(form),
+            FunFormBody::ListForm(form) => self.resolve_list(form),
+            FunFormBody::PairForm(form) => self.resolve_pair(form),
+            FunFormBody::AppForm(form) => self.resolve_app(form),
+            FunFormBody::MemForm(form) => self.resolve_mem(form),
+            FunFormBody::LinearForm(form) => self.resolve_linear(form),
+            FunFormBody::CastForm(form) => self.resolve_cast(form),
+            FunFormBody::LetForm(form) => self.resolve_let(form),
+            FunFormBody::CaseForm(form) => self.resolve_case(form),
+            FunFormBody::FunForm(form) => self.resolve_fun(form),
+        }
+
+        self.scopes.pop();
+    }
+
+    fn resolve_app_value(&mut self, value: &AppFormValue) {
+        match value {
+            AppFormValue::Ignore(_)
+            | AppFormValue::Empty(_)
+            | AppFormValue::Panic(_)
+            | AppFormValue::Atomic(_) => {}
+            AppFormValue::ValueSymbol(value) => self.resolve_symbol(value),
+            AppFormValue::ValuePathSymbol(value) => self.resolve_symbol(value),
+            AppFormValue::PairForm(form) => self.resolve_pair(form),
+            AppFormValue::FunForm(form) => self.resolve_fun(form),
+            AppFormValue::LetForm(form) => self.resolve_let(form),
+            AppFormValue::CaseForm(form) => self.resolve_case(form),
+            AppFormValue::AppForm(form) => self.resolve_app(form),
+            AppFormValue::MemForm(form) => self.resolve_mem(form),
+            AppFormValue::LinearForm(form) => self.resolve_linear(form),
+            AppFormValue::CastForm(form) => self.resolve_cast(form),
+        }
+    }
+
+    fn resolve_app(&mut self, form: &AppForm) {
+        self.resolve_symbol(&form.name);
+
+        for variable in form.variables.iter() {
+            self.resolve_app_value(variable);
+        }
+    }
+
+    fn resolve_mem(&mut self, form: &MemForm) {
+        for value in form.values.iter() {
+            self.resolve_app_value(value);
+        }
+    }
+
+    fn resolve_linear(&mut self, form: &LinearForm) {
+        self.resolve_app_value(&form.value)
+    }
+
+    fn resolve_cast(&mut self, form: &CastForm) {
+        self.resolve_type(&form.target);
+        self.resolve_app_value(&form.value)
+    }
+
+    fn resolve_let(&mut self, form: &LetForm) {
+        self.scopes.push(Scope::default());
+
+        for entry in form.entries.iter() {
+            self.define_let_entry(entry);
+        }
+
+        for entry in form.entries.iter() {
+            self.resolve_let_entry(entry);
+        }
+
+        self.resolve_app_value(&form.value);
+
+        self.scopes.pop();
+    }
+
+    fn resolve_case(&mut self, form: &CaseForm) {
+        match &form.variable {
+            CaseFormVariable::Empty(_) | CaseFormVariable::Atomic(_) => {}
+            CaseFormVariable::ValueSymbol(value) => self.resolve_symbol(value),
+            CaseFormVariable::AppForm(form) => self.resolve_app(form),
+            CaseFormVariable::MemForm(form) => self.resolve_mem(form),
+            CaseFormVariable::LinearForm(form) => self.resolve_linear(form),
+            CaseFormVariable::CastForm(form) => self.resolve_cast(form),
+            CaseFormVariable::LetForm(form) => self.resolve_let(form),
+            CaseFormVariable::CaseForm(form) => self.resolve_case(form),
+        }
+
+        for case_match in form.matches.iter() {
+            match &case_match.case {
+                CaseFormMatchCase::Empty(_)
+                | CaseFormMatchCase::Atomic(_)
+                | CaseFormMatchCase::ValueKeyword(_)
+                | CaseFormMatchCase::TypeKeyword(_) => {}
+                CaseFormMatchCase::TypeSymbol(value)
+                | CaseFormMatchCase::ValueSymbol(value)
+                | CaseFormMatchCase::TypePathSymbol(value)
+                | CaseFormMatchCase::ValuePathSymbol(value) => self.resolve_symbol(value),
+            }
+
+            match &case_match.action {
+                CaseFormMatchAction::Ignore(_)
+                | CaseFormMatchAction::Empty(_)
+                | CaseFormMatchAction::Panic(_)
+                | CaseFormMatchAction::Atomic(_)
+                | CaseFormMatchAction::ValueKeyword(_) => {}
+                CaseFormMatchAction::ValueSymbol(value)
+                | CaseFormMatchAction::ValuePathSymbol(value) => self.resolve_symbol(value),
+                CaseFormMatchAction::PairForm(form) => self.resolve_pair(form),
+                CaseFormMatchAction::FunForm(form) => self.resolve_fun(form),
+                CaseFormMatchAction::LetForm(form) => self.resolve_let(form),
+            }
+        }
+    }
+
+    fn resolve_pair_value(&mut self, value: &PairFormValue) {
+        match value {
+            PairFormValue::Ignore(_)
+            | PairFormValue::Empty(_)
+            | PairFormValue::Panic(_)
+            | PairFormValue::Atomic(_)
+            | PairFormValue::ValueKeyword(_)
+            | PairFormValue::TypeKeyword(_) => {}
+            PairFormValue::ValueSymbol(value)
+            | PairFormValue::TypeSymbol(value)
+            | PairFormValue::ValuePathSymbol(value)
+            | PairFormValue::TypePathSymbol(value) => self.resolve_symbol(value),
+            PairFormValue::Type(form) => self.resolve_type(form),
+            PairFormValue::MapForm(form) => self.resolve_map(form),
+            PairFormValue::VecForm(form) => self.resolve_vec(form),
+            PairFormValue::ArrForm(form) => self.resolve_arr(form),
+            PairFormValue::ListForm(form) => self.resolve_list(form),
+            PairFormValue::PairForm(form) => self.resolve_pair(form),
+            PairFormValue::FunForm(form) => self.resolve_fun(form),
+            PairFormValue::CaseForm(form) => self.resolve_case(form),
+            PairFormValue::LetForm(form) => self.resolve_let(form),
+            PairFormValue::AppForm(form) => self.resolve_app(form),
+        }
+    }
+
+    fn resolve_pair(&mut self, form: &PairForm) {
+        self.resolve_pair_value(&form.first);
+        self.resolve_pair_value(&form.second)
+    }
+
+    fn resolve_map(&mut self, form: &MapForm) {
+        for entry in form.entries.iter() {
+            if let MapFormEntry::PairForm(form) = entry {
+                self.resolve_pair(form);
+            }
+        }
+    }
+
+    fn resolve_list(&mut self, form: &ListForm) {
+        for value in form.values.iter() {
+            match value {
+                ListFormValue::Ignore(_)
+                | ListFormValue::Empty(_)
+                | ListFormValue::Panic(_)
+                | ListFormValue::Atomic(_)
+                | ListFormValue::ValueKeyword(_)
+                | ListFormValue::TypeKeyword(_) => {}
+                ListFormValue::ValueSymbol(value)
+                | ListFormValue::TypeSymbol(value)
+                | ListFormValue::ValuePathSymbol(value)
+                | ListFormValue::TypePathSymbol(value) => self.resolve_symbol(value),
+                ListFormValue::Type(form) => self.resolve_type(form),
+                ListFormValue::FunForm(form) => self.resolve_fun(form),
+                ListFormValue::CaseForm(form) => self.resolve_case(form),
+                ListFormValue::LetForm(form) => self.resolve_let(form),
+                ListFormValue::AppForm(form) => self.resolve_app(form),
+                ListFormValue::PairForm(form) => self.resolve_pair(form),
+                ListFormValue::ArrForm(form) => self.resolve_arr(form),
+                ListFormValue::VecForm(form) => self.resolve_vec(form),
+                ListFormValue::MapForm(form) => self.resolve_map(form),
+                ListFormValue::ListForm(form) => self.resolve_list(form),
+            }
+        }
+    }
+
+    fn resolve_arr(&mut self, form: &ArrForm) {
+        for value in form.values.iter() {
+            match value {
+                ArrFormValue::Ignore(_)
+                | ArrFormValue::Empty(_)
+                | ArrFormValue::Panic(_)
+                | ArrFormValue::Atomic(_)
+                | ArrFormValue::ValueKeyword(_)
+                | ArrFormValue::TypeKeyword(_) => {}
+                ArrFormValue::ValueSymbol(value)
+                | ArrFormValue::TypeSymbol(value)
+                | ArrFormValue::ValuePathSymbol(value)
+                | ArrFormValue::TypePathSymbol(value) => self.resolve_symbol(value),
+                ArrFormValue::Type(form) => self.resolve_type(form),
+                ArrFormValue::FunForm(form) => self.resolve_fun(form),
+                ArrFormValue::CaseForm(form) => self.resolve_case(form),
+                ArrFormValue::LetForm(form) => self.resolve_let(form),
+                ArrFormValue::AppForm(form) => self.resolve_app(form),
+                ArrFormValue::PairForm(form) => self.resolve_pair(form),
+                ArrFormValue::ListForm(form) => self.resolve_list(form),
+                ArrFormValue::VecForm(form) => self.resolve_vec(form),
+                ArrFormValue::MapForm(form) => self.resolve_map(form),
+                ArrFormValue::ArrForm(form) => self.resolve_arr(form),
+            }
+        }
+    }
+
+    fn resolve_vec(&mut self, form: &VecForm) {
+        for value in form.values.iter() {
+            match value {
+                VecFormValue::Ignore(_)
+                | VecFormValue::Empty(_)
+                | VecFormValue::Panic(_)
+                | VecFormValue::Atomic(_)
+                | VecFormValue::ValueKeyword(_)
+                | VecFormValue::TypeKeyword(_) => {}
+                VecFormValue::ValueSymbol(value)
+                | VecFormValue::TypeSymbol(value)
+                | VecFormValue::ValuePathSymbol(value)
+                | VecFormValue::TypePathSymbol(value) => self.resolve_symbol(value),
+                VecFormValue::Type(form) => self.resolve_type(form),
+                VecFormValue::FunForm(form) => self.resolve_fun(form),
+                VecFormValue::CaseForm(form) => self.resolve_case(form),
+                VecFormValue::LetForm(form) => self.resolve_let(form),
+                VecFormValue::AppForm(form) => self.resolve_app(form),
+                VecFormValue::PairForm(form) => self.resolve_pair(form),
+                VecFormValue::ListForm(form) => self.resolve_list(form),
+                VecFormValue::ArrForm(form) => self.resolve_arr(form),
+                VecFormValue::MapForm(form) => self.resolve_map(form),
+                VecFormValue::VecForm(form) => self.resolve_vec(form),
+            }
+        }
+    }
+}
+
+pub fn fun_parameters(form: &FunForm) -> Vec<SimpleValue> {
+    let mut params = vec![];
+
+    for param in form.parameters.iter() {
+        let vars = match param {
+            FunFormParameter::Empty(_) => vec![],
+            FunFormParameter::ValueSymbol(value) => vec![value.clone()],
+            FunFormParameter::MapForm(form) => form.all_variables(),
+            FunFormParameter::VecForm(form) => form.all_variables(),
+            FunFormParameter::ArrForm(form) => form.all_variables(),
+            FunFormParameter::ListForm(form) => form.all_variables(),
+            FunFormParameter::PairForm(form) => form.all_variables(),
+        };
+
+        params.extend(
+            vars.into_iter()
+                .filter(|v| matches!(v, SimpleValue::ValueSymbol(_))),
+        );
+    }
+
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn resolver_resolve() {
+        use super::{Definition, Resolver};
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut s = "
+        (module main (block
+            (type Bool (Enum True False))
+            (type True Atomic)
+            (type False Atomic)
+            (sig not (Fun Bool Maybe))
+            (val not (fun b (case b (match True (fun t false)) (match False (fun f true)))))
+            (val true (pair 0 0))
+            (val false (pair 1 1))))";
+
+        let mut module = ModuleForm::from_str(s).unwrap();
+
+        let mut resolver = Resolver::new();
+
+        let mut res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+
+        s = "
+        (module main (block
+            (type Bool (Enum True False))
+            (type True Atomic)
+            (type False Atomic)
+            (sig not (Fun Bool Bool))
+            (val not (fun b (case b (match True (fun t false)) (match False (fun f true)))))
+            (val true (pair 0 0))
+            (val false (pair 1 1))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+
+        let bindings = res.unwrap();
+
+        assert!(bindings.iter().any(
+            |b| b.symbol.to_string() == "b" && matches!(b.definition, Definition::Parameter(_))
+        ));
+        assert!(
+            bindings
+                .iter()
+                .any(|b| b.symbol.to_string() == "True"
+                    && matches!(b.definition, Definition::Type(_)))
+        );
+        assert!(
+            bindings
+                .iter()
+                .any(|b| b.symbol.to_string() == "false"
+                    && matches!(b.definition, Definition::Val(_)))
+        );
+
+        s = "(module main (block (val x (fun a (f a)))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "semantic error at (file: none, line: 0, pos: 35): undefined name f".to_string()
+        );
+
+        s = "(module main (block (val x (fun a (f a))) (val y (g x)) (val z (h x))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        let (_, errors) = resolver.resolve_recovering(&module);
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(|err| err.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "semantic error at (file: none, line: 0, pos: 35): undefined name f".to_string(),
+                "semantic error at (file: none, line: 0, pos: 50): undefined name g".to_string(),
+                "semantic error at (file: none, line: 0, pos: 64): undefined name h".to_string(),
+            ]
+        );
+
+        s = "(module main (block (val x 1) (val x 2) (val y x)))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("ambiguous name x"));
+
+        s = "(module main (block (import std.io _ _ io) (import std.math _ +)
+            (val main (fun io (io.println io (+ 1 2))))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+        assert!(resolver.deferred.is_empty());
+
+        s = "(module main (block (import std.io) (val main (fun io (println io \"hi\")))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+        assert_eq!(resolver.deferred.len(), 1);
+        assert_eq!(
+            resolver.deferred_errors()[0].to_string(),
+            "semantic error at (file: none, line: 0, pos: 55): undefined name println".to_string()
+        );
+
+        resolver.add_exports("std.io", vec!["println".into()]);
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+        assert!(resolver.deferred.is_empty());
+
+        s = "(module main (block (val main (fun io (x.println io)))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn resolver_resolve_qualified() {
+        use super::{Definition, Resolver};
+        use crate::value::forms::module_form::ModuleForm;
+
+        let mut resolver = Resolver::new();
+        resolver.add_exports("std.io", vec!["println".into()]);
+        resolver.add_exports("std.string", vec!["removeAll".into()]);
+
+        let mut s = "(module main (block
+            (import std.io () () io)
+            (import std.string _ removeAll string)
+            (val main (fun io (io.println io (string.removeAll 'a' \"abc\"))))))";
+
+        let mut module = ModuleForm::from_str(s).unwrap();
+
+        let mut res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+
+        let bindings = res.unwrap();
+
+        assert!(bindings.iter().any(|b| b.symbol.to_string() == "io.println"
+            && matches!(b.definition, Definition::Import(ref i) if i.module.to_string() == "std.io")));
+        assert!(bindings.iter().any(|b| b.symbol.to_string() == "string.removeAll"
+            && matches!(b.definition, Definition::Import(ref i) if i.module.to_string() == "std.string")));
+
+        s = "(module main (block (import std.io) (val main (fun io (std.io.println io)))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_ok());
+
+        s = "(module main (block (import std.io _ () io) (val main (fun io (std.io.println io)))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("undefined qualifier std.io in std.io.println"));
+
+        s = "(module main (block (import std.io _ () io) (val main (fun io (io.print io)))))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("module std.io does not export print"));
+
+        s = "(module main (block (import std.io _ () x) (import std.string _ () x)))";
+
+        module = ModuleForm::from_str(s).unwrap();
+
+        res = resolver.resolve(&module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("qualifier x of module std.string clashes with the one of module std.io"));
+    }
+}
@@ -0,0 +1,72 @@
+use crate::loc::Loc;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct Span {
+    pub start: Loc,
+    pub end: Loc,
+}
+
+impl Span {
+    pub fn new() -> Self {
+        Span::default()
+    }
+
+    pub fn from_locs(start: Loc, end: Loc) -> Self {
+        Span { start, end }
+    }
+
+    pub fn from_loc(loc: Loc, s: &str) -> Self {
+        let end = loc.advance(s);
+
+        Span { start: loc, end }
+    }
+
+    pub fn file(&self) -> Option<String> {
+        self.start.file.clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        self.start.offset <= offset && offset < self.end.offset
+    }
+
+    pub fn join(&self, other: &Span) -> Span {
+        let start = if other.start.offset < self.start.offset {
+            other.start.clone()
+        } else {
+            self.start.clone()
+        };
+
+        let end = if other.end.offset > self.end.offset {
+            other.end.clone()
+        } else {
+            self.end.clone()
+        };
+
+        Span { start, end }
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let file = self.file().unwrap_or_else(|| "none".into());
+
+        format!(
+            "(file: {}, start: {}:{}, end: {}:{})",
+            file, self.start.line, self.start.pos, self.end.line, self.end.pos
+        )
+    }
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
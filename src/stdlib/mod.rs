@@ -0,0 +1,117 @@
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::module_form::ModuleForm;
+use crate::value::forms::val_form::ValFormValue;
+use crate::value::types::FunType;
+use std::collections::BTreeMap;
+
+pub const IO_SOURCE: &str = include_str!("io.at");
+pub const MATH_SOURCE: &str = include_str!("math.at");
+pub const STRING_SOURCE: &str = include_str!("string.at");
+
+pub const MODULES: [(&str, &str); 3] = [
+    ("std.io", IO_SOURCE),
+    ("std.math", MATH_SOURCE),
+    ("std.string", STRING_SOURCE),
+];
+
+pub fn source(module: &str) -> Option<&'static str> {
+    MODULES
+        .iter()
+        .find(|(name, _)| *name == module)
+        .map(|(_, source)| *source)
+}
+
+thread_local! {
+    static BUILTINS: Vec<(String, String)> = parse_builtins();
+}
+
+pub fn builtins() -> Vec<(String, String)> {
+    BUILTINS.with(|builtins| builtins.clone())
+}
+
+fn parse_builtins() -> Vec<(String, String)> {
+    let mut builtins = vec![];
+
+    for (path, source) in MODULES.iter() {
+        let module = ModuleForm::from_str(source).unwrap();
+        let entries = module.block_entries();
+
+        for entry in entries.iter() {
+            let val = match entry {
+                BlockFormEntry::ValForm(val) if matches!(val.value, ValFormValue::Builtin(_)) => {
+                    val
+                }
+                _ => continue,
+            };
+
+            let sig = entries.iter().find_map(|entry| match entry {
+                BlockFormEntry::SigForm(sig) if sig.name.to_string() == val.name.to_string() => {
+                    Some(sig.value.to_string())
+                }
+                _ => None,
+            });
+
+            if let Some(sig) = sig {
+                builtins.push((format!("{}.{}", path, val.name), sig));
+            }
+        }
+    }
+
+    builtins
+}
+
+pub fn signatures() -> BTreeMap<String, FunType> {
+    builtins()
+        .into_iter()
+        .map(|(name, sig)| (name, FunType::from_str(&sig).unwrap()))
+        .collect::<BTreeMap<String, FunType>>()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn stdlib_builtins() {
+        use super::builtins;
+
+        let builtins = builtins();
+
+        assert_eq!(builtins.len(), 11);
+        assert_eq!(
+            builtins[0],
+            ("std.io.print".to_string(), "(Fun IO _ IO)".to_string())
+        );
+        assert!(builtins.contains(&(
+            "std.string.length".to_string(),
+            "(Fun String Size)".to_string()
+        )));
+    }
+
+    #[test]
+    fn stdlib_check() {
+        use super::{signatures, MODULES};
+        use crate::loader::Loader;
+        use crate::semantic::checker::Checker;
+
+        let mut loader = Loader::new();
+        let mut checker = Checker::new();
+        checker.builtins = signatures();
+
+        for (name, _) in MODULES.iter() {
+            let module = loader.load(name).unwrap();
+            let res = checker.check_as(name, &module);
+
+            assert!(res.is_ok());
+        }
+
+        checker.builtins.remove("std.math.%");
+
+        let module = loader.load("std.math").unwrap();
+        let res = checker.check_as("std.math", &module);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("builtin std.math.% has no registered implementation"));
+    }
+}
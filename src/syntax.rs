@@ -12,7 +12,7 @@ pub const KEYWORDS: [&str; 55] = [
 ];
 
 pub fn is_keyword(s: &str) -> bool {
-    KEYWORDS.iter().any(|&k| k == s)
+    KEYWORDS.contains(&s)
 }
 
 pub fn is_value_keyword(s: &str) -> bool {
@@ -247,6 +247,100 @@ pub fn is_escape_char(s: &str) -> bool {
     s == ESCAPE_CHAR.to_string()
 }
 
+pub fn unescape(s: &str) -> std::result::Result<String, (usize, String)> {
+    let chars: Vec<char> = s.chars().collect();
+    let len = chars.len();
+    let mut idx = 0;
+    let mut res = String::new();
+
+    while idx < len {
+        let c = chars[idx];
+
+        if c != ESCAPE_CHAR {
+            res.push(c);
+            idx += 1;
+            continue;
+        }
+
+        let start = idx;
+
+        if idx + 1 >= len {
+            return Err((start, "incomplete escape".into()));
+        }
+
+        idx += 1;
+
+        match chars[idx] {
+            'n' => res.push('\n'),
+            't' => res.push('\t'),
+            'r' => res.push('\r'),
+            '0' => res.push('\0'),
+            c @ ('\\' | '\'' | '"') => res.push(c),
+            'x' => {
+                let digits: String = chars[idx + 1..].iter().take(2).collect();
+
+                let n = if digits.len() == 2 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    u8::from_str_radix(&digits, 16).ok()
+                } else {
+                    None
+                };
+
+                match n {
+                    Some(n) if n <= 0x7f => res.push(n as char),
+                    _ => {
+                        return Err((start, format!("invalid hex escape \\x{}", digits)));
+                    }
+                }
+
+                idx += 2;
+            }
+            'u' => {
+                let rest: String = chars[idx + 1..].iter().collect();
+
+                let end = if rest.starts_with('{') {
+                    rest.find('}')
+                } else {
+                    None
+                };
+
+                let escape = match end {
+                    Some(end) => rest[..=end].to_string(),
+                    None => {
+                        return Err((start, "unicode escape must have the form \\u{...}".into()));
+                    }
+                };
+
+                let digits = &escape[1..escape.len() - 1];
+
+                let c = if !digits.is_empty()
+                    && digits.len() <= 6
+                    && digits.chars().all(|c| c.is_ascii_hexdigit())
+                {
+                    u32::from_str_radix(digits, 16)
+                        .ok()
+                        .and_then(std::char::from_u32)
+                } else {
+                    None
+                };
+
+                match c {
+                    Some(c) => res.push(c),
+                    None => {
+                        return Err((start, format!("invalid unicode escape \\u{}", escape)));
+                    }
+                }
+
+                idx += escape.chars().count();
+            }
+            c => return Err((start, format!("unknown escape \\{}", c))),
+        }
+
+        idx += 1;
+    }
+
+    Ok(res)
+}
+
 pub fn is_separator_char(c: char) -> bool {
     c.is_ascii_whitespace()
         || c == COMMENT_MARK
@@ -346,6 +440,47 @@ pub fn is_float_literal(s: &str) -> bool {
     true
 }
 
+pub fn parse_uint_literal(s: &str) -> Option<u64> {
+    if !is_uint_literal(s) {
+        return None;
+    }
+
+    match s {
+        x if x.starts_with('b') => u64::from_str_radix(&x[1..], 2).ok(),
+        x if x.starts_with('o') => u64::from_str_radix(&x[1..], 8).ok(),
+        x if x.starts_with('x') || x.starts_with('X') => u64::from_str_radix(&x[1..], 16).ok(),
+        x => x.parse::<u64>().ok(),
+    }
+}
+
+pub fn parse_int_literal(s: &str) -> Option<i64> {
+    if !is_int_literal(s) {
+        return None;
+    }
+
+    let n = parse_uint_literal(&s[1..])?;
+
+    if s.starts_with('-') {
+        if n > i64::MAX as u64 + 1 {
+            None
+        } else {
+            Some((n as i64).wrapping_neg())
+        }
+    } else if n > i64::MAX as u64 {
+        None
+    } else {
+        Some(n as i64)
+    }
+}
+
+pub fn parse_float_literal(s: &str) -> Option<f64> {
+    if !is_float_literal(s) {
+        return None;
+    }
+
+    s.parse::<f64>().ok().filter(|n| n.is_finite())
+}
+
 pub fn is_hex_char_letter(c: char) -> bool {
     ('A'..='F').chain('a'..='f').any(|l| l == c)
 }
@@ -490,7 +625,7 @@ pub fn symbol_name(s: &str) -> String {
 }
 
 pub fn symbol_with_qualifier(s: &str, qualifier: &str) -> String {
-    vec![qualifier, s].join(".")
+    [qualifier, s].join(".")
 }
 
 pub fn is_type_path_symbol(s: &str) -> bool {
@@ -0,0 +1,113 @@
+use crate::loc::Loc;
+use crate::token::{Token, TokenKind, Tokens};
+
+pub fn leading_docs(tokens: &Tokens, idx: usize) -> Vec<Token> {
+    let mut docs = vec![];
+    let mut idx = idx.min(tokens.len());
+
+    while idx > 0 {
+        idx -= 1;
+
+        match tokens[idx].kind {
+            TokenKind::DocComment => docs.push(tokens[idx].clone()),
+            TokenKind::Comment => {}
+            _ => break,
+        }
+    }
+
+    docs.reverse();
+
+    docs
+}
+
+pub fn split_detached(docs: &[Token], token: &Token) -> (Vec<Token>, Vec<Token>) {
+    let mut line = token.loc().map(|loc| loc.line);
+    let mut idx = docs.len();
+
+    while idx > 0 {
+        let doc_line = docs[idx - 1].loc().map(|loc| loc.line);
+
+        match (doc_line, line) {
+            (Some(doc_line), Some(next_line)) if next_line <= doc_line + 1 => {
+                line = Some(doc_line);
+                idx -= 1;
+            }
+            _ => break,
+        }
+    }
+
+    (docs[..idx].to_vec(), docs[idx..].to_vec())
+}
+
+pub fn doc_text(docs: &[Token]) -> Option<String> {
+    if docs.is_empty() {
+        return None;
+    }
+
+    let lines = docs
+        .iter()
+        .map(|doc| {
+            let s = doc.to_string();
+            let s = s.trim_end();
+            let s = s.strip_prefix("#!").unwrap_or(s);
+
+            s.strip_prefix(' ').unwrap_or(s).to_string()
+        })
+        .collect::<Vec<String>>();
+
+    Some(lines.join("\n"))
+}
+
+pub fn join_docs(first: Option<String>, second: Option<String>) -> Option<String> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(format!("{}\n{}", first, second)),
+        (first, None) => first,
+        (None, second) => second,
+    }
+}
+
+pub fn token_index(tokens: &Tokens, loc: &Loc) -> Option<usize> {
+    (0..tokens.len()).find(|idx| tokens[*idx].loc().as_ref() == Some(loc))
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn docs_leading_docs() {
+        use super::{doc_text, join_docs, leading_docs, split_detached, token_index};
+        use crate::token::Tokens;
+
+        let s = "# not a doc comment
+#! Detached.
+
+#! First line.
+#! Second line.
+(val x 1)";
+
+        let tokens = Tokens::from_str(s).unwrap();
+        let loc = tokens[4].loc().unwrap();
+        let idx = token_index(&tokens, &loc).unwrap();
+
+        assert_eq!(idx, 4);
+
+        let docs = leading_docs(&tokens, idx);
+
+        assert_eq!(docs.len(), 3);
+        assert!(leading_docs(&tokens, 1).is_empty());
+
+        let (detached, attached) = split_detached(&docs, &tokens[idx]);
+
+        assert_eq!(doc_text(&detached), Some("Detached.".to_string()));
+        assert_eq!(
+            doc_text(&attached),
+            Some("First line.\nSecond line.".to_string())
+        );
+        assert_eq!(doc_text(&[]), None);
+
+        assert_eq!(
+            join_docs(doc_text(&detached), doc_text(&attached)),
+            Some("Detached.\nFirst line.\nSecond line.".to_string())
+        );
+        assert_eq!(join_docs(None, None), None);
+    }
+}
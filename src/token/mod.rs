@@ -1,6 +1,8 @@
+pub mod docs;
 #[allow(clippy::module_inception)]
 pub mod token;
 pub mod tokens;
 
+pub use self::docs::*;
 pub use self::token::*;
 pub use self::tokens::*;
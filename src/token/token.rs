@@ -1,10 +1,14 @@
 use crate::chunk::{StringChunk, StringChunks};
 use crate::loc::Loc;
+use crate::span::Span;
+use crate::syntax::unescape;
 use crate::syntax::EMPTY;
+use crate::syntax::{parse_float_literal, parse_int_literal, parse_uint_literal};
 use std::fmt;
 
-#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Default)]
 pub enum TokenKind {
+    #[default]
     Comment,
     DocComment,
     Keyword,
@@ -22,12 +26,6 @@ pub enum TokenKind {
     FormEnd,
 }
 
-impl Default for TokenKind {
-    fn default() -> Self {
-        TokenKind::Comment
-    }
-}
-
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
 pub struct Token {
     pub kind: TokenKind,
@@ -122,10 +120,63 @@ impl Token {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        let first = self.chunks.content.first()?;
+        let last = self.chunks.content.last()?;
+
+        Some(Span::from_locs(
+            first.loc.clone(),
+            last.loc.advance(&last.content),
+        ))
+    }
+
     pub fn push(&mut self, chunk: StringChunk) {
         self.chunks.push(chunk)
     }
 
+    pub fn to_uint(&self) -> Option<u64> {
+        match self.kind {
+            TokenKind::UIntLiteral => parse_uint_literal(&self.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn to_int(&self) -> Option<i64> {
+        match self.kind {
+            TokenKind::IntLiteral => parse_int_literal(&self.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn to_float(&self) -> Option<f64> {
+        match self.kind {
+            TokenKind::FloatLiteral => parse_float_literal(&self.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn to_char(&self) -> Option<char> {
+        match self.kind {
+            TokenKind::CharLiteral => self.to_unescaped().and_then(|s| s.chars().next()),
+            _ => None,
+        }
+    }
+
+    pub fn to_unescaped(&self) -> Option<String> {
+        match self.kind {
+            TokenKind::CharLiteral | TokenKind::StringLiteral => {
+                let s = self.to_string();
+
+                if s.len() < 2 {
+                    return None;
+                }
+
+                unescape(&s[1..s.len() - 1]).ok()
+            }
+            _ => None,
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         self.chunks.to_string()
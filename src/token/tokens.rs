@@ -1,15 +1,17 @@
-use crate::chunk::StringChunks;
+use crate::chunk::{StringChunk, StringChunks};
 use crate::error::{Error, SyntacticError};
 use crate::result::Result;
+use crate::span::Span;
 use crate::syntax::is_keyword;
-use crate::syntax::SINGLE_QUOTE;
+use crate::syntax::is_whitespace;
 use crate::syntax::{is_comment_mark, is_doc_comment_mark};
 use crate::syntax::{is_double_quote, is_single_quote};
-use crate::syntax::{is_escape_char, is_whitespace};
 use crate::syntax::{is_float_literal, is_int_literal, is_uint_literal};
 use crate::syntax::{is_form_end, is_form_start};
 use crate::syntax::{is_symbol, is_type_symbol, is_value_symbol};
 use crate::syntax::{is_type_path_symbol, is_value_path_symbol};
+use crate::syntax::{parse_float_literal, parse_int_literal, parse_uint_literal};
+use crate::syntax::{unescape, ESCAPE_CHAR, FORM_END};
 use crate::token::Token;
 use std::convert;
 use std::fmt;
@@ -38,9 +40,36 @@ impl Tokens {
         self.0.push(token)
     }
 
+    pub fn span(&self) -> Option<Span> {
+        let first = self.0.first()?.span()?;
+        let last = self.0.last()?.span()?;
+
+        Some(first.join(&last))
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Self> {
-        let chunks = StringChunks::from_str(s);
+        Tokens::from_chunks(StringChunks::from_str(s))
+    }
+
+    pub fn from_str_recovering(s: &str) -> (Self, Vec<Error>) {
+        Tokens::lex(StringChunks::from_str(s), true)
+    }
+
+    pub fn from_str_with_file(s: &str, file: &str) -> Result<Self> {
+        Tokens::from_chunks(StringChunks::from_str_with_file(s, file))
+    }
+
+    fn from_chunks(chunks: StringChunks) -> Result<Self> {
+        let (tokens, errors) = Tokens::lex(chunks, false);
+
+        match errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(tokens),
+        }
+    }
+
+    fn lex(chunks: StringChunks, recover: bool) -> (Self, Vec<Error>) {
         let len = chunks.len();
         let mut idx = 0;
 
@@ -49,6 +78,7 @@ impl Tokens {
         let mut close_form_idxs = vec![];
 
         let mut tokens = Tokens::new();
+        let mut errors = vec![];
 
         while idx < len {
             let chunk = chunks[idx].clone();
@@ -104,6 +134,17 @@ impl Tokens {
                     tokens.push(token);
                 }
                 x if is_uint_literal(&x) => {
+                    if parse_uint_literal(&x).is_none() {
+                        errors.push(Error::Syntactic(SyntacticError {
+                            loc: Some(chunk.loc.clone()),
+                            desc: format!("UInt literal {} exceeds 64 bits", x),
+                        }));
+
+                        if !recover {
+                            break;
+                        }
+                    }
+
                     let mut token = Token::new_uint_literal();
                     token.push(chunk.clone());
 
@@ -112,6 +153,17 @@ impl Tokens {
                     tokens.push(token);
                 }
                 x if is_int_literal(&x) => {
+                    if parse_int_literal(&x).is_none() {
+                        errors.push(Error::Syntactic(SyntacticError {
+                            loc: Some(chunk.loc.clone()),
+                            desc: format!("Int literal {} exceeds 64 bits", x),
+                        }));
+
+                        if !recover {
+                            break;
+                        }
+                    }
+
                     let mut token = Token::new_int_literal();
                     token.push(chunk.clone());
 
@@ -120,6 +172,17 @@ impl Tokens {
                     tokens.push(token);
                 }
                 x if is_float_literal(&x) => {
+                    if parse_float_literal(&x).is_none() {
+                        errors.push(Error::Syntactic(SyntacticError {
+                            loc: Some(chunk.loc.clone()),
+                            desc: format!("Float literal {} overflows", x),
+                        }));
+
+                        if !recover {
+                            break;
+                        }
+                    }
+
                     let mut token = Token::new_float_literal();
                     token.push(chunk.clone());
 
@@ -127,95 +190,75 @@ impl Tokens {
 
                     tokens.push(token);
                 }
-                x if is_single_quote(&x) => {
-                    if idx + 2 > len {
-                        return Err(Error::Syntactic(SyntacticError {
-                            loc: Some(chunks[idx].loc.clone()),
-                            desc: format!("expected {} to be a char", x),
-                        }));
-                    }
-
+                x if is_single_quote(&x) || is_double_quote(&x) => {
+                    let is_char = is_single_quote(&x);
+                    let kind = if is_char { "char" } else { "string" };
                     let mut schunk = chunk;
-                    let mut rem_len = 2;
+                    let mut closed = false;
 
-                    while rem_len > 0 {
-                        idx += 1;
+                    idx += 1;
 
+                    while idx < len {
                         let c = chunks[idx].content.clone();
-                        if c.len() != 1 {
-                            return Err(Error::Syntactic(SyntacticError {
-                                loc: Some(chunks[idx].loc.clone()),
-                                desc: format!("expected {} to be a char", x),
-                            }));
-                        }
-
-                        if is_escape_char(&c)
-                            && idx + 2 < len
-                            && (is_single_quote(&chunks[idx + 1].content)
-                                || is_escape_char(&chunks[idx + 1].content))
-                        {
-                            continue;
-                        }
+                        let escapes = schunk.content[1..]
+                            .chars()
+                            .rev()
+                            .take_while(|c| *c == ESCAPE_CHAR)
+                            .count();
 
                         schunk.content.push_str(&c);
 
-                        rem_len -= 1;
-                    }
+                        idx += 1;
 
-                    if !schunk.content.ends_with(SINGLE_QUOTE) {
-                        return Err(Error::Syntactic(SyntacticError {
-                            loc: Some(chunks[idx].loc.clone()),
-                            desc: format!("expected {} to be a char", x),
-                        }));
+                        if c == x && escapes % 2 == 0 {
+                            closed = true;
+                            break;
+                        }
                     }
 
-                    let mut token = Token::new_char_literal();
-                    token.push(schunk);
-
-                    tokens.push(token);
-
-                    idx += 1;
-                }
-                mut x if is_double_quote(&x) => {
-                    let mut schunk = chunk;
-
-                    if idx + 1 >= len {
-                        return Err(Error::Syntactic(SyntacticError {
-                            loc: Some(chunks[idx].loc.clone()),
-                            desc: format!("expected {} to be a string", x),
+                    if !closed {
+                        errors.push(Error::Syntactic(SyntacticError {
+                            loc: Some(schunk.loc.clone()),
+                            desc: format!("expected {} to be a {}", x, kind),
                         }));
-                    }
 
-                    idx += 1;
+                        break;
+                    }
 
-                    while idx < len {
-                        x = chunks[idx].content.clone();
-                        schunk.content.push_str(&x);
-
-                        if is_escape_char(&x)
-                            && idx + 2 < len
-                            && (is_double_quote(&chunks[idx + 1].content)
-                                || is_escape_char(&chunks[idx + 1].content))
-                        {
-                            idx += 1;
-                            x = chunks[idx].content.clone();
-                            schunk.content.push_str(&x);
-                            idx += 1;
-                            continue;
+                    let content = &schunk.content[1..schunk.content.len() - 1];
+
+                    let error = match unescape(content) {
+                        Err((offset, desc)) => Some(SyntacticError {
+                            loc: Some(schunk.loc.advance(
+                                &schunk.content.chars().take(offset + 1).collect::<String>(),
+                            )),
+                            desc,
+                        }),
+                        Ok(decoded) if is_char && decoded.chars().count() != 1 => {
+                            Some(SyntacticError {
+                                loc: Some(schunk.loc.clone()),
+                                desc: format!("expected {} to be a char", schunk.content),
+                            })
                         }
+                        Ok(_) => None,
+                    };
 
-                        if is_double_quote(&x) {
+                    if let Some(error) = error {
+                        errors.push(Error::Syntactic(error));
+
+                        if !recover {
                             break;
                         }
-
-                        idx += 1;
                     }
 
-                    let mut token = Token::new_string_literal();
-                    token.push(schunk.clone());
-                    tokens.push(token);
+                    let mut token = if is_char {
+                        Token::new_char_literal()
+                    } else {
+                        Token::new_string_literal()
+                    };
 
-                    idx += 1;
+                    token.push(schunk);
+                    tokens.push(token);
                 }
                 mut x if is_form_start(&x) => {
                     forms_count += 1;
@@ -258,10 +301,19 @@ impl Tokens {
                     forms_count -= 1;
 
                     if forms_count < 0 {
-                        return Err(Error::Syntactic(SyntacticError {
+                        errors.push(Error::Syntactic(SyntacticError {
                             loc: Some(chunks[idx].loc.clone()),
                             desc: "closing a form never opened".into(),
                         }));
+
+                        if !recover {
+                            break;
+                        }
+
+                        forms_count = 0;
+                        idx += 1;
+
+                        continue;
                     }
 
                     open_form_idxs.pop();
@@ -297,17 +349,30 @@ impl Tokens {
                     idx += 1;
                 }
                 _ => {
-                    return Err(Error::Syntactic(SyntacticError {
+                    errors.push(Error::Syntactic(SyntacticError {
                         loc: Some(chunks[idx].loc.clone()),
                         desc: "unrecognized syntax".into(),
                     }));
+
+                    if !recover {
+                        break;
+                    }
+
+                    idx += 1;
                 }
             }
         }
 
+        if !errors.is_empty() && !recover {
+            return (tokens, errors);
+        }
+
         if forms_count != 0 {
             let (err_idx, desc): (usize, String) = if forms_count > 0 {
-                (open_form_idxs.remove(0), "form not closed".into())
+                (
+                    open_form_idxs[open_form_idxs.len() - forms_count as usize],
+                    "form not closed".into(),
+                )
             } else {
                 (
                     close_form_idxs.pop().unwrap(),
@@ -315,13 +380,31 @@ impl Tokens {
                 )
             };
 
-            return Err(Error::Syntactic(SyntacticError {
+            errors.push(Error::Syntactic(SyntacticError {
                 loc: Some(chunks[err_idx].loc.clone()),
                 desc,
             }));
+
+            if recover {
+                let loc = chunks
+                    .content
+                    .last()
+                    .map(|chunk| chunk.loc.advance(&chunk.content))
+                    .unwrap_or_default();
+
+                for _ in 0..forms_count {
+                    let mut token = Token::new_form_end();
+                    token.push(StringChunk {
+                        loc: loc.clone(),
+                        content: FORM_END.to_string(),
+                    });
+
+                    tokens.push(token);
+                }
+            }
         }
 
-        Ok(tokens)
+        (tokens, errors)
     }
 
     pub fn from_string(s: String) -> Result<Self> {
@@ -329,7 +412,9 @@ impl Tokens {
     }
 
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        Self::from_string(fs::read_to_string(path)?)
+        let s = fs::read_to_string(&path)?;
+
+        Self::from_str_with_file(&s, &path.as_ref().display().to_string())
     }
 
     #[allow(clippy::inherent_to_string_shadow_display)]
@@ -459,12 +544,29 @@ mod tests {
         use super::Tokens;
         use crate::token::TokenKind;
 
-        let s = "XFFF";
+        let mut s = "XFFF";
 
         let tokens = Tokens::from_str(s).unwrap();
 
         assert_eq!(tokens.len(), 1);
         assert_eq!(tokens[0].kind, TokenKind::UIntLiteral);
+        assert_eq!(tokens[0].to_uint(), Some(4095));
+        assert_eq!(tokens[0].to_int(), None);
+
+        s = "18446744073709551615";
+
+        assert_eq!(Tokens::from_str(s).unwrap()[0].to_uint(), Some(u64::MAX));
+
+        s = "(a\n 18446744073709551616)";
+
+        let res = Tokens::from_str(s);
+
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "syntactic error at (file: none, line: 1, pos: 1): UInt literal 18446744073709551616 exceeds 64 bits"
+                .to_string()
+        );
     }
 
     #[test]
@@ -472,12 +574,27 @@ mod tests {
         use super::Tokens;
         use crate::token::TokenKind;
 
-        let s = "-o476";
+        let mut s = "-o476";
 
         let tokens = Tokens::from_str(s).unwrap();
 
         assert_eq!(tokens.len(), 1);
         assert_eq!(tokens[0].kind, TokenKind::IntLiteral);
+        assert_eq!(tokens[0].to_int(), Some(-318));
+
+        s = "-9223372036854775808";
+
+        assert_eq!(Tokens::from_str(s).unwrap()[0].to_int(), Some(i64::MIN));
+
+        s = "+9223372036854775808";
+
+        let res = Tokens::from_str(s);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Int literal +9223372036854775808 exceeds 64 bits"));
     }
 
     #[test]
@@ -485,12 +602,23 @@ mod tests {
         use super::Tokens;
         use crate::token::TokenKind;
 
-        let s = "-0.1E-10";
+        let mut s = "-0.1E-10";
 
         let tokens = Tokens::from_str(s).unwrap();
 
         assert_eq!(tokens.len(), 1);
         assert_eq!(tokens[0].kind, TokenKind::FloatLiteral);
+        assert_eq!(tokens[0].to_float(), Some(-0.1E-10));
+
+        s = "1.0E400";
+
+        let res = Tokens::from_str(s);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("Float literal 1.0E400 overflows"));
     }
 
     #[test]
@@ -498,15 +626,42 @@ mod tests {
         use super::Tokens;
         use crate::token::TokenKind;
 
-        let s = "'\\'";
+        let mut s = "'\\\\'";
 
         let tokens = Tokens::from_str(s).unwrap();
 
         assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].to_char(), Some('\\'));
 
         for token in tokens.into_iter() {
             assert_eq!(token.kind, TokenKind::CharLiteral);
         }
+
+        let cases = [
+            ("'\\n'", '\n'),
+            ("'\\''", '\''),
+            ("'\\x41'", 'A'),
+            ("'\\u{3bb}'", '\u{3bb}'),
+            ("'\u{3bb}'", '\u{3bb}'),
+        ];
+
+        for (s, expected) in cases.iter() {
+            assert_eq!(Tokens::from_str(s).unwrap()[0].to_char(), Some(*expected));
+        }
+
+        s = "'\\'";
+
+        assert!(Tokens::from_str(s).is_err());
+
+        s = "'ab'";
+
+        let res = Tokens::from_str(s);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected 'ab' to be a char"));
     }
 
     #[test]
@@ -514,12 +669,45 @@ mod tests {
         use super::Tokens;
         use crate::token::TokenKind;
 
-        let s = "\"\\\"";
+        let mut s = "\"a\\\"b\\\\\"";
 
         let tokens = Tokens::from_str(s).unwrap();
 
         assert_eq!(tokens.len(), 1);
         assert_eq!(tokens[0].kind, TokenKind::StringLiteral);
+        assert_eq!(tokens[0].to_string(), s.to_string());
+        assert_eq!(tokens[0].to_unescaped(), Some("a\"b\\".to_string()));
+
+        s = "\"x: {}\\n\\t\\x7e\\u{1F600}\"";
+
+        assert_eq!(
+            Tokens::from_str(s).unwrap()[0].to_unescaped(),
+            Some("x: {}\n\t~\u{1F600}".to_string())
+        );
+
+        let cases = [
+            (
+                "\"ab\\qc\"",
+                "(file: none, line: 0, pos: 3): unknown escape \\q",
+            ),
+            (
+                "(f\n  \"a\\x80\")",
+                "(file: none, line: 1, pos: 4): invalid hex escape \\x80",
+            ),
+            (
+                "\"a\nb\\u{110000}\"",
+                "(file: none, line: 1, pos: 1): invalid unicode escape \\u{110000}",
+            ),
+            ("\"\\u12\"", "unicode escape must have the form \\u{...}"),
+            ("\"abc", "expected \" to be a string"),
+        ];
+
+        for (s, expected) in cases.iter() {
+            let res = Tokens::from_str(s);
+
+            assert!(res.is_err());
+            assert!(res.unwrap_err().to_string().contains(expected));
+        }
     }
 
     #[test]
@@ -647,6 +835,47 @@ mod tests {
         assert_eq!(tokens[17].kind, TokenKind::Comment);
     }
 
+    #[test]
+    fn tokens_from_str_recovering() {
+        use super::Tokens;
+        use crate::token::TokenKind;
+
+        let s = "(a aB0c-d.,ef_!+/9 \"\\q\") ) (b";
+
+        let (tokens, errors) = Tokens::from_str_recovering(s);
+
+        let descs = errors
+            .iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(descs.len(), 4);
+        assert!(descs[0].contains("unrecognized syntax"));
+        assert!(descs[1].contains("pos: 20): unknown escape \\q"));
+        assert!(descs[2].contains("pos: 25): closing a form never opened"));
+        assert!(descs[3].contains("pos: 27): form not closed"));
+
+        let kinds = tokens
+            .into_iter()
+            .map(|token| token.kind)
+            .collect::<Vec<TokenKind>>();
+
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::FormStart,
+                TokenKind::ValueSymbol,
+                TokenKind::StringLiteral,
+                TokenKind::FormEnd,
+                TokenKind::FormStart,
+                TokenKind::ValueSymbol,
+                TokenKind::FormEnd,
+            ]
+        );
+
+        assert!(Tokens::from_str(s).is_err());
+    }
+
     #[test]
     fn tokens_from_file() {
         use super::Tokens;
@@ -668,7 +897,8 @@ mod tests {
         assert_eq!(tokens[8].kind, TokenKind::ValuePathSymbol);
         assert_eq!(tokens[14].kind, TokenKind::ValueSymbol);
         assert_eq!(tokens[37].kind, TokenKind::CharLiteral);
-        assert_eq!(tokens[37].chunks[0].content, "'''".to_string());
+        assert_eq!(tokens[37].chunks[0].content, "'\\''".to_string());
+        assert_eq!(tokens[37].to_char(), Some('\''));
         assert_eq!(tokens[38].kind, TokenKind::StringLiteral);
     }
 }
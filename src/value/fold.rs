@@ -0,0 +1,1236 @@
+use crate::program::Program;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::attrs_form::{AttrsForm, AttrsFormValue};
+use crate::value::forms::block_form::{BlockForm, BlockFormEntry};
+use crate::value::forms::case_form::{
+    CaseForm, CaseFormMatch, CaseFormMatchAction, CaseFormMatchCase, CaseFormVariable,
+};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::error_form::ErrorForm;
+use crate::value::forms::export_form::{ExportForm, ExportFormDef};
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::MemForm;
+use crate::value::forms::module_form::{ModuleForm, ModuleFormBlock};
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::simple_value::SimpleValue;
+use crate::value::types::{
+    ArrType, EnumType, FunType, ListType, MapType, PairType, SimpleType, Type, VecType,
+};
+
+pub trait Fold {
+    fn fold_program(&mut self, node: Program) -> Program {
+        fold_program(self, node)
+    }
+
+    fn fold_module_form(&mut self, node: ModuleForm) -> ModuleForm {
+        fold_module_form(self, node)
+    }
+
+    fn fold_module_form_block(&mut self, node: ModuleFormBlock) -> ModuleFormBlock {
+        fold_module_form_block(self, node)
+    }
+
+    fn fold_block_form(&mut self, node: BlockForm) -> BlockForm {
+        fold_block_form(self, node)
+    }
+
+    fn fold_block_form_entry(&mut self, node: BlockFormEntry) -> BlockFormEntry {
+        fold_block_form_entry(self, node)
+    }
+
+    fn fold_import_form(&mut self, node: ImportForm) -> ImportForm {
+        fold_import_form(self, node)
+    }
+
+    fn fold_import_form_def(&mut self, node: ImportFormDef) -> ImportFormDef {
+        fold_import_form_def(self, node)
+    }
+
+    fn fold_export_form(&mut self, node: ExportForm) -> ExportForm {
+        fold_export_form(self, node)
+    }
+
+    fn fold_export_form_def(&mut self, node: ExportFormDef) -> ExportFormDef {
+        fold_export_form_def(self, node)
+    }
+
+    fn fold_attrs_form(&mut self, node: AttrsForm) -> AttrsForm {
+        fold_attrs_form(self, node)
+    }
+
+    fn fold_attrs_form_value(&mut self, node: AttrsFormValue) -> AttrsFormValue {
+        fold_attrs_form_value(self, node)
+    }
+
+    fn fold_type_form(&mut self, node: TypeForm) -> TypeForm {
+        fold_type_form(self, node)
+    }
+
+    fn fold_sig_form(&mut self, node: SigForm) -> SigForm {
+        fold_sig_form(self, node)
+    }
+
+    fn fold_val_form(&mut self, node: ValForm) -> ValForm {
+        fold_val_form(self, node)
+    }
+
+    fn fold_val_form_value(&mut self, node: ValFormValue) -> ValFormValue {
+        fold_val_form_value(self, node)
+    }
+
+    fn fold_error_form(&mut self, node: ErrorForm) -> ErrorForm {
+        fold_error_form(self, node)
+    }
+
+    fn fold_fun_form(&mut self, node: FunForm) -> FunForm {
+        fold_fun_form(self, node)
+    }
+
+    fn fold_fun_form_parameter(&mut self, node: FunFormParameter) -> FunFormParameter {
+        fold_fun_form_parameter(self, node)
+    }
+
+    fn fold_fun_form_body(&mut self, node: FunFormBody) -> FunFormBody {
+        fold_fun_form_body(self, node)
+    }
+
+    fn fold_let_form(&mut self, node: LetForm) -> LetForm {
+        fold_let_form(self, node)
+    }
+
+    fn fold_let_form_entry(&mut self, node: LetFormEntry) -> LetFormEntry {
+        fold_let_form_entry(self, node)
+    }
+
+    fn fold_app_form(&mut self, node: AppForm) -> AppForm {
+        fold_app_form(self, node)
+    }
+
+    fn fold_app_form_value(&mut self, node: AppFormValue) -> AppFormValue {
+        fold_app_form_value(self, node)
+    }
+
+    fn fold_case_form(&mut self, node: CaseForm) -> CaseForm {
+        fold_case_form(self, node)
+    }
+
+    fn fold_case_form_variable(&mut self, node: CaseFormVariable) -> CaseFormVariable {
+        fold_case_form_variable(self, node)
+    }
+
+    fn fold_case_form_match(&mut self, node: CaseFormMatch) -> CaseFormMatch {
+        fold_case_form_match(self, node)
+    }
+
+    fn fold_case_form_match_case(&mut self, node: CaseFormMatchCase) -> CaseFormMatchCase {
+        fold_case_form_match_case(self, node)
+    }
+
+    fn fold_case_form_match_action(&mut self, node: CaseFormMatchAction) -> CaseFormMatchAction {
+        fold_case_form_match_action(self, node)
+    }
+
+    fn fold_pair_form(&mut self, node: PairForm) -> PairForm {
+        fold_pair_form(self, node)
+    }
+
+    fn fold_pair_form_value(&mut self, node: PairFormValue) -> PairFormValue {
+        fold_pair_form_value(self, node)
+    }
+
+    fn fold_list_form(&mut self, node: ListForm) -> ListForm {
+        fold_list_form(self, node)
+    }
+
+    fn fold_list_form_value(&mut self, node: ListFormValue) -> ListFormValue {
+        fold_list_form_value(self, node)
+    }
+
+    fn fold_arr_form(&mut self, node: ArrForm) -> ArrForm {
+        fold_arr_form(self, node)
+    }
+
+    fn fold_arr_form_value(&mut self, node: ArrFormValue) -> ArrFormValue {
+        fold_arr_form_value(self, node)
+    }
+
+    fn fold_vec_form(&mut self, node: VecForm) -> VecForm {
+        fold_vec_form(self, node)
+    }
+
+    fn fold_vec_form_value(&mut self, node: VecFormValue) -> VecFormValue {
+        fold_vec_form_value(self, node)
+    }
+
+    fn fold_map_form(&mut self, node: MapForm) -> MapForm {
+        fold_map_form(self, node)
+    }
+
+    fn fold_map_form_entry(&mut self, node: MapFormEntry) -> MapFormEntry {
+        fold_map_form_entry(self, node)
+    }
+
+    fn fold_mem_form(&mut self, node: MemForm) -> MemForm {
+        fold_mem_form(self, node)
+    }
+
+    fn fold_linear_form(&mut self, node: LinearForm) -> LinearForm {
+        fold_linear_form(self, node)
+    }
+
+    fn fold_cast_form(&mut self, node: CastForm) -> CastForm {
+        fold_cast_form(self, node)
+    }
+
+    fn fold_type(&mut self, node: Type) -> Type {
+        fold_type(self, node)
+    }
+
+    fn fold_simple_type(&mut self, node: SimpleType) -> SimpleType {
+        fold_simple_type(self, node)
+    }
+
+    fn fold_enum_type(&mut self, node: EnumType) -> EnumType {
+        fold_enum_type(self, node)
+    }
+
+    fn fold_pair_type(&mut self, node: PairType) -> PairType {
+        fold_pair_type(self, node)
+    }
+
+    fn fold_list_type(&mut self, node: ListType) -> ListType {
+        fold_list_type(self, node)
+    }
+
+    fn fold_arr_type(&mut self, node: ArrType) -> ArrType {
+        fold_arr_type(self, node)
+    }
+
+    fn fold_vec_type(&mut self, node: VecType) -> VecType {
+        fold_vec_type(self, node)
+    }
+
+    fn fold_map_type(&mut self, node: MapType) -> MapType {
+        fold_map_type(self, node)
+    }
+
+    fn fold_fun_type(&mut self, node: FunType) -> FunType {
+        fold_fun_type(self, node)
+    }
+
+    fn fold_simple_value(&mut self, node: SimpleValue) -> SimpleValue {
+        node
+    }
+}
+
+pub fn fold_program<F: Fold + ?Sized>(folder: &mut F, node: Program) -> Program {
+    Program {
+        modules: node
+            .modules
+            .into_iter()
+            .map(|item| folder.fold_module_form(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_module_form<F: Fold + ?Sized>(folder: &mut F, node: ModuleForm) -> ModuleForm {
+    ModuleForm {
+        name: folder.fold_simple_value(node.name),
+        type_parameters: node
+            .type_parameters
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        block: folder.fold_module_form_block(node.block),
+        ..node
+    }
+}
+
+pub fn fold_module_form_block<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: ModuleFormBlock,
+) -> ModuleFormBlock {
+    match node {
+        ModuleFormBlock::Empty(value) => ModuleFormBlock::Empty(folder.fold_simple_value(value)),
+        ModuleFormBlock::Form(value) => {
+            ModuleFormBlock::Form(Box::new(folder.fold_block_form(*value)))
+        }
+    }
+}
+
+pub fn fold_block_form<F: Fold + ?Sized>(folder: &mut F, node: BlockForm) -> BlockForm {
+    BlockForm {
+        entries: node
+            .entries
+            .into_iter()
+            .map(|item| folder.fold_block_form_entry(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_block_form_entry<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: BlockFormEntry,
+) -> BlockFormEntry {
+    match node {
+        BlockFormEntry::Empty(value) => BlockFormEntry::Empty(folder.fold_simple_value(value)),
+        BlockFormEntry::ImportForm(value) => {
+            BlockFormEntry::ImportForm(Box::new(folder.fold_import_form(*value)))
+        }
+        BlockFormEntry::ExportForm(value) => {
+            BlockFormEntry::ExportForm(Box::new(folder.fold_export_form(*value)))
+        }
+        BlockFormEntry::AttrsForm(value) => {
+            BlockFormEntry::AttrsForm(Box::new(folder.fold_attrs_form(*value)))
+        }
+        BlockFormEntry::TypeForm(value) => {
+            BlockFormEntry::TypeForm(Box::new(folder.fold_type_form(*value)))
+        }
+        BlockFormEntry::SigForm(value) => {
+            BlockFormEntry::SigForm(Box::new(folder.fold_sig_form(*value)))
+        }
+        BlockFormEntry::ValForm(value) => {
+            BlockFormEntry::ValForm(Box::new(folder.fold_val_form(*value)))
+        }
+        BlockFormEntry::ErrorForm(value) => {
+            BlockFormEntry::ErrorForm(Box::new(folder.fold_error_form(*value)))
+        }
+    }
+}
+
+pub fn fold_import_form<F: Fold + ?Sized>(folder: &mut F, node: ImportForm) -> ImportForm {
+    ImportForm {
+        module: folder.fold_simple_value(node.module),
+        qualifier: node.qualifier.map(|item| folder.fold_simple_value(item)),
+        type_variables: node
+            .type_variables
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        defs: node
+            .defs
+            .into_iter()
+            .map(|item| folder.fold_import_form_def(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_import_form_def<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: ImportFormDef,
+) -> ImportFormDef {
+    match node {
+        ImportFormDef::Ignore(value) => ImportFormDef::Ignore(folder.fold_simple_value(value)),
+        ImportFormDef::Empty(value) => ImportFormDef::Empty(folder.fold_simple_value(value)),
+        ImportFormDef::ValueSymbol(value) => {
+            ImportFormDef::ValueSymbol(folder.fold_simple_value(value))
+        }
+        ImportFormDef::TypeSymbol(value) => {
+            ImportFormDef::TypeSymbol(folder.fold_simple_value(value))
+        }
+    }
+}
+
+pub fn fold_export_form<F: Fold + ?Sized>(folder: &mut F, node: ExportForm) -> ExportForm {
+    ExportForm {
+        defs: node
+            .defs
+            .into_iter()
+            .map(|item| folder.fold_export_form_def(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_export_form_def<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: ExportFormDef,
+) -> ExportFormDef {
+    match node {
+        ExportFormDef::Empty(value) => ExportFormDef::Empty(folder.fold_simple_value(value)),
+        ExportFormDef::ValueSymbol(value) => {
+            ExportFormDef::ValueSymbol(folder.fold_simple_value(value))
+        }
+        ExportFormDef::TypeSymbol(value) => {
+            ExportFormDef::TypeSymbol(folder.fold_simple_value(value))
+        }
+    }
+}
+
+pub fn fold_attrs_form<F: Fold + ?Sized>(folder: &mut F, node: AttrsForm) -> AttrsForm {
+    AttrsForm {
+        name: folder.fold_simple_value(node.name),
+        values: node
+            .values
+            .into_iter()
+            .map(|item| folder.fold_attrs_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_attrs_form_value<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: AttrsFormValue,
+) -> AttrsFormValue {
+    match node {
+        AttrsFormValue::Empty(value) => AttrsFormValue::Empty(folder.fold_simple_value(value)),
+        AttrsFormValue::Panic(value) => AttrsFormValue::Panic(folder.fold_simple_value(value)),
+        AttrsFormValue::Atomic(value) => AttrsFormValue::Atomic(folder.fold_simple_value(value)),
+        AttrsFormValue::ValueSymbol(value) => {
+            AttrsFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        AttrsFormValue::TypeSymbol(value) => {
+            AttrsFormValue::TypeSymbol(folder.fold_simple_value(value))
+        }
+        AttrsFormValue::ValuePathSymbol(value) => {
+            AttrsFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        AttrsFormValue::TypePathSymbol(value) => {
+            AttrsFormValue::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        AttrsFormValue::Map(value) => AttrsFormValue::Map(Box::new(folder.fold_map_form(*value))),
+    }
+}
+
+pub fn fold_type_form<F: Fold + ?Sized>(folder: &mut F, node: TypeForm) -> TypeForm {
+    TypeForm {
+        name: folder.fold_simple_value(node.name),
+        value: Box::new(folder.fold_type(*node.value)),
+        ..node
+    }
+}
+
+pub fn fold_sig_form<F: Fold + ?Sized>(folder: &mut F, node: SigForm) -> SigForm {
+    SigForm {
+        name: folder.fold_simple_value(node.name),
+        value: Box::new(folder.fold_type(*node.value)),
+        ..node
+    }
+}
+
+pub fn fold_val_form<F: Fold + ?Sized>(folder: &mut F, node: ValForm) -> ValForm {
+    ValForm {
+        name: folder.fold_simple_value(node.name),
+        value: folder.fold_val_form_value(node.value),
+        ..node
+    }
+}
+
+pub fn fold_val_form_value<F: Fold + ?Sized>(folder: &mut F, node: ValFormValue) -> ValFormValue {
+    match node {
+        ValFormValue::Empty(value) => ValFormValue::Empty(folder.fold_simple_value(value)),
+        ValFormValue::Panic(value) => ValFormValue::Panic(folder.fold_simple_value(value)),
+        ValFormValue::Atomic(value) => ValFormValue::Atomic(folder.fold_simple_value(value)),
+        ValFormValue::Builtin(value) => ValFormValue::Builtin(folder.fold_simple_value(value)),
+        ValFormValue::ValueSymbol(value) => {
+            ValFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        ValFormValue::PairForm(value) => {
+            ValFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        ValFormValue::FunForm(value) => {
+            ValFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        ValFormValue::LetForm(value) => {
+            ValFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        ValFormValue::AppForm(value) => {
+            ValFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        ValFormValue::MemForm(value) => {
+            ValFormValue::MemForm(Box::new(folder.fold_mem_form(*value)))
+        }
+        ValFormValue::LinearForm(value) => {
+            ValFormValue::LinearForm(Box::new(folder.fold_linear_form(*value)))
+        }
+        ValFormValue::CastForm(value) => {
+            ValFormValue::CastForm(Box::new(folder.fold_cast_form(*value)))
+        }
+        ValFormValue::CaseForm(value) => {
+            ValFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+    }
+}
+
+pub fn fold_error_form<F: Fold + ?Sized>(_folder: &mut F, node: ErrorForm) -> ErrorForm {
+    node
+}
+
+pub fn fold_fun_form<F: Fold + ?Sized>(folder: &mut F, node: FunForm) -> FunForm {
+    FunForm {
+        parameters: node
+            .parameters
+            .into_iter()
+            .map(|item| folder.fold_fun_form_parameter(item))
+            .collect(),
+        body: folder.fold_fun_form_body(node.body),
+        ..node
+    }
+}
+
+pub fn fold_fun_form_parameter<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: FunFormParameter,
+) -> FunFormParameter {
+    match node {
+        FunFormParameter::Empty(value) => FunFormParameter::Empty(folder.fold_simple_value(value)),
+        FunFormParameter::ValueSymbol(value) => {
+            FunFormParameter::ValueSymbol(folder.fold_simple_value(value))
+        }
+        FunFormParameter::MapForm(value) => {
+            FunFormParameter::MapForm(Box::new(folder.fold_map_form(*value)))
+        }
+        FunFormParameter::VecForm(value) => {
+            FunFormParameter::VecForm(Box::new(folder.fold_vec_form(*value)))
+        }
+        FunFormParameter::ArrForm(value) => {
+            FunFormParameter::ArrForm(Box::new(folder.fold_arr_form(*value)))
+        }
+        FunFormParameter::ListForm(value) => {
+            FunFormParameter::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+        FunFormParameter::PairForm(value) => {
+            FunFormParameter::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+    }
+}
+
+pub fn fold_fun_form_body<F: Fold + ?Sized>(folder: &mut F, node: FunFormBody) -> FunFormBody {
+    match node {
+        FunFormBody::Empty(value) => FunFormBody::Empty(folder.fold_simple_value(value)),
+        FunFormBody::Panic(value) => FunFormBody::Panic(folder.fold_simple_value(value)),
+        FunFormBody::Atomic(value) => FunFormBody::Atomic(folder.fold_simple_value(value)),
+        FunFormBody::ValueSymbol(value) => {
+            FunFormBody::ValueSymbol(folder.fold_simple_value(value))
+        }
+        FunFormBody::ValuePathSymbol(value) => {
+            FunFormBody::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        FunFormBody::MapForm(value) => FunFormBody::MapForm(Box::new(folder.fold_map_form(*value))),
+        FunFormBody::VecForm(value) => FunFormBody::VecForm(Box::new(folder.fold_vec_form(*value))),
+        FunFormBody::ArrForm(value) => FunFormBody::ArrForm(Box::new(folder.fold_arr_form(*value))),
+        FunFormBody::ListForm(value) => {
+            FunFormBody::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+        FunFormBody::PairForm(value) => {
+            FunFormBody::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        FunFormBody::AppForm(value) => FunFormBody::AppForm(Box::new(folder.fold_app_form(*value))),
+        FunFormBody::MemForm(value) => FunFormBody::MemForm(Box::new(folder.fold_mem_form(*value))),
+        FunFormBody::LinearForm(value) => {
+            FunFormBody::LinearForm(Box::new(folder.fold_linear_form(*value)))
+        }
+        FunFormBody::CastForm(value) => {
+            FunFormBody::CastForm(Box::new(folder.fold_cast_form(*value)))
+        }
+        FunFormBody::LetForm(value) => FunFormBody::LetForm(Box::new(folder.fold_let_form(*value))),
+        FunFormBody::CaseForm(value) => {
+            FunFormBody::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        FunFormBody::FunForm(value) => FunFormBody::FunForm(Box::new(folder.fold_fun_form(*value))),
+    }
+}
+
+pub fn fold_let_form<F: Fold + ?Sized>(folder: &mut F, node: LetForm) -> LetForm {
+    LetForm {
+        entries: node
+            .entries
+            .into_iter()
+            .map(|item| folder.fold_let_form_entry(item))
+            .collect(),
+        value: folder.fold_app_form_value(node.value),
+        ..node
+    }
+}
+
+pub fn fold_let_form_entry<F: Fold + ?Sized>(folder: &mut F, node: LetFormEntry) -> LetFormEntry {
+    match node {
+        LetFormEntry::Empty(value) => LetFormEntry::Empty(folder.fold_simple_value(value)),
+        LetFormEntry::ImportForm(value) => {
+            LetFormEntry::ImportForm(Box::new(folder.fold_import_form(*value)))
+        }
+        LetFormEntry::AttrsForm(value) => {
+            LetFormEntry::AttrsForm(Box::new(folder.fold_attrs_form(*value)))
+        }
+        LetFormEntry::TypeForm(value) => {
+            LetFormEntry::TypeForm(Box::new(folder.fold_type_form(*value)))
+        }
+        LetFormEntry::SigForm(value) => {
+            LetFormEntry::SigForm(Box::new(folder.fold_sig_form(*value)))
+        }
+        LetFormEntry::ValForm(value) => {
+            LetFormEntry::ValForm(Box::new(folder.fold_val_form(*value)))
+        }
+    }
+}
+
+pub fn fold_app_form<F: Fold + ?Sized>(folder: &mut F, node: AppForm) -> AppForm {
+    AppForm {
+        name: folder.fold_simple_value(node.name),
+        variables: node
+            .variables
+            .into_iter()
+            .map(|item| folder.fold_app_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_app_form_value<F: Fold + ?Sized>(folder: &mut F, node: AppFormValue) -> AppFormValue {
+    match node {
+        AppFormValue::Ignore(value) => AppFormValue::Ignore(folder.fold_simple_value(value)),
+        AppFormValue::Empty(value) => AppFormValue::Empty(folder.fold_simple_value(value)),
+        AppFormValue::Panic(value) => AppFormValue::Panic(folder.fold_simple_value(value)),
+        AppFormValue::Atomic(value) => AppFormValue::Atomic(folder.fold_simple_value(value)),
+        AppFormValue::ValueSymbol(value) => {
+            AppFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        AppFormValue::ValuePathSymbol(value) => {
+            AppFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        AppFormValue::PairForm(value) => {
+            AppFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        AppFormValue::FunForm(value) => {
+            AppFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        AppFormValue::LetForm(value) => {
+            AppFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        AppFormValue::CaseForm(value) => {
+            AppFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        AppFormValue::AppForm(value) => {
+            AppFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        AppFormValue::MemForm(value) => {
+            AppFormValue::MemForm(Box::new(folder.fold_mem_form(*value)))
+        }
+        AppFormValue::LinearForm(value) => {
+            AppFormValue::LinearForm(Box::new(folder.fold_linear_form(*value)))
+        }
+        AppFormValue::CastForm(value) => {
+            AppFormValue::CastForm(Box::new(folder.fold_cast_form(*value)))
+        }
+    }
+}
+
+pub fn fold_case_form<F: Fold + ?Sized>(folder: &mut F, node: CaseForm) -> CaseForm {
+    CaseForm {
+        variable: folder.fold_case_form_variable(node.variable),
+        matches: node
+            .matches
+            .into_iter()
+            .map(|item| folder.fold_case_form_match(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_case_form_variable<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: CaseFormVariable,
+) -> CaseFormVariable {
+    match node {
+        CaseFormVariable::Empty(value) => CaseFormVariable::Empty(folder.fold_simple_value(value)),
+        CaseFormVariable::Atomic(value) => {
+            CaseFormVariable::Atomic(folder.fold_simple_value(value))
+        }
+        CaseFormVariable::ValueSymbol(value) => {
+            CaseFormVariable::ValueSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormVariable::AppForm(value) => {
+            CaseFormVariable::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        CaseFormVariable::MemForm(value) => {
+            CaseFormVariable::MemForm(Box::new(folder.fold_mem_form(*value)))
+        }
+        CaseFormVariable::LinearForm(value) => {
+            CaseFormVariable::LinearForm(Box::new(folder.fold_linear_form(*value)))
+        }
+        CaseFormVariable::CastForm(value) => {
+            CaseFormVariable::CastForm(Box::new(folder.fold_cast_form(*value)))
+        }
+        CaseFormVariable::LetForm(value) => {
+            CaseFormVariable::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        CaseFormVariable::CaseForm(value) => {
+            CaseFormVariable::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+    }
+}
+
+pub fn fold_case_form_match<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: CaseFormMatch,
+) -> CaseFormMatch {
+    CaseFormMatch {
+        case: folder.fold_case_form_match_case(node.case),
+        action: folder.fold_case_form_match_action(node.action),
+        ..node
+    }
+}
+
+pub fn fold_case_form_match_case<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: CaseFormMatchCase,
+) -> CaseFormMatchCase {
+    match node {
+        CaseFormMatchCase::Empty(value) => {
+            CaseFormMatchCase::Empty(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::Atomic(value) => {
+            CaseFormMatchCase::Atomic(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::ValueKeyword(value) => {
+            CaseFormMatchCase::ValueKeyword(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::TypeKeyword(value) => {
+            CaseFormMatchCase::TypeKeyword(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::TypeSymbol(value) => {
+            CaseFormMatchCase::TypeSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::ValueSymbol(value) => {
+            CaseFormMatchCase::ValueSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::TypePathSymbol(value) => {
+            CaseFormMatchCase::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormMatchCase::ValuePathSymbol(value) => {
+            CaseFormMatchCase::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+    }
+}
+
+pub fn fold_case_form_match_action<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: CaseFormMatchAction,
+) -> CaseFormMatchAction {
+    match node {
+        CaseFormMatchAction::Ignore(value) => {
+            CaseFormMatchAction::Ignore(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::Empty(value) => {
+            CaseFormMatchAction::Empty(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::Panic(value) => {
+            CaseFormMatchAction::Panic(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::Atomic(value) => {
+            CaseFormMatchAction::Atomic(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::ValueKeyword(value) => {
+            CaseFormMatchAction::ValueKeyword(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::ValueSymbol(value) => {
+            CaseFormMatchAction::ValueSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::ValuePathSymbol(value) => {
+            CaseFormMatchAction::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        CaseFormMatchAction::PairForm(value) => {
+            CaseFormMatchAction::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        CaseFormMatchAction::FunForm(value) => {
+            CaseFormMatchAction::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        CaseFormMatchAction::LetForm(value) => {
+            CaseFormMatchAction::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+    }
+}
+
+pub fn fold_pair_form<F: Fold + ?Sized>(folder: &mut F, node: PairForm) -> PairForm {
+    PairForm {
+        first: folder.fold_pair_form_value(node.first),
+        second: folder.fold_pair_form_value(node.second),
+        ..node
+    }
+}
+
+pub fn fold_pair_form_value<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: PairFormValue,
+) -> PairFormValue {
+    match node {
+        PairFormValue::Ignore(value) => PairFormValue::Ignore(folder.fold_simple_value(value)),
+        PairFormValue::Empty(value) => PairFormValue::Empty(folder.fold_simple_value(value)),
+        PairFormValue::Panic(value) => PairFormValue::Panic(folder.fold_simple_value(value)),
+        PairFormValue::Atomic(value) => PairFormValue::Atomic(folder.fold_simple_value(value)),
+        PairFormValue::ValueKeyword(value) => {
+            PairFormValue::ValueKeyword(folder.fold_simple_value(value))
+        }
+        PairFormValue::TypeKeyword(value) => {
+            PairFormValue::TypeKeyword(folder.fold_simple_value(value))
+        }
+        PairFormValue::ValueSymbol(value) => {
+            PairFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        PairFormValue::TypeSymbol(value) => {
+            PairFormValue::TypeSymbol(folder.fold_simple_value(value))
+        }
+        PairFormValue::ValuePathSymbol(value) => {
+            PairFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        PairFormValue::TypePathSymbol(value) => {
+            PairFormValue::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        PairFormValue::Type(value) => PairFormValue::Type(Box::new(folder.fold_type(*value))),
+        PairFormValue::MapForm(value) => {
+            PairFormValue::MapForm(Box::new(folder.fold_map_form(*value)))
+        }
+        PairFormValue::VecForm(value) => {
+            PairFormValue::VecForm(Box::new(folder.fold_vec_form(*value)))
+        }
+        PairFormValue::ArrForm(value) => {
+            PairFormValue::ArrForm(Box::new(folder.fold_arr_form(*value)))
+        }
+        PairFormValue::ListForm(value) => {
+            PairFormValue::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+        PairFormValue::PairForm(value) => {
+            PairFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        PairFormValue::FunForm(value) => {
+            PairFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        PairFormValue::CaseForm(value) => {
+            PairFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        PairFormValue::LetForm(value) => {
+            PairFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        PairFormValue::AppForm(value) => {
+            PairFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+    }
+}
+
+pub fn fold_list_form<F: Fold + ?Sized>(folder: &mut F, node: ListForm) -> ListForm {
+    ListForm {
+        values: node
+            .values
+            .into_iter()
+            .map(|item| folder.fold_list_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_list_form_value<F: Fold + ?Sized>(
+    folder: &mut F,
+    node: ListFormValue,
+) -> ListFormValue {
+    match node {
+        ListFormValue::Ignore(value) => ListFormValue::Ignore(folder.fold_simple_value(value)),
+        ListFormValue::Empty(value) => ListFormValue::Empty(folder.fold_simple_value(value)),
+        ListFormValue::Panic(value) => ListFormValue::Panic(folder.fold_simple_value(value)),
+        ListFormValue::Atomic(value) => ListFormValue::Atomic(folder.fold_simple_value(value)),
+        ListFormValue::ValueKeyword(value) => {
+            ListFormValue::ValueKeyword(folder.fold_simple_value(value))
+        }
+        ListFormValue::TypeKeyword(value) => {
+            ListFormValue::TypeKeyword(folder.fold_simple_value(value))
+        }
+        ListFormValue::ValueSymbol(value) => {
+            ListFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        ListFormValue::TypeSymbol(value) => {
+            ListFormValue::TypeSymbol(folder.fold_simple_value(value))
+        }
+        ListFormValue::ValuePathSymbol(value) => {
+            ListFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        ListFormValue::TypePathSymbol(value) => {
+            ListFormValue::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        ListFormValue::Type(value) => ListFormValue::Type(Box::new(folder.fold_type(*value))),
+        ListFormValue::FunForm(value) => {
+            ListFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        ListFormValue::CaseForm(value) => {
+            ListFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        ListFormValue::LetForm(value) => {
+            ListFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        ListFormValue::AppForm(value) => {
+            ListFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        ListFormValue::PairForm(value) => {
+            ListFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        ListFormValue::ArrForm(value) => {
+            ListFormValue::ArrForm(Box::new(folder.fold_arr_form(*value)))
+        }
+        ListFormValue::VecForm(value) => {
+            ListFormValue::VecForm(Box::new(folder.fold_vec_form(*value)))
+        }
+        ListFormValue::MapForm(value) => {
+            ListFormValue::MapForm(Box::new(folder.fold_map_form(*value)))
+        }
+        ListFormValue::ListForm(value) => {
+            ListFormValue::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+    }
+}
+
+pub fn fold_arr_form<F: Fold + ?Sized>(folder: &mut F, node: ArrForm) -> ArrForm {
+    ArrForm {
+        values: node
+            .values
+            .into_iter()
+            .map(|item| folder.fold_arr_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_arr_form_value<F: Fold + ?Sized>(folder: &mut F, node: ArrFormValue) -> ArrFormValue {
+    match node {
+        ArrFormValue::Ignore(value) => ArrFormValue::Ignore(folder.fold_simple_value(value)),
+        ArrFormValue::Empty(value) => ArrFormValue::Empty(folder.fold_simple_value(value)),
+        ArrFormValue::Panic(value) => ArrFormValue::Panic(folder.fold_simple_value(value)),
+        ArrFormValue::Atomic(value) => ArrFormValue::Atomic(folder.fold_simple_value(value)),
+        ArrFormValue::ValueKeyword(value) => {
+            ArrFormValue::ValueKeyword(folder.fold_simple_value(value))
+        }
+        ArrFormValue::TypeKeyword(value) => {
+            ArrFormValue::TypeKeyword(folder.fold_simple_value(value))
+        }
+        ArrFormValue::ValueSymbol(value) => {
+            ArrFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        ArrFormValue::TypeSymbol(value) => {
+            ArrFormValue::TypeSymbol(folder.fold_simple_value(value))
+        }
+        ArrFormValue::ValuePathSymbol(value) => {
+            ArrFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        ArrFormValue::TypePathSymbol(value) => {
+            ArrFormValue::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        ArrFormValue::Type(value) => ArrFormValue::Type(Box::new(folder.fold_type(*value))),
+        ArrFormValue::FunForm(value) => {
+            ArrFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        ArrFormValue::CaseForm(value) => {
+            ArrFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        ArrFormValue::LetForm(value) => {
+            ArrFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        ArrFormValue::AppForm(value) => {
+            ArrFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        ArrFormValue::PairForm(value) => {
+            ArrFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        ArrFormValue::ListForm(value) => {
+            ArrFormValue::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+        ArrFormValue::VecForm(value) => {
+            ArrFormValue::VecForm(Box::new(folder.fold_vec_form(*value)))
+        }
+        ArrFormValue::MapForm(value) => {
+            ArrFormValue::MapForm(Box::new(folder.fold_map_form(*value)))
+        }
+        ArrFormValue::ArrForm(value) => {
+            ArrFormValue::ArrForm(Box::new(folder.fold_arr_form(*value)))
+        }
+    }
+}
+
+pub fn fold_vec_form<F: Fold + ?Sized>(folder: &mut F, node: VecForm) -> VecForm {
+    VecForm {
+        values: node
+            .values
+            .into_iter()
+            .map(|item| folder.fold_vec_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_vec_form_value<F: Fold + ?Sized>(folder: &mut F, node: VecFormValue) -> VecFormValue {
+    match node {
+        VecFormValue::Ignore(value) => VecFormValue::Ignore(folder.fold_simple_value(value)),
+        VecFormValue::Empty(value) => VecFormValue::Empty(folder.fold_simple_value(value)),
+        VecFormValue::Panic(value) => VecFormValue::Panic(folder.fold_simple_value(value)),
+        VecFormValue::Atomic(value) => VecFormValue::Atomic(folder.fold_simple_value(value)),
+        VecFormValue::ValueKeyword(value) => {
+            VecFormValue::ValueKeyword(folder.fold_simple_value(value))
+        }
+        VecFormValue::TypeKeyword(value) => {
+            VecFormValue::TypeKeyword(folder.fold_simple_value(value))
+        }
+        VecFormValue::ValueSymbol(value) => {
+            VecFormValue::ValueSymbol(folder.fold_simple_value(value))
+        }
+        VecFormValue::TypeSymbol(value) => {
+            VecFormValue::TypeSymbol(folder.fold_simple_value(value))
+        }
+        VecFormValue::ValuePathSymbol(value) => {
+            VecFormValue::ValuePathSymbol(folder.fold_simple_value(value))
+        }
+        VecFormValue::TypePathSymbol(value) => {
+            VecFormValue::TypePathSymbol(folder.fold_simple_value(value))
+        }
+        VecFormValue::Type(value) => VecFormValue::Type(Box::new(folder.fold_type(*value))),
+        VecFormValue::FunForm(value) => {
+            VecFormValue::FunForm(Box::new(folder.fold_fun_form(*value)))
+        }
+        VecFormValue::CaseForm(value) => {
+            VecFormValue::CaseForm(Box::new(folder.fold_case_form(*value)))
+        }
+        VecFormValue::LetForm(value) => {
+            VecFormValue::LetForm(Box::new(folder.fold_let_form(*value)))
+        }
+        VecFormValue::AppForm(value) => {
+            VecFormValue::AppForm(Box::new(folder.fold_app_form(*value)))
+        }
+        VecFormValue::PairForm(value) => {
+            VecFormValue::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+        VecFormValue::ListForm(value) => {
+            VecFormValue::ListForm(Box::new(folder.fold_list_form(*value)))
+        }
+        VecFormValue::ArrForm(value) => {
+            VecFormValue::ArrForm(Box::new(folder.fold_arr_form(*value)))
+        }
+        VecFormValue::MapForm(value) => {
+            VecFormValue::MapForm(Box::new(folder.fold_map_form(*value)))
+        }
+        VecFormValue::VecForm(value) => {
+            VecFormValue::VecForm(Box::new(folder.fold_vec_form(*value)))
+        }
+    }
+}
+
+pub fn fold_map_form<F: Fold + ?Sized>(folder: &mut F, node: MapForm) -> MapForm {
+    MapForm {
+        entries: node
+            .entries
+            .into_iter()
+            .map(|item| folder.fold_map_form_entry(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_map_form_entry<F: Fold + ?Sized>(folder: &mut F, node: MapFormEntry) -> MapFormEntry {
+    match node {
+        MapFormEntry::Ignore(value) => MapFormEntry::Ignore(folder.fold_simple_value(value)),
+        MapFormEntry::Empty(value) => MapFormEntry::Empty(folder.fold_simple_value(value)),
+        MapFormEntry::PairForm(value) => {
+            MapFormEntry::PairForm(Box::new(folder.fold_pair_form(*value)))
+        }
+    }
+}
+
+pub fn fold_mem_form<F: Fold + ?Sized>(folder: &mut F, node: MemForm) -> MemForm {
+    MemForm {
+        values: node
+            .values
+            .into_iter()
+            .map(|item| folder.fold_app_form_value(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_linear_form<F: Fold + ?Sized>(folder: &mut F, node: LinearForm) -> LinearForm {
+    LinearForm {
+        value: folder.fold_app_form_value(node.value),
+        ..node
+    }
+}
+
+pub fn fold_cast_form<F: Fold + ?Sized>(folder: &mut F, node: CastForm) -> CastForm {
+    CastForm {
+        target: folder.fold_type(node.target),
+        value: folder.fold_app_form_value(node.value),
+        ..node
+    }
+}
+
+pub fn fold_type<F: Fold + ?Sized>(folder: &mut F, node: Type) -> Type {
+    match node {
+        Type::Simple(value) => Type::Simple(folder.fold_simple_type(value)),
+        Type::Enum(value) => Type::Enum(Box::new(folder.fold_enum_type(*value))),
+        Type::Pair(value) => Type::Pair(Box::new(folder.fold_pair_type(*value))),
+        Type::List(value) => Type::List(Box::new(folder.fold_list_type(*value))),
+        Type::Arr(value) => Type::Arr(Box::new(folder.fold_arr_type(*value))),
+        Type::Vec(value) => Type::Vec(Box::new(folder.fold_vec_type(*value))),
+        Type::Map(value) => Type::Map(Box::new(folder.fold_map_type(*value))),
+        Type::Fun(value) => Type::Fun(Box::new(folder.fold_fun_type(*value))),
+    }
+}
+
+pub fn fold_simple_type<F: Fold + ?Sized>(folder: &mut F, node: SimpleType) -> SimpleType {
+    match node {
+        SimpleType::Builtin(value) => SimpleType::Builtin(folder.fold_simple_value(value)),
+        SimpleType::Ignore(value) => SimpleType::Ignore(folder.fold_simple_value(value)),
+        SimpleType::Empty(value) => SimpleType::Empty(folder.fold_simple_value(value)),
+        SimpleType::Atomic(value) => SimpleType::Atomic(folder.fold_simple_value(value)),
+        SimpleType::UInt(value) => SimpleType::UInt(folder.fold_simple_value(value)),
+        SimpleType::Int(value) => SimpleType::Int(folder.fold_simple_value(value)),
+        SimpleType::Float(value) => SimpleType::Float(folder.fold_simple_value(value)),
+        SimpleType::Size(value) => SimpleType::Size(folder.fold_simple_value(value)),
+        SimpleType::Pointer(value) => SimpleType::Pointer(folder.fold_simple_value(value)),
+        SimpleType::Ref(value) => SimpleType::Ref(folder.fold_simple_value(value)),
+        SimpleType::Char(value) => SimpleType::Char(folder.fold_simple_value(value)),
+        SimpleType::String(value) => SimpleType::String(folder.fold_simple_value(value)),
+        SimpleType::Mem(value) => SimpleType::Mem(folder.fold_simple_value(value)),
+        SimpleType::Path(value) => SimpleType::Path(folder.fold_simple_value(value)),
+        SimpleType::IO(value) => SimpleType::IO(folder.fold_simple_value(value)),
+        SimpleType::Ctx(value) => SimpleType::Ctx(folder.fold_simple_value(value)),
+        SimpleType::Type(value) => SimpleType::Type(folder.fold_simple_value(value)),
+        SimpleType::Symbol(value) => SimpleType::Symbol(folder.fold_simple_value(value)),
+        SimpleType::PathSymbol(value) => SimpleType::PathSymbol(folder.fold_simple_value(value)),
+    }
+}
+
+pub fn fold_enum_type<F: Fold + ?Sized>(folder: &mut F, node: EnumType) -> EnumType {
+    EnumType {
+        elements: node
+            .elements
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_pair_type<F: Fold + ?Sized>(folder: &mut F, node: PairType) -> PairType {
+    PairType {
+        first: Box::new(folder.fold_type(*node.first)),
+        second: Box::new(folder.fold_type(*node.second)),
+        ..node
+    }
+}
+
+pub fn fold_list_type<F: Fold + ?Sized>(folder: &mut F, node: ListType) -> ListType {
+    ListType {
+        elements: node
+            .elements
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_arr_type<F: Fold + ?Sized>(folder: &mut F, node: ArrType) -> ArrType {
+    ArrType {
+        elements: node
+            .elements
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_vec_type<F: Fold + ?Sized>(folder: &mut F, node: VecType) -> VecType {
+    VecType {
+        elements: node
+            .elements
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_map_type<F: Fold + ?Sized>(folder: &mut F, node: MapType) -> MapType {
+    MapType {
+        entries: node
+            .entries
+            .into_iter()
+            .map(|item| folder.fold_pair_type(item))
+            .collect(),
+        ..node
+    }
+}
+
+pub fn fold_fun_type<F: Fold + ?Sized>(folder: &mut F, node: FunType) -> FunType {
+    FunType {
+        parameters: node
+            .parameters
+            .into_iter()
+            .map(|item| folder.fold_type(item))
+            .collect(),
+        body: Box::new(folder.fold_type(*node.body)),
+        ..node
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn fold_desugar() {
+        use super::{fold_app_form_value, Fold};
+        use crate::program::Program;
+        use crate::value::forms::app_form::AppFormValue;
+        use crate::value::types::SimpleType;
+        use crate::value::SimpleValue;
+
+        struct RenameType;
+
+        impl Fold for RenameType {
+            fn fold_simple_type(&mut self, node: SimpleType) -> SimpleType {
+                match node {
+                    SimpleType::Symbol(value) if value.to_string() == "Result" => {
+                        SimpleType::Symbol(SimpleValue::from_str("Outcome").unwrap())
+                    }
+                    node => node,
+                }
+            }
+        }
+
+        let program = Program::from_file("./examples/result.at").unwrap();
+        let module = RenameType.fold_module_form(program.modules[0].clone());
+
+        assert!(module.to_string().contains("(sig unwrap (Fun Outcome T))"));
+        assert!(module.to_string().contains("(type Result (Enum T E))"));
+
+        struct DropDups;
+
+        impl Fold for DropDups {
+            fn fold_app_form_value(&mut self, node: AppFormValue) -> AppFormValue {
+                match node {
+                    AppFormValue::LinearForm(form) if form.is_dup() => {
+                        self.fold_app_form_value(form.value)
+                    }
+                    node => fold_app_form_value(self, node),
+                }
+            }
+        }
+
+        let s = "(module m (block (val f (fun x (g (dup x) (h (drop x)))))))";
+
+        let module = crate::value::forms::module_form::ModuleForm::from_str(s).unwrap();
+        let module = DropDups.fold_module_form(module);
+
+        assert_eq!(
+            module.to_string(),
+            "(module m (block (val f (fun x (g x (h (drop x)))))))".to_string()
+        );
+    }
+}
@@ -1,19 +1,23 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::AppForm;
 use crate::value::forms::ArrForm;
 use crate::value::forms::AttrsForm;
 use crate::value::forms::BlockForm;
 use crate::value::forms::CaseForm;
+use crate::value::forms::CastForm;
 use crate::value::forms::ExportForm;
 use crate::value::forms::Form;
 use crate::value::forms::FunForm;
 use crate::value::forms::ImportForm;
 use crate::value::forms::LetForm;
+use crate::value::forms::LinearForm;
 use crate::value::forms::ListForm;
 use crate::value::forms::MapForm;
+use crate::value::forms::MemForm;
 use crate::value::forms::ModuleForm;
 use crate::value::forms::PairForm;
 use crate::value::forms::SigForm;
@@ -37,6 +41,9 @@ pub enum FormValue {
     LetForm(Box<LetForm>),
     CaseForm(Box<CaseForm>),
     AppForm(Box<AppForm>),
+    MemForm(Box<MemForm>),
+    LinearForm(Box<LinearForm>),
+    CastForm(Box<CastForm>),
     MapForm(Box<MapForm>),
     VecForm(Box<VecForm>),
     ArrForm(Box<ArrForm>),
@@ -70,6 +77,9 @@ impl FormValue {
             FormValue::LetForm(form) => form.file(),
             FormValue::CaseForm(form) => form.file(),
             FormValue::AppForm(form) => form.file(),
+            FormValue::MemForm(form) => form.file(),
+            FormValue::LinearForm(form) => form.file(),
+            FormValue::CastForm(form) => form.file(),
             FormValue::MapForm(form) => form.file(),
             FormValue::VecForm(form) => form.file(),
             FormValue::ArrForm(form) => form.file(),
@@ -93,6 +103,9 @@ impl FormValue {
             FormValue::LetForm(form) => form.loc(),
             FormValue::CaseForm(form) => form.loc(),
             FormValue::AppForm(form) => form.loc(),
+            FormValue::MemForm(form) => form.loc(),
+            FormValue::LinearForm(form) => form.loc(),
+            FormValue::CastForm(form) => form.loc(),
             FormValue::MapForm(form) => form.loc(),
             FormValue::VecForm(form) => form.loc(),
             FormValue::ArrForm(form) => form.loc(),
@@ -102,6 +115,32 @@ impl FormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FormValue::ModuleForm(form) => form.span(),
+            FormValue::BlockForm(form) => form.span(),
+            FormValue::ImportForm(form) => form.span(),
+            FormValue::ExportForm(form) => form.span(),
+            FormValue::AttrsForm(form) => form.span(),
+            FormValue::TypeForm(form) => form.span(),
+            FormValue::SigForm(form) => form.span(),
+            FormValue::ValForm(form) => form.span(),
+            FormValue::FunForm(form) => form.span(),
+            FormValue::LetForm(form) => form.span(),
+            FormValue::CaseForm(form) => form.span(),
+            FormValue::AppForm(form) => form.span(),
+            FormValue::MemForm(form) => form.span(),
+            FormValue::LinearForm(form) => form.span(),
+            FormValue::CastForm(form) => form.span(),
+            FormValue::MapForm(form) => form.span(),
+            FormValue::VecForm(form) => form.span(),
+            FormValue::ArrForm(form) => form.span(),
+            FormValue::ListForm(form) => form.span(),
+            FormValue::PairForm(form) => form.span(),
+            FormValue::Type(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -117,6 +156,9 @@ impl FormValue {
             FormValue::LetForm(form) => form.to_string(),
             FormValue::CaseForm(form) => form.to_string(),
             FormValue::AppForm(form) => form.to_string(),
+            FormValue::MemForm(form) => form.to_string(),
+            FormValue::LinearForm(form) => form.to_string(),
+            FormValue::CastForm(form) => form.to_string(),
             FormValue::MapForm(form) => form.to_string(),
             FormValue::VecForm(form) => form.to_string(),
             FormValue::ArrForm(form) => form.to_string(),
@@ -162,6 +204,12 @@ impl FormValue {
             FormValue::LetForm(Box::new(form))
         } else if let Ok(form) = CaseForm::from_form(form) {
             FormValue::CaseForm(Box::new(form))
+        } else if let Ok(form) = MemForm::from_form(form) {
+            FormValue::MemForm(Box::new(form))
+        } else if let Ok(form) = LinearForm::from_form(form) {
+            FormValue::LinearForm(Box::new(form))
+        } else if let Ok(form) = CastForm::from_form(form) {
+            FormValue::CastForm(Box::new(form))
         } else if let Ok(form) = AppForm::from_form(form) {
             FormValue::AppForm(Box::new(form))
         } else if let Ok(form) = MapForm::from_form(form) {
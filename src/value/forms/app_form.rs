@@ -1,12 +1,18 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::case_form::CaseForm;
+use crate::value::forms::cast_form::CastForm;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::fun_form::FunForm;
 use crate::value::forms::let_form::LetForm;
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::mem_form::{MemForm, MemFormKind};
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -24,6 +30,9 @@ pub enum AppFormValue {
     LetForm(Box<LetForm>),
     CaseForm(Box<CaseForm>),
     AppForm(Box<AppForm>),
+    MemForm(Box<MemForm>),
+    LinearForm(Box<LinearForm>),
+    CastForm(Box<CastForm>),
 }
 
 impl Default for AppFormValue {
@@ -46,6 +55,9 @@ impl AppFormValue {
             AppFormValue::LetForm(form) => form.file(),
             AppFormValue::CaseForm(form) => form.file(),
             AppFormValue::AppForm(form) => form.file(),
+            AppFormValue::MemForm(form) => form.file(),
+            AppFormValue::LinearForm(form) => form.file(),
+            AppFormValue::CastForm(form) => form.file(),
         }
     }
 
@@ -62,119 +74,57 @@ impl AppFormValue {
             AppFormValue::LetForm(form) => form.loc(),
             AppFormValue::CaseForm(form) => form.loc(),
             AppFormValue::AppForm(form) => form.loc(),
+            AppFormValue::MemForm(form) => form.loc(),
+            AppFormValue::LinearForm(form) => form.loc(),
+            AppFormValue::CastForm(form) => form.loc(),
         }
     }
 
-    pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        match self.clone() {
-            AppFormValue::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            AppFormValue::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            AppFormValue::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            AppFormValue::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            AppFormValue::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            AppFormValue::Ignore(ignore) => ignore.span(),
+            AppFormValue::Empty(empty) => empty.span(),
+            AppFormValue::Panic(panic) => panic.span(),
+            AppFormValue::Atomic(atomic) => atomic.span(),
+            AppFormValue::ValueSymbol(symbol) => symbol.span(),
+            AppFormValue::ValuePathSymbol(symbol) => symbol.span(),
+            AppFormValue::PairForm(form) => form.span(),
+            AppFormValue::FunForm(form) => form.span(),
+            AppFormValue::LetForm(form) => form.span(),
+            AppFormValue::CaseForm(form) => form.span(),
+            AppFormValue::AppForm(form) => form.span(),
+            AppFormValue::MemForm(form) => form.span(),
+            AppFormValue::LinearForm(form) => form.span(),
+            AppFormValue::CastForm(form) => form.span(),
         }
+    }
 
-        params
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_app_form_value(self);
+
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self.clone() {
-            AppFormValue::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            AppFormValue::ValuePathSymbol(value) => {
-                value_vars.push(value);
-            }
-            AppFormValue::PairForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            AppFormValue::FunForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            AppFormValue::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            AppFormValue::CaseForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            AppFormValue::AppForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_app_form_value(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        match self.clone() {
-            AppFormValue::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            AppFormValue::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            AppFormValue::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            AppFormValue::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            AppFormValue::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_app_form_value(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self.clone() {
-            AppFormValue::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            AppFormValue::ValuePathSymbol(value) => {
-                vars.push(value);
-            }
-            AppFormValue::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            AppFormValue::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            AppFormValue::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            AppFormValue::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            AppFormValue::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_app_form_value(self);
 
-        vars
+        collector.vars
     }
 
     #[allow(clippy::inherent_to_string_shadow_display)]
@@ -191,6 +141,9 @@ impl AppFormValue {
             AppFormValue::LetForm(form) => form.to_string(),
             AppFormValue::CaseForm(form) => form.to_string(),
             AppFormValue::AppForm(form) => form.to_string(),
+            AppFormValue::MemForm(form) => form.to_string(),
+            AppFormValue::LinearForm(form) => form.to_string(),
+            AppFormValue::CastForm(form) => form.to_string(),
         }
     }
 }
@@ -221,6 +174,10 @@ impl AppForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn variables_to_string(&self) -> String {
         self.variables
             .iter()
@@ -230,45 +187,31 @@ impl AppForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        for variable in self.variables.iter() {
-            params.extend(variable.all_parameters());
-        }
+        let mut collector = Parameters::default();
+        collector.visit_app_form(self);
 
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-        value_vars.push(self.name.clone());
-
-        for variable in self.variables.iter() {
-            value_vars.extend(variable.all_value_variables());
-        }
+        let mut collector = Variables::default();
+        collector.visit_app_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_app_form(self);
 
-        for variable in self.variables.iter() {
-            type_vars.extend(variable.all_type_variables());
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-        vars.push(self.name.clone());
-
-        for variable in self.variables.iter() {
-            vars.extend(variable.all_variables());
-        }
+        let mut collector = Variables::default();
+        collector.visit_app_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn parse_variables(&mut self, form: &Form) -> Result<()> {
@@ -308,7 +251,17 @@ impl AppForm {
                     }
                 },
                 FormTailElement::Form(form) => {
-                    if let Ok(form) = PairForm::from_form(&form) {
+                    if MemForm::is_mem_form(&form) {
+                        let form = MemForm::from_form(&form)?;
+                        self.variables.push(AppFormValue::MemForm(Box::new(form)));
+                    } else if LinearForm::is_linear_form(&form) {
+                        let form = LinearForm::from_form(&form)?;
+                        self.variables
+                            .push(AppFormValue::LinearForm(Box::new(form)));
+                    } else if CastForm::is_cast_form(&form) {
+                        let form = CastForm::from_form(&form)?;
+                        self.variables.push(AppFormValue::CastForm(Box::new(form)));
+                    } else if let Ok(form) = PairForm::from_form(&form) {
                         self.variables.push(AppFormValue::PairForm(Box::new(form)));
                     } else if let Ok(form) = FunForm::from_form(&form) {
                         self.variables.push(AppFormValue::FunForm(Box::new(form)));
@@ -349,6 +302,19 @@ impl AppForm {
                 app.name = name;
             }
             SimpleValue::ValueKeyword(_) => {
+                let keyword = name.to_string();
+
+                if MemFormKind::from_keyword(&keyword).is_some()
+                    || keyword == "dup"
+                    || keyword == "drop"
+                    || keyword == "cast"
+                {
+                    return Err(Error::Syntactic(SyntacticError {
+                        loc: name.loc(),
+                        desc: format!("unexpected keyword {}", keyword),
+                    }));
+                }
+
                 app.name = name;
             }
             SimpleValue::ValueSymbol(_) => {
@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::case_form::CaseForm;
@@ -10,8 +11,10 @@ use crate::value::forms::let_form::LetForm;
 use crate::value::forms::list_form::ListForm;
 use crate::value::forms::map_form::MapForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
 use crate::value::forms::vec_form::VecForm;
 use crate::value::types::Type;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -96,6 +99,31 @@ impl ArrFormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ArrFormValue::Ignore(ignore) => ignore.span(),
+            ArrFormValue::Empty(empty) => empty.span(),
+            ArrFormValue::Panic(panic) => panic.span(),
+            ArrFormValue::Atomic(atomic) => atomic.span(),
+            ArrFormValue::ValueKeyword(keyword) => keyword.span(),
+            ArrFormValue::TypeKeyword(keyword) => keyword.span(),
+            ArrFormValue::ValueSymbol(symbol) => symbol.span(),
+            ArrFormValue::TypeSymbol(symbol) => symbol.span(),
+            ArrFormValue::ValuePathSymbol(symbol) => symbol.span(),
+            ArrFormValue::TypePathSymbol(symbol) => symbol.span(),
+            ArrFormValue::Type(form) => form.span(),
+            ArrFormValue::FunForm(form) => form.span(),
+            ArrFormValue::CaseForm(form) => form.span(),
+            ArrFormValue::LetForm(form) => form.span(),
+            ArrFormValue::AppForm(form) => form.span(),
+            ArrFormValue::PairForm(form) => form.span(),
+            ArrFormValue::ListForm(form) => form.span(),
+            ArrFormValue::VecForm(form) => form.span(),
+            ArrFormValue::MapForm(form) => form.span(),
+            ArrFormValue::ArrForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -148,6 +176,10 @@ impl ArrForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn len(&self) -> usize {
         self.values.len()
     }
@@ -171,31 +203,11 @@ impl ArrForm {
                 | ArrFormValue::CaseForm(_)
                 | ArrFormValue::LetForm(_)
                 | ArrFormValue::AppForm(_) => return false,
-                ArrFormValue::PairForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ArrFormValue::MapForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ArrFormValue::VecForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ArrFormValue::ListForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ArrFormValue::ArrForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
+                ArrFormValue::PairForm(form) if !form.can_be_parameter() => return false,
+                ArrFormValue::MapForm(form) if !form.can_be_parameter() => return false,
+                ArrFormValue::VecForm(form) if !form.can_be_parameter() => return false,
+                ArrFormValue::ListForm(form) if !form.can_be_parameter() => return false,
+                ArrFormValue::ArrForm(form) if !form.can_be_parameter() => return false,
                 _ => {}
             }
         }
@@ -212,189 +224,31 @@ impl ArrForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_arr_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                ArrFormValue::FunForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::CaseForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::LetForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::AppForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::PairForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::ListForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::VecForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::MapForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ArrFormValue::ArrForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                _ => {}
-            }
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                ArrFormValue::ValueSymbol(value) => {
-                    value_vars.push(value);
-                }
-                ArrFormValue::ValuePathSymbol(value) => {
-                    value_vars.push(value);
-                }
-                ArrFormValue::FunForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::CaseForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::LetForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::AppForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::PairForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::ListForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::VecForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::MapForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ArrFormValue::ArrForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_arr_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_arr_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                ArrFormValue::TypeSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                ArrFormValue::TypePathSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                ArrFormValue::Type(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::FunForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::CaseForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::LetForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::AppForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::PairForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::ListForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::VecForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::MapForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ArrFormValue::ArrForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                _ => {}
-            }
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                ArrFormValue::ValueSymbol(value) => {
-                    vars.push(value);
-                }
-                ArrFormValue::TypeSymbol(value) => {
-                    vars.push(value);
-                }
-                ArrFormValue::ValuePathSymbol(value) => {
-                    vars.push(value);
-                }
-                ArrFormValue::TypePathSymbol(value) => {
-                    vars.push(value);
-                }
-                ArrFormValue::Type(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::FunForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::CaseForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::LetForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::AppForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::PairForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::ListForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::VecForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::MapForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ArrFormValue::ArrForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_arr_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<ArrForm> {
@@ -1,9 +1,12 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::map_form::MapForm;
+use crate::value::forms::variables::Variables;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -53,6 +56,19 @@ impl AttrsFormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            AttrsFormValue::Empty(empty) => empty.span(),
+            AttrsFormValue::Panic(value) => value.span(),
+            AttrsFormValue::Atomic(value) => value.span(),
+            AttrsFormValue::ValueSymbol(value) => value.span(),
+            AttrsFormValue::TypeSymbol(value) => value.span(),
+            AttrsFormValue::ValuePathSymbol(value) => value.span(),
+            AttrsFormValue::TypePathSymbol(value) => value.span(),
+            AttrsFormValue::Map(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -68,56 +84,24 @@ impl AttrsFormValue {
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_attrs_form_value(self);
 
-        match self {
-            AttrsFormValue::Empty(_)
-            | AttrsFormValue::Panic(_)
-            | AttrsFormValue::Atomic(_)
-            | AttrsFormValue::TypeSymbol(_)
-            | AttrsFormValue::TypePathSymbol(_) => {}
-            AttrsFormValue::ValueSymbol(value) => value_vars.push(value.clone()),
-            AttrsFormValue::ValuePathSymbol(value) => value_vars.push(value.clone()),
-            AttrsFormValue::Map(form) => value_vars.extend(form.all_value_variables()),
-        }
-
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        match self {
-            AttrsFormValue::Empty(_)
-            | AttrsFormValue::Panic(_)
-            | AttrsFormValue::Atomic(_)
-            | AttrsFormValue::ValueSymbol(_)
-            | AttrsFormValue::ValuePathSymbol(_) => {}
-            AttrsFormValue::TypeSymbol(value) => {
-                type_vars.push(Type::from_simple_value(value).unwrap())
-            }
-            AttrsFormValue::TypePathSymbol(value) => {
-                type_vars.push(Type::from_simple_value(value).unwrap())
-            }
-            AttrsFormValue::Map(form) => type_vars.extend(form.all_type_variables()),
-        }
+        let mut collector = Variables::default();
+        collector.visit_attrs_form_value(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self {
-            AttrsFormValue::Empty(_) | AttrsFormValue::Panic(_) | AttrsFormValue::Atomic(_) => {}
-            AttrsFormValue::ValueSymbol(value) => vars.push(value.clone()),
-            AttrsFormValue::TypeSymbol(value) => vars.push(value.clone()),
-            AttrsFormValue::ValuePathSymbol(value) => vars.push(value.clone()),
-            AttrsFormValue::TypePathSymbol(value) => vars.push(value.clone()),
-            AttrsFormValue::Map(form) => vars.extend(form.all_variables()),
-        }
+        let mut collector = Variables::default();
+        collector.visit_attrs_form_value(self);
 
-        vars
+        collector.vars
     }
 }
 
@@ -132,6 +116,7 @@ pub struct AttrsForm {
     pub tokens: Box<Tokens>,
     pub name: SimpleValue,
     pub values: Vec<AttrsFormValue>,
+    pub doc: Option<String>,
 }
 
 impl AttrsForm {
@@ -147,6 +132,10 @@ impl AttrsForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn is_type_attributes(&self) -> bool {
         matches!(self.name, SimpleValue::TypeSymbol(_))
     }
@@ -164,28 +153,24 @@ impl AttrsForm {
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_attrs_form(self);
 
-        for value in self.values.iter() {
-            value_vars.extend(value.all_value_variables());
-        }
-
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_attrs_form(self);
 
-        for value in self.values.iter() {
-            type_vars.extend(value.all_type_variables());
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        // TODO
-        vec![]
+        let mut collector = Variables::default();
+        collector.visit_attrs_form(self);
+
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<AttrsForm> {
@@ -1,15 +1,20 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
+use crate::token::{doc_text, leading_docs, split_detached, token_index};
 use crate::value::forms::app_form::AppFormValue;
 use crate::value::forms::attrs_form::AttrsForm;
+use crate::value::forms::error_form::ErrorForm;
 use crate::value::forms::export_form::ExportForm;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::import_form::ImportForm;
 use crate::value::forms::sig_form::SigForm;
 use crate::value::forms::type_form::TypeForm;
 use crate::value::forms::val_form::ValForm;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -23,6 +28,7 @@ pub enum BlockFormEntry {
     TypeForm(Box<TypeForm>),
     SigForm(Box<SigForm>),
     ValForm(Box<ValForm>),
+    ErrorForm(Box<ErrorForm>),
 }
 
 impl Default for BlockFormEntry {
@@ -41,6 +47,7 @@ impl BlockFormEntry {
             BlockFormEntry::TypeForm(form) => form.file(),
             BlockFormEntry::SigForm(form) => form.file(),
             BlockFormEntry::ValForm(form) => form.file(),
+            BlockFormEntry::ErrorForm(form) => form.file(),
         }
     }
 
@@ -53,6 +60,73 @@ impl BlockFormEntry {
             BlockFormEntry::TypeForm(form) => form.loc(),
             BlockFormEntry::SigForm(form) => form.loc(),
             BlockFormEntry::ValForm(form) => form.loc(),
+            BlockFormEntry::ErrorForm(form) => form.loc(),
+        }
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            BlockFormEntry::Empty(empty) => empty.span(),
+            BlockFormEntry::ImportForm(form) => form.span(),
+            BlockFormEntry::ExportForm(form) => form.span(),
+            BlockFormEntry::AttrsForm(form) => form.span(),
+            BlockFormEntry::TypeForm(form) => form.span(),
+            BlockFormEntry::SigForm(form) => form.span(),
+            BlockFormEntry::ValForm(form) => form.span(),
+            BlockFormEntry::ErrorForm(form) => form.span(),
+        }
+    }
+
+    pub fn is_documentable(&self) -> bool {
+        matches!(
+            self,
+            BlockFormEntry::AttrsForm(_)
+                | BlockFormEntry::TypeForm(_)
+                | BlockFormEntry::SigForm(_)
+                | BlockFormEntry::ValForm(_)
+        )
+    }
+
+    pub fn doc(&self) -> Option<String> {
+        match self {
+            BlockFormEntry::AttrsForm(form) => form.doc.clone(),
+            BlockFormEntry::TypeForm(form) => form.doc.clone(),
+            BlockFormEntry::SigForm(form) => form.doc.clone(),
+            BlockFormEntry::ValForm(form) => form.doc.clone(),
+            _ => None,
+        }
+    }
+
+    pub fn set_doc(&mut self, doc: Option<String>) {
+        match self {
+            BlockFormEntry::AttrsForm(form) => form.doc = doc,
+            BlockFormEntry::TypeForm(form) => form.doc = doc,
+            BlockFormEntry::SigForm(form) => form.doc = doc,
+            BlockFormEntry::ValForm(form) => form.doc = doc,
+            _ => {}
+        }
+    }
+
+    pub fn from_form(form: &Form) -> Result<BlockFormEntry> {
+        match form.head.to_string().as_str() {
+            "import" => Ok(BlockFormEntry::ImportForm(Box::new(ImportForm::from_form(
+                form,
+            )?))),
+            "export" => Ok(BlockFormEntry::ExportForm(Box::new(ExportForm::from_form(
+                form,
+            )?))),
+            "attrs" => Ok(BlockFormEntry::AttrsForm(Box::new(AttrsForm::from_form(
+                form,
+            )?))),
+            "type" => Ok(BlockFormEntry::TypeForm(Box::new(TypeForm::from_form(
+                form,
+            )?))),
+            "sig" => Ok(BlockFormEntry::SigForm(Box::new(SigForm::from_form(form)?))),
+            "val" => Ok(BlockFormEntry::ValForm(Box::new(ValForm::from_form(form)?))),
+            _ => Err(Error::Syntactic(SyntacticError {
+                loc: form.loc(),
+                desc: "unexpected form".into(),
+            })),
         }
     }
 
@@ -66,6 +140,7 @@ impl BlockFormEntry {
             BlockFormEntry::TypeForm(form) => form.to_string(),
             BlockFormEntry::SigForm(form) => form.to_string(),
             BlockFormEntry::ValForm(form) => form.to_string(),
+            BlockFormEntry::ErrorForm(form) => form.to_string(),
         }
     }
 }
@@ -82,6 +157,7 @@ pub type BlockFormValue = AppFormValue;
 pub struct BlockForm {
     pub tokens: Box<Tokens>,
     pub entries: Vec<BlockFormEntry>,
+    pub doc: Option<String>,
 }
 
 impl BlockForm {
@@ -97,6 +173,10 @@ impl BlockForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn entry_as_import(&self, idx: usize) -> Option<Box<ImportForm>> {
         if idx > self.entries.len() - 1 {
             return None;
@@ -178,103 +258,62 @@ impl BlockForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                BlockFormEntry::ImportForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                BlockFormEntry::ValForm(form) => {
-                    params.push(form.name.clone());
-                    params.extend(form.all_parameters());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Parameters::default();
+        collector.visit_block_form(self);
 
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_block_form(self);
 
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                BlockFormEntry::ExportForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                BlockFormEntry::AttrsForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                BlockFormEntry::ValForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                _ => {}
-            }
-        }
-
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                BlockFormEntry::ImportForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                BlockFormEntry::ExportForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                BlockFormEntry::AttrsForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                BlockFormEntry::TypeForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                BlockFormEntry::SigForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                BlockFormEntry::ValForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_block_form(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_block_form(self);
 
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                BlockFormEntry::ImportForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                BlockFormEntry::ExportForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                BlockFormEntry::AttrsForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                BlockFormEntry::TypeForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                BlockFormEntry::SigForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                BlockFormEntry::ValForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                _ => {}
+        collector.vars
+    }
+
+    fn attach_docs(&mut self) {
+        let tokens = &self.tokens;
+
+        for (idx, entry) in self.entries.iter_mut().enumerate() {
+            let token_idx = match entry.loc().and_then(|loc| token_index(tokens, &loc)) {
+                Some(token_idx) => token_idx,
+                None => continue,
+            };
+
+            let docs = leading_docs(tokens, token_idx);
+
+            if docs.is_empty() {
+                continue;
             }
-        }
 
-        vars
+            if idx > 0 {
+                entry.set_doc(doc_text(&docs));
+                continue;
+            }
+
+            let (detached, attached) = split_detached(&docs, &tokens[token_idx]);
+
+            if entry.is_documentable() {
+                self.doc = doc_text(&detached);
+                entry.set_doc(doc_text(&attached));
+            } else {
+                self.doc = doc_text(&docs);
+            }
+        }
     }
 
     pub fn from_form(form: &Form) -> Result<BlockForm> {
@@ -338,6 +377,8 @@ impl BlockForm {
             }
         }
 
+        block_form.attach_docs();
+
         Ok(block_form)
     }
 
@@ -347,6 +388,61 @@ impl BlockForm {
         BlockForm::from_form(&form)
     }
 
+    pub fn from_tokens_recovering(tokens: &Tokens) -> (BlockForm, Vec<Error>) {
+        let mut block_form = BlockForm::new();
+        block_form.tokens = Box::new(tokens.to_owned());
+
+        let mut errors = vec![];
+        let elements = Form::split_elements(tokens);
+
+        if elements.is_empty() || elements[0].to_string() != "block" {
+            errors.push(Error::Syntactic(SyntacticError {
+                loc: tokens.span().map(|span| span.start),
+                desc: "expected a block keyword".into(),
+            }));
+
+            return (block_form, errors);
+        }
+
+        if elements.len() == 1 {
+            errors.push(Error::Syntactic(SyntacticError {
+                loc: block_form.loc(),
+                desc: "expected at least a value".into(),
+            }));
+        }
+
+        for entry_tokens in elements[1..].iter() {
+            let res =
+                Form::from_tokens(entry_tokens).and_then(|form| BlockFormEntry::from_form(&form));
+
+            match res {
+                Ok(entry) => block_form.entries.push(entry),
+                Err(err) => {
+                    let error = match &err {
+                        Error::Syntactic(error) => error.clone(),
+                        _ => SyntacticError {
+                            loc: entry_tokens[0].loc(),
+                            desc: err.to_string(),
+                        },
+                    };
+
+                    block_form
+                        .entries
+                        .push(BlockFormEntry::ErrorForm(Box::new(ErrorForm::new(
+                            entry_tokens,
+                            error,
+                        ))));
+
+                    errors.push(err);
+                }
+            }
+        }
+
+        block_form.attach_docs();
+
+        (block_form, errors)
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<BlockForm> {
         let tokens = Tokens::from_str(s)?;
@@ -1,12 +1,18 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
+use crate::value::forms::cast_form::CastForm;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::fun_form::FunForm;
 use crate::value::forms::let_form::LetForm;
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::mem_form::MemForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -17,6 +23,9 @@ pub enum CaseFormVariable {
     Atomic(SimpleValue),
     ValueSymbol(SimpleValue),
     AppForm(Box<AppForm>),
+    MemForm(Box<MemForm>),
+    LinearForm(Box<LinearForm>),
+    CastForm(Box<CastForm>),
     LetForm(Box<LetForm>),
     CaseForm(Box<CaseForm>),
 }
@@ -34,6 +43,9 @@ impl CaseFormVariable {
             CaseFormVariable::Atomic(atomic) => atomic.file(),
             CaseFormVariable::ValueSymbol(symbol) => symbol.file(),
             CaseFormVariable::AppForm(form) => form.file(),
+            CaseFormVariable::MemForm(form) => form.file(),
+            CaseFormVariable::LinearForm(form) => form.file(),
+            CaseFormVariable::CastForm(form) => form.file(),
             CaseFormVariable::LetForm(form) => form.file(),
             CaseFormVariable::CaseForm(form) => form.file(),
         }
@@ -45,91 +57,54 @@ impl CaseFormVariable {
             CaseFormVariable::Atomic(atomic) => atomic.loc(),
             CaseFormVariable::ValueSymbol(symbol) => symbol.loc(),
             CaseFormVariable::AppForm(form) => form.loc(),
+            CaseFormVariable::MemForm(form) => form.loc(),
+            CaseFormVariable::LinearForm(form) => form.loc(),
+            CaseFormVariable::CastForm(form) => form.loc(),
             CaseFormVariable::LetForm(form) => form.loc(),
             CaseFormVariable::CaseForm(form) => form.loc(),
         }
     }
 
-    pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        match self.clone() {
-            CaseFormVariable::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            CaseFormVariable::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            CaseFormVariable::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CaseFormVariable::Empty(empty) => empty.span(),
+            CaseFormVariable::Atomic(atomic) => atomic.span(),
+            CaseFormVariable::ValueSymbol(symbol) => symbol.span(),
+            CaseFormVariable::AppForm(form) => form.span(),
+            CaseFormVariable::MemForm(form) => form.span(),
+            CaseFormVariable::LinearForm(form) => form.span(),
+            CaseFormVariable::CastForm(form) => form.span(),
+            CaseFormVariable::LetForm(form) => form.span(),
+            CaseFormVariable::CaseForm(form) => form.span(),
         }
+    }
+
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_case_form_variable(self);
 
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self.clone() {
-            CaseFormVariable::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            CaseFormVariable::AppForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            CaseFormVariable::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            CaseFormVariable::CaseForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_variable(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        match self.clone() {
-            CaseFormVariable::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            CaseFormVariable::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            CaseFormVariable::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_variable(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self.clone() {
-            CaseFormVariable::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            CaseFormVariable::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            CaseFormVariable::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            CaseFormVariable::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_variable(self);
 
-        vars
+        collector.vars
     }
 
     #[allow(clippy::inherent_to_string_shadow_display)]
@@ -139,6 +114,9 @@ impl CaseFormVariable {
             CaseFormVariable::Atomic(atomic) => atomic.to_string(),
             CaseFormVariable::ValueSymbol(symbol) => symbol.to_string(),
             CaseFormVariable::AppForm(form) => form.to_string(),
+            CaseFormVariable::MemForm(form) => form.to_string(),
+            CaseFormVariable::LinearForm(form) => form.to_string(),
+            CaseFormVariable::CastForm(form) => form.to_string(),
             CaseFormVariable::LetForm(form) => form.to_string(),
             CaseFormVariable::CaseForm(form) => form.to_string(),
         }
@@ -155,6 +133,7 @@ impl fmt::Display for CaseFormVariable {
 pub enum CaseFormMatchCase {
     Empty(SimpleValue),
     Atomic(SimpleValue),
+    ValueKeyword(SimpleValue),
     TypeKeyword(SimpleValue),
     TypeSymbol(SimpleValue),
     ValueSymbol(SimpleValue),
@@ -167,6 +146,7 @@ impl CaseFormMatchCase {
         match self {
             CaseFormMatchCase::Empty(empty) => empty.file(),
             CaseFormMatchCase::Atomic(atomic) => atomic.file(),
+            CaseFormMatchCase::ValueKeyword(keyword) => keyword.file(),
             CaseFormMatchCase::TypeKeyword(keyword) => keyword.file(),
             CaseFormMatchCase::TypeSymbol(symbol) => symbol.file(),
             CaseFormMatchCase::ValueSymbol(symbol) => symbol.file(),
@@ -179,6 +159,7 @@ impl CaseFormMatchCase {
         match self {
             CaseFormMatchCase::Empty(empty) => empty.loc(),
             CaseFormMatchCase::Atomic(atomic) => atomic.loc(),
+            CaseFormMatchCase::ValueKeyword(keyword) => keyword.loc(),
             CaseFormMatchCase::TypeKeyword(keyword) => keyword.loc(),
             CaseFormMatchCase::TypeSymbol(symbol) => symbol.loc(),
             CaseFormMatchCase::ValueSymbol(symbol) => symbol.loc(),
@@ -187,11 +168,29 @@ impl CaseFormMatchCase {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CaseFormMatchCase::Empty(empty) => empty.span(),
+            CaseFormMatchCase::Atomic(atomic) => atomic.span(),
+            CaseFormMatchCase::ValueKeyword(keyword) => keyword.span(),
+            CaseFormMatchCase::TypeKeyword(keyword) => keyword.span(),
+            CaseFormMatchCase::TypeSymbol(symbol) => symbol.span(),
+            CaseFormMatchCase::ValueSymbol(symbol) => symbol.span(),
+            CaseFormMatchCase::TypePathSymbol(symbol) => symbol.span(),
+            CaseFormMatchCase::ValuePathSymbol(symbol) => symbol.span(),
+        }
+    }
+
+    pub fn is_catch_all(&self) -> bool {
+        matches!(self, CaseFormMatchCase::ValueKeyword(_))
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
             CaseFormMatchCase::Empty(_) => "()".into(),
             CaseFormMatchCase::Atomic(atomic) => atomic.to_string(),
+            CaseFormMatchCase::ValueKeyword(keyword) => keyword.to_string(),
             CaseFormMatchCase::TypeKeyword(keyword) => keyword.to_string(),
             CaseFormMatchCase::TypeSymbol(symbol) => symbol.to_string(),
             CaseFormMatchCase::ValueSymbol(symbol) => symbol.to_string(),
@@ -258,6 +257,21 @@ impl CaseFormMatchAction {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CaseFormMatchAction::Ignore(ignore) => ignore.span(),
+            CaseFormMatchAction::Empty(empty) => empty.span(),
+            CaseFormMatchAction::Panic(panic) => panic.span(),
+            CaseFormMatchAction::Atomic(atomic) => atomic.span(),
+            CaseFormMatchAction::ValueKeyword(keyword) => keyword.span(),
+            CaseFormMatchAction::ValueSymbol(symbol) => symbol.span(),
+            CaseFormMatchAction::ValuePathSymbol(symbol) => symbol.span(),
+            CaseFormMatchAction::PairForm(form) => form.span(),
+            CaseFormMatchAction::FunForm(form) => form.span(),
+            CaseFormMatchAction::LetForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -307,92 +321,36 @@ impl CaseFormMatch {
         self.tokens[0].loc()
     }
 
-    pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        match self.action.clone() {
-            CaseFormMatchAction::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            CaseFormMatchAction::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            CaseFormMatchAction::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
-        }
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_case_form_match(self);
 
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self.action.clone() {
-            CaseFormMatchAction::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            CaseFormMatchAction::ValuePathSymbol(value) => {
-                value_vars.push(value);
-            }
-            CaseFormMatchAction::PairForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            CaseFormMatchAction::FunForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            CaseFormMatchAction::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_match(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        match self.action.clone() {
-            CaseFormMatchAction::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            CaseFormMatchAction::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            CaseFormMatchAction::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_match(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self.action.clone() {
-            CaseFormMatchAction::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            CaseFormMatchAction::ValuePathSymbol(value) => {
-                vars.push(value);
-            }
-            CaseFormMatchAction::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            CaseFormMatchAction::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            CaseFormMatchAction::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form_match(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<CaseFormMatch> {
@@ -421,6 +379,11 @@ impl CaseFormMatch {
                 SimpleValue::Atomic(_) => {
                     case_match.case = CaseFormMatchCase::Atomic(value);
                 }
+                SimpleValue::ValueKeyword(_)
+                    if value.to_string() == "others" || value.to_string() == "default" =>
+                {
+                    case_match.case = CaseFormMatchCase::ValueKeyword(value);
+                }
                 SimpleValue::TypeKeyword(_) => {
                     case_match.case = CaseFormMatchCase::TypeKeyword(value);
                 }
@@ -549,6 +512,10 @@ impl CaseForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn matches_to_string(&self) -> String {
         self.matches
             .iter()
@@ -558,79 +525,31 @@ impl CaseForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_case_form(self);
 
-        params.extend(self.variable.all_parameters());
-
-        for branch in self.matches.iter() {
-            let new_params = branch
-                .all_parameters()
-                .iter()
-                .filter(|bv| !params.iter().any(|v| v.to_string() == bv.to_string()))
-                .map(|v| v.to_owned())
-                .collect::<Vec<SimpleValue>>();
-
-            params.extend(new_params);
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        value_vars.extend(self.variable.all_value_variables());
-
-        for branch in self.matches.iter() {
-            let new_value_vars = branch
-                .all_value_variables()
-                .iter()
-                .filter(|bv| !value_vars.iter().any(|v| v.to_string() == bv.to_string()))
-                .map(|v| v.to_owned())
-                .collect::<Vec<SimpleValue>>();
+        let mut collector = Variables::default();
+        collector.visit_case_form(self);
 
-            value_vars.extend(new_value_vars);
-        }
-
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        type_vars.extend(self.variable.all_type_variables());
-
-        for branch in self.matches.iter() {
-            let new_type_vars = branch
-                .all_type_variables()
-                .iter()
-                .filter(|bv| !type_vars.iter().any(|v| v.to_string() == bv.to_string()))
-                .map(|v| v.to_owned())
-                .collect::<Vec<Type>>();
-
-            type_vars.extend(new_type_vars);
-        }
+        let mut collector = Variables::default();
+        collector.visit_case_form(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_case_form(self);
 
-        vars.extend(self.variable.all_variables());
-
-        for branch in self.matches.iter() {
-            let new_vars = branch
-                .all_variables()
-                .iter()
-                .filter(|bv| !vars.iter().any(|v| v.to_string() == bv.to_string()))
-                .map(|v| v.to_owned())
-                .collect::<Vec<SimpleValue>>();
-
-            vars.extend(new_vars);
-        }
-
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<CaseForm> {
@@ -670,7 +589,16 @@ impl CaseForm {
                 }
             },
             FormTailElement::Form(form) => {
-                if let Ok(form) = LetForm::from_form(&form) {
+                if MemForm::is_mem_form(&form) {
+                    let form = MemForm::from_form(&form)?;
+                    case.variable = CaseFormVariable::MemForm(Box::new(form));
+                } else if LinearForm::is_linear_form(&form) {
+                    let form = LinearForm::from_form(&form)?;
+                    case.variable = CaseFormVariable::LinearForm(Box::new(form));
+                } else if CastForm::is_cast_form(&form) {
+                    let form = CastForm::from_form(&form)?;
+                    case.variable = CaseFormVariable::CastForm(Box::new(form));
+                } else if let Ok(form) = LetForm::from_form(&form) {
                     case.variable = CaseFormVariable::LetForm(Box::new(form));
                 } else if let Ok(form) = AppForm::from_form(&form) {
                     case.variable = CaseFormVariable::AppForm(Box::new(form));
@@ -776,6 +704,23 @@ mod tests {
         assert_eq!(case_match.action.to_string(), "id".to_string());
         assert_eq!(case_match.to_string(), s.to_string());
 
+        s = "(match others id)";
+
+        res = CaseFormMatch::from_str(s);
+
+        assert!(res.is_ok());
+
+        case_match = res.unwrap();
+
+        assert!(case_match.case.is_catch_all());
+        assert_eq!(case_match.to_string(), s.to_string());
+
+        s = "(match id id)";
+
+        res = CaseFormMatch::from_str(s);
+
+        assert!(res.is_err());
+
         s = "(match True (fun t \"True\"))";
 
         res = CaseFormMatch::from_str(s);
@@ -0,0 +1,177 @@
+use crate::error::{Error, SyntacticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::span::Span;
+use crate::token::Tokens;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::form::{Form, FormTailElement};
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
+use crate::value::SimpleValue;
+use crate::value::Type;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct CastForm {
+    pub tokens: Box<Tokens>,
+    pub target: Type,
+    pub value: AppFormValue,
+}
+
+impl CastForm {
+    pub fn new() -> CastForm {
+        CastForm::default()
+    }
+
+    pub fn file(&self) -> String {
+        self.tokens[0].file()
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        self.tokens[0].loc()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
+    pub fn is_cast_form(form: &Form) -> bool {
+        matches!(form.head, SimpleValue::ValueKeyword(_)) && form.head.to_string() == "cast"
+    }
+
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_cast_form(self);
+
+        collector.params
+    }
+
+    pub fn all_value_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_cast_form(self);
+
+        collector.value_vars
+    }
+
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = Variables::default();
+        collector.visit_cast_form(self);
+
+        collector.type_vars
+    }
+
+    pub fn all_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_cast_form(self);
+
+        collector.vars
+    }
+
+    pub fn from_form(form: &Form) -> Result<CastForm> {
+        if !CastForm::is_cast_form(form) {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.head.loc(),
+                desc: "expected a cast keyword".into(),
+            }));
+        }
+
+        if form.tail.len() != 2 {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.loc(),
+                desc: "expected a type and a value".into(),
+            }));
+        }
+
+        let mut cast = CastForm::new();
+        cast.tokens = form.tokens.clone();
+
+        cast.target = match form.tail[0].clone() {
+            FormTailElement::Simple(value) => Type::from_simple_value(&value)?,
+            FormTailElement::Form(form) => Type::from_form(&form)?,
+        };
+
+        let mut value_form = form.clone();
+        value_form.tail = form.tail[1..].to_vec();
+
+        let mut app = AppForm::new();
+        app.parse_variables(&value_form)?;
+        cast.value = app.variables[0].clone();
+
+        Ok(cast)
+    }
+
+    pub fn from_tokens(tokens: &Tokens) -> Result<CastForm> {
+        let form = Form::from_tokens(tokens)?;
+
+        CastForm::from_form(&form)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<CastForm> {
+        let tokens = Tokens::from_str(s)?;
+
+        CastForm::from_tokens(&tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        format!("(cast {} {})", self.target, self.value)
+    }
+}
+
+impl fmt::Display for CastForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl std::str::FromStr for CastForm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cast_form_from_str() {
+        use super::CastForm;
+
+        let mut s = "(cast UInt x)";
+
+        let mut res = CastForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        let mut form = res.unwrap();
+
+        assert_eq!(form.target.to_string(), "UInt".to_string());
+        assert_eq!(form.value.to_string(), "x".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(cast Char (math.+ 96 1))";
+
+        res = CastForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.target.to_string(), "Char".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(cast UInt)";
+
+        res = CastForm::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(cast x UInt)";
+
+        res = CastForm::from_str(s);
+
+        assert!(res.is_err());
+    }
+}
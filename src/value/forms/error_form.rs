@@ -0,0 +1,58 @@
+use crate::error::SyntacticError;
+use crate::loc::Loc;
+use crate::span::Span;
+use crate::token::{TokenKind, Tokens};
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+pub struct ErrorForm {
+    pub tokens: Box<Tokens>,
+    pub error: SyntacticError,
+}
+
+impl ErrorForm {
+    pub fn new(tokens: &Tokens, error: SyntacticError) -> ErrorForm {
+        ErrorForm {
+            tokens: Box::new(tokens.to_owned()),
+            error,
+        }
+    }
+
+    pub fn file(&self) -> String {
+        self.tokens[0].file()
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        self.tokens[0].loc()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        let mut s = String::new();
+        let mut prev_kind = TokenKind::FormStart;
+
+        for token in self.tokens.clone().into_iter() {
+            if !s.is_empty()
+                && prev_kind != TokenKind::FormStart
+                && token.kind != TokenKind::FormEnd
+            {
+                s.push(' ');
+            }
+
+            s.push_str(&token.to_string());
+            prev_kind = token.kind;
+        }
+
+        s
+    }
+}
+
+impl fmt::Display for ErrorForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
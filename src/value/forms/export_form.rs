@@ -1,9 +1,12 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::variables::Variables;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -38,6 +41,14 @@ impl ExportFormDef {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ExportFormDef::Empty(empty) => empty.span(),
+            ExportFormDef::ValueSymbol(symbol) => symbol.span(),
+            ExportFormDef::TypeSymbol(symbol) => symbol.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -73,6 +84,10 @@ impl ExportForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn defs_to_string(&self) -> String {
         match self.defs.len() {
             1 => self.defs[0].to_string(),
@@ -89,45 +104,24 @@ impl ExportForm {
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for def in self.defs.iter() {
-            if let ExportFormDef::ValueSymbol(value) = def.clone() {
-                value_vars.push(value);
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_export_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_export_form(self);
 
-        for def in self.defs.iter() {
-            if let ExportFormDef::TypeSymbol(value) = def.clone() {
-                type_vars.push(Type::from_simple_value(&value).unwrap());
-            }
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for def in self.defs.iter() {
-            match def.clone() {
-                ExportFormDef::ValueSymbol(value) => {
-                    vars.push(value);
-                }
-                ExportFormDef::TypeSymbol(value) => {
-                    vars.push(value);
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_export_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<ExportForm> {
@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::syntax::{is_keyword, is_type_keyword};
 use crate::syntax::{is_symbol, is_type_symbol, is_value_symbol, symbol_name};
 use crate::token::{TokenKind, Tokens};
@@ -28,6 +29,13 @@ impl FormTailElement {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FormTailElement::Simple(value) => value.span(),
+            FormTailElement::Form(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -63,6 +71,10 @@ impl Form {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn tail_to_string(&self) -> String {
         self.tail
             .iter()
@@ -129,6 +141,56 @@ impl Form {
         !(self.is_value_form() || self.is_types_form())
     }
 
+    pub fn split_elements(tokens: &Tokens) -> Vec<Tokens> {
+        let len = tokens.len();
+
+        if len < 2 || tokens[0].kind != TokenKind::FormStart {
+            return vec![];
+        }
+
+        let mut idx = 1;
+        let mut elements = vec![];
+
+        while idx < len - 1 {
+            match tokens[idx].kind {
+                TokenKind::Comment | TokenKind::DocComment => {
+                    idx += 1;
+                }
+                TokenKind::FormStart => {
+                    let mut count = 0;
+                    let mut element = Tokens::new();
+
+                    while idx < len - 1 {
+                        let token = tokens[idx].clone();
+                        element.push(token.clone());
+                        idx += 1;
+
+                        if token.kind == TokenKind::FormStart {
+                            count += 1;
+                        } else if token.kind == TokenKind::FormEnd {
+                            count -= 1;
+
+                            if count == 0 {
+                                break;
+                            }
+                        }
+                    }
+
+                    elements.push(element);
+                }
+                _ => {
+                    let mut element = Tokens::new();
+                    element.push(tokens[idx].clone());
+                    elements.push(element);
+
+                    idx += 1;
+                }
+            }
+        }
+
+        elements
+    }
+
     pub fn from_tokens(tokens: &Tokens) -> Result<Form> {
         let len = tokens.len();
 
@@ -257,6 +319,42 @@ impl std::str::FromStr for Form {
 
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn form_span() {
+        use super::Form;
+        use super::FormTailElement;
+
+        let s = "(f \"λ\"\n  (g x))";
+
+        let form = Form::from_str(s).unwrap();
+        let span = form.span().unwrap();
+
+        assert_eq!(span.start.offset, 0);
+        assert_eq!(span.end.offset, s.len());
+        assert_eq!(span.len(), s.len());
+        assert_eq!((span.start.line, span.start.pos), (0, 0));
+        assert_eq!((span.end.line, span.end.pos), (1, 8));
+        assert_eq!(
+            span.to_string(),
+            "(file: none, start: 0:0, end: 1:8)".to_string()
+        );
+
+        let inner = match &form.tail[1] {
+            FormTailElement::Form(inner) => inner.span().unwrap(),
+            _ => panic!("expected a form"),
+        };
+
+        assert_eq!(&s[inner.start.offset..inner.end.offset], "(g x)");
+        assert_eq!((inner.start.line, inner.start.pos), (1, 2));
+        assert!(span.contains(inner.start.offset));
+        assert_eq!(span.join(&inner), span);
+
+        let atomic = form.tail[0].span().unwrap();
+
+        assert_eq!(&s[atomic.start.offset..atomic.end.offset], "\"λ\"");
+        assert_eq!((atomic.end.line, atomic.end.pos), (0, 6));
+    }
+
     #[test]
     fn form_from_str() {
         use super::Form;
@@ -1,16 +1,22 @@
 use crate::error::{Error, SemanticError, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::arr_form::ArrForm;
 use crate::value::forms::case_form::CaseForm;
+use crate::value::forms::cast_form::CastForm;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::let_form::LetForm;
+use crate::value::forms::linear_form::LinearForm;
 use crate::value::forms::list_form::ListForm;
 use crate::value::forms::map_form::MapForm;
+use crate::value::forms::mem_form::MemForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
 use crate::value::forms::vec_form::VecForm;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -57,6 +63,18 @@ impl FunFormParameter {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FunFormParameter::Empty(empty) => empty.span(),
+            FunFormParameter::ValueSymbol(symbol) => symbol.span(),
+            FunFormParameter::MapForm(form) => form.span(),
+            FunFormParameter::VecForm(form) => form.span(),
+            FunFormParameter::ArrForm(form) => form.span(),
+            FunFormParameter::ListForm(form) => form.span(),
+            FunFormParameter::PairForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -90,6 +108,9 @@ pub enum FunFormBody {
     ListForm(Box<ListForm>),
     PairForm(Box<PairForm>),
     AppForm(Box<AppForm>),
+    MemForm(Box<MemForm>),
+    LinearForm(Box<LinearForm>),
+    CastForm(Box<CastForm>),
     LetForm(Box<LetForm>),
     CaseForm(Box<CaseForm>),
     FunForm(Box<FunForm>),
@@ -115,6 +136,9 @@ impl FunFormBody {
             FunFormBody::ListForm(form) => form.file(),
             FunFormBody::PairForm(form) => form.file(),
             FunFormBody::AppForm(form) => form.file(),
+            FunFormBody::MemForm(form) => form.file(),
+            FunFormBody::LinearForm(form) => form.file(),
+            FunFormBody::CastForm(form) => form.file(),
             FunFormBody::LetForm(form) => form.file(),
             FunFormBody::CaseForm(form) => form.file(),
             FunFormBody::FunForm(form) => form.file(),
@@ -134,12 +158,37 @@ impl FunFormBody {
             FunFormBody::ListForm(form) => form.loc(),
             FunFormBody::PairForm(form) => form.loc(),
             FunFormBody::AppForm(form) => form.loc(),
+            FunFormBody::MemForm(form) => form.loc(),
+            FunFormBody::LinearForm(form) => form.loc(),
+            FunFormBody::CastForm(form) => form.loc(),
             FunFormBody::LetForm(form) => form.loc(),
             FunFormBody::CaseForm(form) => form.loc(),
             FunFormBody::FunForm(form) => form.loc(),
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            FunFormBody::Empty(empty) => empty.span(),
+            FunFormBody::Panic(panic) => panic.span(),
+            FunFormBody::Atomic(atomic) => atomic.span(),
+            FunFormBody::ValueSymbol(symbol) => symbol.span(),
+            FunFormBody::ValuePathSymbol(symbol) => symbol.span(),
+            FunFormBody::MapForm(form) => form.span(),
+            FunFormBody::VecForm(form) => form.span(),
+            FunFormBody::ArrForm(form) => form.span(),
+            FunFormBody::ListForm(form) => form.span(),
+            FunFormBody::PairForm(form) => form.span(),
+            FunFormBody::AppForm(form) => form.span(),
+            FunFormBody::MemForm(form) => form.span(),
+            FunFormBody::LinearForm(form) => form.span(),
+            FunFormBody::CastForm(form) => form.span(),
+            FunFormBody::LetForm(form) => form.span(),
+            FunFormBody::CaseForm(form) => form.span(),
+            FunFormBody::FunForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -154,6 +203,9 @@ impl FunFormBody {
             FunFormBody::ListForm(form) => form.to_string(),
             FunFormBody::PairForm(form) => form.to_string(),
             FunFormBody::AppForm(form) => form.to_string(),
+            FunFormBody::MemForm(form) => form.to_string(),
+            FunFormBody::LinearForm(form) => form.to_string(),
+            FunFormBody::CastForm(form) => form.to_string(),
             FunFormBody::LetForm(form) => form.to_string(),
             FunFormBody::CaseForm(form) => form.to_string(),
             FunFormBody::FunForm(form) => form.to_string(),
@@ -187,6 +239,10 @@ impl FunForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn parameters_to_string(&self) -> String {
         self.parameters
             .iter()
@@ -196,187 +252,31 @@ impl FunForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        for param in self.parameters.iter() {
-            match param.clone() {
-                FunFormParameter::ValueSymbol(value) => {
-                    params.push(value);
-                }
-                FunFormParameter::MapForm(form) => {
-                    params.extend(form.all_variables());
-                }
-                FunFormParameter::VecForm(form) => {
-                    params.extend(form.all_variables());
-                }
-                FunFormParameter::ArrForm(form) => {
-                    params.extend(form.all_variables());
-                }
-                FunFormParameter::ListForm(form) => {
-                    params.extend(form.all_variables());
-                }
-                FunFormParameter::PairForm(form) => {
-                    params.extend(form.all_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Parameters::default();
+        collector.visit_fun_form(self);
 
-        match self.body.clone() {
-            FunFormBody::MapForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::VecForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::ListForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::ArrForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            FunFormBody::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self.body.clone() {
-            FunFormBody::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            FunFormBody::ValuePathSymbol(value) => {
-                value_vars.push(value);
-            }
-            FunFormBody::MapForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::VecForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::ArrForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::ListForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::PairForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::AppForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::CaseForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            FunFormBody::FunForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_fun_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_fun_form(self);
 
-        match self.body.clone() {
-            FunFormBody::MapForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::VecForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::ArrForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::ListForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            FunFormBody::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_fun_form(self);
 
-        match self.body.clone() {
-            FunFormBody::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            FunFormBody::ValuePathSymbol(value) => {
-                vars.push(value);
-            }
-            FunFormBody::MapForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::VecForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::ArrForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::ListForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            FunFormBody::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
-
-        vars
+        collector.vars
     }
 
     pub fn all_bound_variables(&self) -> Vec<SimpleValue> {
@@ -643,7 +543,16 @@ impl FunForm {
                 }
             },
             FormTailElement::Form(form) => {
-                if let Ok(form) = PairForm::from_form(&form) {
+                if MemForm::is_mem_form(&form) {
+                    let form = MemForm::from_form(&form)?;
+                    self.body = FunFormBody::MemForm(Box::new(form));
+                } else if LinearForm::is_linear_form(&form) {
+                    let form = LinearForm::from_form(&form)?;
+                    self.body = FunFormBody::LinearForm(Box::new(form));
+                } else if CastForm::is_cast_form(&form) {
+                    let form = CastForm::from_form(&form)?;
+                    self.body = FunFormBody::CastForm(Box::new(form));
+                } else if let Ok(form) = PairForm::from_form(&form) {
                     self.body = FunFormBody::PairForm(Box::new(form));
                 } else if let Ok(form) = LetForm::from_form(&form) {
                     self.body = FunFormBody::LetForm(Box::new(form));
@@ -1486,4 +1395,29 @@ mod tests {
 
         assert!(form.check_parameters_use().is_err());
     }
+
+    #[test]
+    fn fun_form_check_parameters_use_dup_drop() {
+        use super::FunForm;
+
+        let mut form = FunForm::from_str("(fun a b (f (dup a) b))").unwrap();
+
+        assert!(form.check_parameters_use().is_ok());
+
+        form = FunForm::from_str("(fun a b (f (dup a) b a))").unwrap();
+
+        assert!(form.check_parameters_use().is_err());
+
+        form = FunForm::from_str("(fun a b (f (drop a) b))").unwrap();
+
+        assert!(form.check_parameters_use().is_ok());
+
+        form = FunForm::from_str("(fun a b (f (drop a) b a))").unwrap();
+
+        assert!(form.check_parameters_use().is_err());
+
+        form = FunForm::from_str("(fun a b (f (dup a) b b))").unwrap();
+
+        assert!(form.check_parameters_use().is_err());
+    }
 }
@@ -1,10 +1,13 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::list_form::{ListForm, ListFormValue};
-use crate::value::types::Type;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::types::{SimpleType, Type};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -41,6 +44,15 @@ impl ImportFormDef {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ImportFormDef::Ignore(ignore) => ignore.span(),
+            ImportFormDef::Empty(empty) => empty.span(),
+            ImportFormDef::ValueSymbol(symbol) => symbol.span(),
+            ImportFormDef::TypeSymbol(symbol) => symbol.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -80,6 +92,10 @@ impl ImportForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn type_variables_to_string(&self) -> String {
         match self.type_variables.len() {
             0 => "()".into(),
@@ -110,38 +126,54 @@ impl ImportForm {
         }
     }
 
-    pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        for def in self.defs.iter() {
-            match def.clone() {
-                ImportFormDef::Ignore(value) => {
-                    params.push(value);
-                }
-                ImportFormDef::Empty(value) => {
-                    params.push(value);
-                }
-                ImportFormDef::ValueSymbol(value) => {
-                    params.push(value);
-                }
-                ImportFormDef::TypeSymbol(value) => {
-                    params.push(value);
-                }
-            }
+    pub fn qualifier_name(&self) -> String {
+        match self.qualifier {
+            Some(ref qualifier) => qualifier.to_string(),
+            None => self.module.to_string(),
         }
+    }
 
-        params
+    pub fn is_qualified_only(&self) -> bool {
+        self.defs
+            .iter()
+            .any(|def| matches!(def, ImportFormDef::Empty(_)))
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        self.type_variables.clone()
+    pub fn imports_all(&self) -> bool {
+        self.defs.is_empty()
+            || self
+                .defs
+                .iter()
+                .any(|def| matches!(def, ImportFormDef::Ignore(_)))
     }
 
-    pub fn all_variables(&self) -> Vec<SimpleValue> {
+    pub fn type_arguments(&self) -> Vec<Type> {
         self.type_variables
             .iter()
-            .map(|tv| tv.as_simple_value().unwrap())
-            .collect::<Vec<SimpleValue>>()
+            .filter(|tv| !matches!(tv, Type::Simple(SimpleType::Ignore(_))))
+            .cloned()
+            .collect::<Vec<Type>>()
+    }
+
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_import_form(self);
+
+        collector.params
+    }
+
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = Variables::default();
+        collector.visit_import_form(self);
+
+        collector.type_vars
+    }
+
+    pub fn all_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_import_form(self);
+
+        collector.vars
     }
 
     fn parse_qualifier(&mut self, form: &Form, idx: usize) -> Result<()> {
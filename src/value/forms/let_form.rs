@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::app_form::AppFormValue;
@@ -13,6 +14,8 @@ use crate::value::forms::pair_form::PairForm;
 use crate::value::forms::sig_form::SigForm;
 use crate::value::forms::type_form::TypeForm;
 use crate::value::forms::val_form::ValForm;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -56,6 +59,17 @@ impl LetFormEntry {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            LetFormEntry::Empty(empty) => empty.span(),
+            LetFormEntry::ImportForm(form) => form.span(),
+            LetFormEntry::AttrsForm(form) => form.span(),
+            LetFormEntry::TypeForm(form) => form.span(),
+            LetFormEntry::SigForm(form) => form.span(),
+            LetFormEntry::ValForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -97,6 +111,10 @@ impl LetForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn entry_as_import(&self, idx: usize) -> Option<Box<ImportForm>> {
         if idx > self.entries.len() - 1 {
             return None;
@@ -161,99 +179,31 @@ impl LetForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
-
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                LetFormEntry::ImportForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                LetFormEntry::ValForm(form) => {
-                    params.push(form.name.clone());
-                    params.extend(form.all_parameters());
-                }
-                _ => {}
-            }
-        }
-
-        params.extend(self.value.all_parameters());
+        let mut collector = Parameters::default();
+        collector.visit_let_form(self);
 
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                LetFormEntry::AttrsForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                LetFormEntry::ValForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                _ => {}
-            }
-        }
-
-        value_vars.extend(self.value.all_value_variables());
+        let mut collector = Variables::default();
+        collector.visit_let_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_let_form(self);
 
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                LetFormEntry::AttrsForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                LetFormEntry::TypeForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                LetFormEntry::SigForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                LetFormEntry::ValForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                _ => {}
-            }
-        }
-
-        type_vars.extend(self.value.all_type_variables());
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for entry in self.entries.iter() {
-            match entry.clone() {
-                LetFormEntry::ImportForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                LetFormEntry::AttrsForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                LetFormEntry::TypeForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                LetFormEntry::SigForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                LetFormEntry::ValForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                _ => {}
-            }
-        }
-
-        vars.extend(self.value.all_variables());
+        let mut collector = Variables::default();
+        collector.visit_let_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<LetForm> {
@@ -0,0 +1,222 @@
+use crate::error::{Error, SyntacticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::span::Span;
+use crate::token::Tokens;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::form::Form;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
+use crate::value::SimpleValue;
+use crate::value::Type;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub enum LinearFormKind {
+    #[default]
+    Dup,
+    Drop,
+}
+
+impl LinearFormKind {
+    pub fn from_keyword(s: &str) -> Option<LinearFormKind> {
+        match s {
+            "dup" => Some(LinearFormKind::Dup),
+            "drop" => Some(LinearFormKind::Drop),
+            _ => None,
+        }
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        match self {
+            LinearFormKind::Dup => "dup".into(),
+            LinearFormKind::Drop => "drop".into(),
+        }
+    }
+}
+
+impl fmt::Display for LinearFormKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct LinearForm {
+    pub tokens: Box<Tokens>,
+    pub kind: LinearFormKind,
+    pub value: AppFormValue,
+}
+
+impl LinearForm {
+    pub fn new() -> LinearForm {
+        LinearForm::default()
+    }
+
+    pub fn file(&self) -> String {
+        self.tokens[0].file()
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        self.tokens[0].loc()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
+    pub fn is_linear_form(form: &Form) -> bool {
+        match form.head {
+            SimpleValue::ValueKeyword(_) => {
+                LinearFormKind::from_keyword(&form.head.to_string()).is_some()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_dup(&self) -> bool {
+        self.kind == LinearFormKind::Dup
+    }
+
+    pub fn is_drop(&self) -> bool {
+        self.kind == LinearFormKind::Drop
+    }
+
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_linear_form(self);
+
+        collector.params
+    }
+
+    pub fn all_value_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_linear_form(self);
+
+        collector.value_vars
+    }
+
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = Variables::default();
+        collector.visit_linear_form(self);
+
+        collector.type_vars
+    }
+
+    pub fn all_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_linear_form(self);
+
+        collector.vars
+    }
+
+    pub fn from_form(form: &Form) -> Result<LinearForm> {
+        if !LinearForm::is_linear_form(form) {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.head.loc(),
+                desc: "expected a dup or drop keyword".into(),
+            }));
+        }
+
+        if form.tail.len() != 1 {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.loc(),
+                desc: "expected a value".into(),
+            }));
+        }
+
+        let mut linear = LinearForm::new();
+        linear.tokens = form.tokens.clone();
+        linear.kind = LinearFormKind::from_keyword(&form.head.to_string()).unwrap_or_default();
+
+        let mut app = AppForm::new();
+        app.parse_variables(form)?;
+        linear.value = app.variables[0].clone();
+
+        Ok(linear)
+    }
+
+    pub fn from_tokens(tokens: &Tokens) -> Result<LinearForm> {
+        let form = Form::from_tokens(tokens)?;
+
+        LinearForm::from_form(&form)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<LinearForm> {
+        let tokens = Tokens::from_str(s)?;
+
+        LinearForm::from_tokens(&tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        format!("({} {})", self.kind, self.value)
+    }
+}
+
+impl fmt::Display for LinearForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl std::str::FromStr for LinearForm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn linear_form_from_str() {
+        use super::LinearForm;
+        use super::LinearFormKind;
+
+        let mut s = "(dup x)";
+
+        let mut res = LinearForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        let mut form = res.unwrap();
+
+        assert_eq!(form.kind, LinearFormKind::Dup);
+        assert_eq!(form.value.to_string(), "x".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(drop (f x))";
+
+        res = LinearForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.kind, LinearFormKind::Drop);
+        assert_eq!(form.value.to_string(), "(f x)".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(dup x y)";
+
+        res = LinearForm::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(drop)";
+
+        res = LinearForm::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(ref x)";
+
+        res = LinearForm::from_str(s);
+
+        assert!(res.is_err());
+    }
+}
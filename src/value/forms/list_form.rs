@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::arr_form::ArrForm;
@@ -10,8 +11,10 @@ use crate::value::forms::fun_form::FunForm;
 use crate::value::forms::let_form::LetForm;
 use crate::value::forms::map_form::MapForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
 use crate::value::forms::vec_form::VecForm;
 use crate::value::types::Type;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -96,6 +99,31 @@ impl ListFormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ListFormValue::Ignore(ignore) => ignore.span(),
+            ListFormValue::Empty(empty) => empty.span(),
+            ListFormValue::Panic(panic) => panic.span(),
+            ListFormValue::Atomic(atomic) => atomic.span(),
+            ListFormValue::ValueKeyword(keyword) => keyword.span(),
+            ListFormValue::TypeKeyword(keyword) => keyword.span(),
+            ListFormValue::ValueSymbol(symbol) => symbol.span(),
+            ListFormValue::TypeSymbol(symbol) => symbol.span(),
+            ListFormValue::ValuePathSymbol(symbol) => symbol.span(),
+            ListFormValue::TypePathSymbol(symbol) => symbol.span(),
+            ListFormValue::Type(form) => form.span(),
+            ListFormValue::FunForm(form) => form.span(),
+            ListFormValue::CaseForm(form) => form.span(),
+            ListFormValue::LetForm(form) => form.span(),
+            ListFormValue::AppForm(form) => form.span(),
+            ListFormValue::PairForm(form) => form.span(),
+            ListFormValue::ArrForm(form) => form.span(),
+            ListFormValue::VecForm(form) => form.span(),
+            ListFormValue::MapForm(form) => form.span(),
+            ListFormValue::ListForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -148,6 +176,10 @@ impl ListForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn len(&self) -> usize {
         self.values.len()
     }
@@ -171,31 +203,11 @@ impl ListForm {
                 | ListFormValue::CaseForm(_)
                 | ListFormValue::LetForm(_)
                 | ListFormValue::AppForm(_) => return false,
-                ListFormValue::PairForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ListFormValue::MapForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ListFormValue::ArrForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ListFormValue::VecForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                ListFormValue::ListForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
+                ListFormValue::PairForm(form) if !form.can_be_parameter() => return false,
+                ListFormValue::MapForm(form) if !form.can_be_parameter() => return false,
+                ListFormValue::ArrForm(form) if !form.can_be_parameter() => return false,
+                ListFormValue::VecForm(form) if !form.can_be_parameter() => return false,
+                ListFormValue::ListForm(form) if !form.can_be_parameter() => return false,
                 _ => {}
             }
         }
@@ -212,189 +224,31 @@ impl ListForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_list_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                ListFormValue::FunForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::CaseForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::LetForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::AppForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::PairForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::ArrForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::MapForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::VecForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                ListFormValue::ListForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                _ => {}
-            }
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                ListFormValue::ValueSymbol(value) => {
-                    value_vars.push(value);
-                }
-                ListFormValue::ValuePathSymbol(value) => {
-                    value_vars.push(value);
-                }
-                ListFormValue::FunForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::CaseForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::LetForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::AppForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::PairForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::ArrForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::MapForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::VecForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                ListFormValue::ListForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_list_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_list_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                ListFormValue::TypeSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                ListFormValue::TypePathSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                ListFormValue::Type(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::FunForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::CaseForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::LetForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::AppForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::PairForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::ArrForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::MapForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::VecForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                ListFormValue::ListForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                _ => {}
-            }
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                ListFormValue::ValueSymbol(value) => {
-                    vars.push(value);
-                }
-                ListFormValue::TypeSymbol(value) => {
-                    vars.push(value);
-                }
-                ListFormValue::ValuePathSymbol(value) => {
-                    vars.push(value);
-                }
-                ListFormValue::TypePathSymbol(value) => {
-                    vars.push(value);
-                }
-                ListFormValue::Type(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::FunForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::CaseForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::LetForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::AppForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::PairForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::ArrForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::MapForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::VecForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                ListFormValue::ListForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_list_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<ListForm> {
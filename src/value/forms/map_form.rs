@@ -1,9 +1,12 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::collections::BTreeMap;
@@ -39,6 +42,14 @@ impl MapFormEntry {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            MapFormEntry::Ignore(ignore) => ignore.span(),
+            MapFormEntry::Empty(empty) => empty.span(),
+            MapFormEntry::PairForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -49,36 +60,24 @@ impl MapFormEntry {
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self {
-            MapFormEntry::Ignore(_) | MapFormEntry::Empty(_) => {}
-            MapFormEntry::PairForm(form) => value_vars.extend(form.all_value_variables()),
-        }
+        let mut collector = Variables::default();
+        collector.visit_map_form_entry(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_map_form_entry(self);
 
-        match self {
-            MapFormEntry::Ignore(_) | MapFormEntry::Empty(_) => {}
-            MapFormEntry::PairForm(form) => type_vars.extend(form.all_type_variables()),
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_map_form_entry(self);
 
-        match self {
-            MapFormEntry::Ignore(_) | MapFormEntry::Empty(_) => {}
-            MapFormEntry::PairForm(form) => vars.extend(form.all_variables()),
-        }
-
-        vars
+        collector.vars
     }
 }
 
@@ -107,6 +106,10 @@ impl MapForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn len(&self) -> usize {
         if self.is_empty() {
             0
@@ -228,45 +231,31 @@ impl MapForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_map_form(self);
 
-        for entry in self.entries.iter() {
-            if let MapFormEntry::PairForm(form) = entry.clone() {
-                params.extend(form.all_parameters());
-            }
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for entry in self.entries.iter() {
-            value_vars.extend(entry.all_value_variables());
-        }
+        let mut collector = Variables::default();
+        collector.visit_map_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        for entry in self.entries.iter() {
-            type_vars.extend(entry.all_type_variables());
-        }
+        let mut collector = Variables::default();
+        collector.visit_map_form(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for entry in self.entries.iter() {
-            vars.extend(entry.all_variables());
-        }
+        let mut collector = Variables::default();
+        collector.visit_map_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<MapForm> {
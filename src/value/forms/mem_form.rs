@@ -0,0 +1,257 @@
+use crate::error::{Error, SyntacticError};
+use crate::loc::Loc;
+use crate::result::Result;
+use crate::span::Span;
+use crate::token::Tokens;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::form::Form;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
+use crate::value::SimpleValue;
+use crate::value::Type;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub enum MemFormKind {
+    #[default]
+    Ref,
+    Deref,
+    Load,
+    Store,
+    Size,
+}
+
+impl MemFormKind {
+    pub fn from_keyword(s: &str) -> Option<MemFormKind> {
+        match s {
+            "ref" => Some(MemFormKind::Ref),
+            "deref" => Some(MemFormKind::Deref),
+            "load" => Some(MemFormKind::Load),
+            "store" => Some(MemFormKind::Store),
+            "size" => Some(MemFormKind::Size),
+            _ => None,
+        }
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            MemFormKind::Store => 2,
+            _ => 1,
+        }
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        match self {
+            MemFormKind::Ref => "ref".into(),
+            MemFormKind::Deref => "deref".into(),
+            MemFormKind::Load => "load".into(),
+            MemFormKind::Store => "store".into(),
+            MemFormKind::Size => "size".into(),
+        }
+    }
+}
+
+impl fmt::Display for MemFormKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Default)]
+pub struct MemForm {
+    pub tokens: Box<Tokens>,
+    pub kind: MemFormKind,
+    pub values: Vec<AppFormValue>,
+}
+
+impl MemForm {
+    pub fn new() -> MemForm {
+        MemForm::default()
+    }
+
+    pub fn file(&self) -> String {
+        self.tokens[0].file()
+    }
+
+    pub fn loc(&self) -> Option<Loc> {
+        self.tokens[0].loc()
+    }
+
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
+    pub fn is_mem_form(form: &Form) -> bool {
+        match form.head {
+            SimpleValue::ValueKeyword(_) => {
+                MemFormKind::from_keyword(&form.head.to_string()).is_some()
+            }
+            _ => false,
+        }
+    }
+
+    pub fn values_to_string(&self) -> String {
+        self.values
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    pub fn all_parameters(&self) -> Vec<SimpleValue> {
+        let mut collector = Parameters::default();
+        collector.visit_mem_form(self);
+
+        collector.params
+    }
+
+    pub fn all_value_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_mem_form(self);
+
+        collector.value_vars
+    }
+
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = Variables::default();
+        collector.visit_mem_form(self);
+
+        collector.type_vars
+    }
+
+    pub fn all_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_mem_form(self);
+
+        collector.vars
+    }
+
+    pub fn from_form(form: &Form) -> Result<MemForm> {
+        if !MemForm::is_mem_form(form) {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.head.loc(),
+                desc: "expected a memory keyword".into(),
+            }));
+        }
+
+        let mut mem = MemForm::new();
+        mem.tokens = form.tokens.clone();
+        mem.kind = MemFormKind::from_keyword(&form.head.to_string()).unwrap_or_default();
+
+        let arity = mem.kind.arity();
+
+        if form.tail.len() != arity {
+            return Err(Error::Syntactic(SyntacticError {
+                loc: form.loc(),
+                desc: format!(
+                    "expected {} {} for {}, found {}",
+                    arity,
+                    if arity == 1 { "value" } else { "values" },
+                    mem.kind,
+                    form.tail.len()
+                ),
+            }));
+        }
+
+        let mut app = AppForm::new();
+        app.parse_variables(form)?;
+        mem.values = app.variables;
+
+        Ok(mem)
+    }
+
+    pub fn from_tokens(tokens: &Tokens) -> Result<MemForm> {
+        let form = Form::from_tokens(tokens)?;
+
+        MemForm::from_form(&form)
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Result<MemForm> {
+        let tokens = Tokens::from_str(s)?;
+
+        MemForm::from_tokens(&tokens)
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        format!("({} {})", self.kind, self.values_to_string())
+    }
+}
+
+impl fmt::Display for MemForm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl std::str::FromStr for MemForm {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_str(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn mem_form_from_str() {
+        use super::MemForm;
+        use super::MemFormKind;
+
+        let mut s = "(ref x)";
+
+        let mut res = MemForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        let mut form = res.unwrap();
+
+        assert_eq!(form.kind, MemFormKind::Ref);
+        assert_eq!(form.values_to_string(), "x".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(store p (load (deref r)))";
+
+        res = MemForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.kind, MemFormKind::Store);
+        assert_eq!(form.values.len(), 2);
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(size (pair a b))";
+
+        res = MemForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.kind, MemFormKind::Size);
+        assert_eq!(form.to_string(), s.to_string());
+
+        s = "(store p)";
+
+        res = MemForm::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(deref r s)";
+
+        res = MemForm::from_str(s);
+
+        assert!(res.is_err());
+
+        s = "(f x)";
+
+        res = MemForm::from_str(s);
+
+        assert!(res.is_err());
+    }
+}
@@ -3,18 +3,23 @@ pub mod arr_form;
 pub mod attrs_form;
 pub mod block_form;
 pub mod case_form;
+pub mod cast_form;
+pub mod error_form;
 pub mod export_form;
 pub mod form;
 pub mod fun_form;
 pub mod import_form;
 pub mod let_form;
+pub mod linear_form;
 pub mod list_form;
 pub mod map_form;
+pub mod mem_form;
 pub mod module_form;
 pub mod pair_form;
 pub mod sig_form;
 pub mod type_form;
 pub mod val_form;
+pub(crate) mod variables;
 pub mod vec_form;
 
 pub use app_form::*;
@@ -22,13 +27,17 @@ pub use arr_form::*;
 pub use attrs_form::*;
 pub use block_form::*;
 pub use case_form::*;
+pub use cast_form::*;
+pub use error_form::*;
 pub use export_form::*;
 pub use form::*;
 pub use fun_form::*;
 pub use import_form::*;
 pub use let_form::*;
+pub use linear_form::*;
 pub use list_form::*;
 pub use map_form::*;
+pub use mem_form::*;
 pub use module_form::*;
 pub use pair_form::*;
 pub use sig_form::*;
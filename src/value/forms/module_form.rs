@@ -1,12 +1,19 @@
-use crate::error::{Error, SyntacticError};
+use crate::chunk::StringChunk;
+use crate::error::{Error, SemanticError, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
-use crate::token::Tokens;
+use crate::span::Span;
+use crate::token::{Token, TokenKind, Tokens};
+use crate::value::fold::Fold;
 use crate::value::forms::block_form::{BlockForm, BlockFormEntry};
+use crate::value::forms::export_form::ExportFormDef;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::list_form::{ListForm, ListFormValue};
-use crate::value::types::Type;
+use crate::value::forms::variables::Variables;
+use crate::value::types::{Substitution, Type};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
+use std::collections::BTreeMap;
 use std::fmt;
 
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
@@ -36,6 +43,13 @@ impl ModuleFormBlock {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ModuleFormBlock::Empty(empty) => empty.span(),
+            ModuleFormBlock::Form(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -57,6 +71,7 @@ pub struct ModuleForm {
     pub name: SimpleValue,
     pub type_parameters: Vec<Type>,
     pub block: ModuleFormBlock,
+    pub doc: Option<String>,
 }
 
 impl ModuleForm {
@@ -72,6 +87,10 @@ impl ModuleForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn block_entries(&self) -> Vec<BlockFormEntry> {
         let mut entries = vec![];
 
@@ -97,42 +116,130 @@ impl ModuleForm {
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_module_form(self);
 
-        match self.block.clone() {
-            ModuleFormBlock::Empty(_) => {}
-            ModuleFormBlock::Form(form) => {
-                value_vars = form.all_value_variables();
+        collector.value_vars
+    }
+
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = Variables::default();
+        collector.visit_module_form(self);
+
+        collector.type_vars
+    }
+
+    pub fn all_variables(&self) -> Vec<SimpleValue> {
+        let mut collector = Variables::default();
+        collector.visit_module_form(self);
+
+        collector.vars
+    }
+
+    pub fn definitions(&self) -> Vec<SimpleValue> {
+        let mut defs: Vec<SimpleValue> = vec![];
+
+        for entry in self.block_entries() {
+            let name = match entry {
+                BlockFormEntry::TypeForm(form) => form.name,
+                BlockFormEntry::SigForm(form) => form.name,
+                BlockFormEntry::ValForm(form) => form.name,
+                _ => continue,
+            };
+
+            if !defs.iter().any(|d| d.to_string() == name.to_string()) {
+                defs.push(name);
             }
         }
 
-        value_vars
+        defs
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.definitions().iter().any(|d| d.to_string() == name)
+    }
 
-        match self.block.clone() {
-            ModuleFormBlock::Empty(_) => {}
-            ModuleFormBlock::Form(form) => {
-                type_vars = form.all_type_variables();
+    pub fn has_exports(&self) -> bool {
+        self.block_entries()
+            .iter()
+            .any(|entry| matches!(entry, BlockFormEntry::ExportForm(_)))
+    }
+
+    pub fn exports(&self) -> Result<Vec<SimpleValue>> {
+        if !self.has_exports() {
+            return Ok(self.definitions());
+        }
+
+        let mut exports: Vec<SimpleValue> = vec![];
+
+        for entry in self.block_entries() {
+            if let BlockFormEntry::ExportForm(form) = entry {
+                for def in form.defs.iter() {
+                    match def {
+                        ExportFormDef::ValueSymbol(value) | ExportFormDef::TypeSymbol(value) => {
+                            if !self.is_defined(&value.to_string()) {
+                                return Err(Error::Semantic(SemanticError {
+                                    loc: value.loc(),
+                                    desc: format!(
+                                        "exported name {} is not defined in module {}",
+                                        value, self.name
+                                    ),
+                                }));
+                            }
+
+                            if !exports.iter().any(|e| e.to_string() == value.to_string()) {
+                                exports.push(value.clone());
+                            }
+                        }
+                        ExportFormDef::Empty(_) => {}
+                    }
+                }
             }
         }
 
-        type_vars
+        Ok(exports)
     }
 
-    pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+    pub fn instantiate(&self, args: &[Type]) -> Result<ModuleForm> {
+        if args.len() != self.type_parameters.len() {
+            return Err(Error::Semantic(SemanticError {
+                loc: args
+                    .first()
+                    .and_then(|arg| arg.loc())
+                    .or_else(|| self.loc()),
+                desc: format!(
+                    "expected {} type arguments for module {}, found {}",
+                    self.type_parameters.len(),
+                    self.name,
+                    args.len()
+                ),
+            }));
+        }
 
-        match self.block.clone() {
-            ModuleFormBlock::Empty(_) => {}
-            ModuleFormBlock::Form(form) => {
-                vars = form.all_variables();
-            }
+        let substitutions = self
+            .type_parameters
+            .iter()
+            .map(|tp| tp.to_string())
+            .zip(args.iter().cloned())
+            .collect::<BTreeMap<String, Type>>();
+
+        let mut module = self.clone();
+        module.type_parameters = vec![];
+
+        Ok(Substitution {
+            substitutions: &substitutions,
         }
+        .fold_module_form(module))
+    }
 
-        vars
+    pub fn type_aliases(&self) -> BTreeMap<String, Type> {
+        self.block_entries()
+            .into_iter()
+            .filter_map(|entry| match entry {
+                BlockFormEntry::TypeForm(form) => Some((form.name.to_string(), *form.value)),
+                _ => None,
+            })
+            .collect::<BTreeMap<String, Type>>()
     }
 
     pub fn type_parameters_to_string(&self) -> String {
@@ -207,6 +314,7 @@ impl ModuleForm {
             },
             FormTailElement::Form(form) => {
                 let form = BlockForm::from_form(&form)?;
+                self.doc = form.doc.clone();
                 self.block = ModuleFormBlock::Form(Box::new(form));
             }
         }
@@ -274,6 +382,87 @@ impl ModuleForm {
         ModuleForm::from_form(&form)
     }
 
+    pub fn from_tokens_recovering(tokens: &Tokens) -> (ModuleForm, Vec<Error>) {
+        let mut errors = vec![];
+
+        if tokens.is_empty() {
+            errors.push(Error::Syntactic(SyntacticError {
+                loc: None,
+                desc: "expected a module form".into(),
+            }));
+
+            return (ModuleForm::new(), errors);
+        }
+
+        let elements = Form::split_elements(tokens);
+
+        let block_idx = elements.iter().rposition(|element| {
+            element.len() > 1
+                && element[0].kind == TokenKind::FormStart
+                && element[1].to_string() == "block"
+        });
+
+        let block_idx = match block_idx {
+            Some(idx) if idx > 0 => idx,
+            _ => {
+                return match ModuleForm::from_tokens(tokens) {
+                    Ok(module) => (module, errors),
+                    Err(err) => {
+                        errors.push(err);
+                        (ModuleForm::new(), errors)
+                    }
+                };
+            }
+        };
+
+        let (block, block_errors) = BlockForm::from_tokens_recovering(&elements[block_idx]);
+
+        let mut header = Tokens::new();
+        header.push(tokens[0].clone());
+
+        for (idx, element) in elements.iter().enumerate() {
+            if idx == block_idx {
+                let mut token = Token::new_empty_literal();
+                token.push(StringChunk {
+                    loc: element[0].loc().unwrap_or_default(),
+                    content: "()".into(),
+                });
+                header.push(token);
+            } else {
+                for token in element.clone().into_iter() {
+                    header.push(token);
+                }
+            }
+        }
+
+        header.push(tokens[tokens.len() - 1].clone());
+
+        let mut module = match ModuleForm::from_tokens(&header) {
+            Ok(module) => module,
+            Err(err) => {
+                errors.push(err);
+                ModuleForm::new()
+            }
+        };
+
+        module.tokens = Box::new(tokens.to_owned());
+        module.doc = block.doc.clone();
+        module.block = ModuleFormBlock::Form(Box::new(block));
+
+        errors.extend(block_errors);
+
+        (module, errors)
+    }
+
+    pub fn from_str_recovering(s: &str) -> (ModuleForm, Vec<Error>) {
+        let (tokens, mut errors) = Tokens::from_str_recovering(s);
+        let (module, module_errors) = ModuleForm::from_tokens_recovering(&tokens);
+
+        errors.extend(module_errors);
+
+        (module, errors)
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<ModuleForm> {
         let tokens = Tokens::from_str(s)?;
@@ -452,4 +641,74 @@ mod tests {
             "(import std.io _ println)".to_string()
         );
     }
+
+    #[test]
+    fn module_form_exports() {
+        use super::ModuleForm;
+
+        let mut s = "(module x (block (type T UInt) (sig f (Fun T T)) (val f (fun a a))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+
+        assert_eq!(form.definitions().len(), 2);
+        assert!(!form.has_exports());
+        assert_eq!(form.exports().unwrap().len(), 2);
+
+        s = "(module x (block (export f) (type T UInt) (val f (fun a a))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+
+        assert!(form.has_exports());
+
+        let exports = form.exports().unwrap();
+
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].to_string(), "f".to_string());
+
+        s = "(module x (block (export (list f g)) (val f (fun a a))))";
+
+        form = ModuleForm::from_str(s).unwrap();
+
+        let res = form.exports();
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("exported name g is not defined in module x"));
+    }
+
+    #[test]
+    fn module_form_instantiate() {
+        use super::ModuleForm;
+        use crate::value::forms::import_form::ImportForm;
+
+        let s = "(module result (list T E) (block (type Result (Enum T E)) (sig unwrap (Fun Result T))))";
+
+        let form = ModuleForm::from_str(s).unwrap();
+
+        let args = ImportForm::from_str("(import result (list String StringError))")
+            .unwrap()
+            .type_arguments();
+
+        let mut res = form.instantiate(&args);
+
+        assert!(res.is_ok());
+
+        let instance = res.unwrap();
+
+        assert!(instance.type_parameters.is_empty());
+        assert_eq!(
+            instance.to_string(),
+            "(module result (block (type Result (Enum String StringError)) (sig unwrap (Fun Result String))))".to_string()
+        );
+
+        res = form.instantiate(&args[..1]);
+
+        assert!(res.is_err());
+        assert!(res
+            .unwrap_err()
+            .to_string()
+            .contains("expected 2 type arguments for module result, found 1"));
+    }
 }
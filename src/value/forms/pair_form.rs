@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::arr_form::ArrForm;
@@ -10,8 +11,10 @@ use crate::value::forms::fun_form::FunForm;
 use crate::value::forms::let_form::LetForm;
 use crate::value::forms::list_form::ListForm;
 use crate::value::forms::map_form::MapForm;
+use crate::value::forms::variables::{Parameters, Variables};
 use crate::value::forms::vec_form::VecForm;
 use crate::value::types::Type;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -96,6 +99,31 @@ impl PairFormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            PairFormValue::Ignore(ignore) => ignore.span(),
+            PairFormValue::Empty(empty) => empty.span(),
+            PairFormValue::Panic(panic) => panic.span(),
+            PairFormValue::Atomic(atomic) => atomic.span(),
+            PairFormValue::ValueKeyword(keyword) => keyword.span(),
+            PairFormValue::TypeKeyword(keyword) => keyword.span(),
+            PairFormValue::ValueSymbol(symbol) => symbol.span(),
+            PairFormValue::TypeSymbol(symbol) => symbol.span(),
+            PairFormValue::ValuePathSymbol(symbol) => symbol.span(),
+            PairFormValue::TypePathSymbol(symbol) => symbol.span(),
+            PairFormValue::Type(form) => form.span(),
+            PairFormValue::MapForm(form) => form.span(),
+            PairFormValue::VecForm(form) => form.span(),
+            PairFormValue::ArrForm(form) => form.span(),
+            PairFormValue::ListForm(form) => form.span(),
+            PairFormValue::PairForm(form) => form.span(),
+            PairFormValue::FunForm(form) => form.span(),
+            PairFormValue::CaseForm(form) => form.span(),
+            PairFormValue::LetForm(form) => form.span(),
+            PairFormValue::AppForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -149,6 +177,10 @@ impl PairForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn can_be_parameter(&self) -> bool {
         match self.first.clone() {
             PairFormValue::Ignore(_)
@@ -164,31 +196,11 @@ impl PairForm {
             | PairFormValue::CaseForm(_)
             | PairFormValue::LetForm(_)
             | PairFormValue::AppForm(_) => return false,
-            PairFormValue::PairForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::MapForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::ArrForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::VecForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::ListForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
+            PairFormValue::PairForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::MapForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::ArrForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::VecForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::ListForm(form) if !form.can_be_parameter() => return false,
             _ => {}
         }
 
@@ -206,31 +218,11 @@ impl PairForm {
             | PairFormValue::CaseForm(_)
             | PairFormValue::LetForm(_)
             | PairFormValue::AppForm(_) => return false,
-            PairFormValue::PairForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::MapForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::ArrForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::VecForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
-            PairFormValue::ListForm(form) => {
-                if !form.can_be_parameter() {
-                    return false;
-                }
-            }
+            PairFormValue::PairForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::MapForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::ArrForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::VecForm(form) if !form.can_be_parameter() => return false,
+            PairFormValue::ListForm(form) if !form.can_be_parameter() => return false,
             _ => {}
         }
 
@@ -238,335 +230,31 @@ impl PairForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_pair_form(self);
 
-        match self.first.clone() {
-            PairFormValue::MapForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::VecForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::ArrForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::ListForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
-        }
-
-        match self.second.clone() {
-            PairFormValue::MapForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::VecForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::ArrForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::ListForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            PairFormValue::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        match self.first.clone() {
-            PairFormValue::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            PairFormValue::ValuePathSymbol(value) => {
-                value_vars.push(value);
-            }
-            PairFormValue::MapForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
-
-        match self.second.clone() {
-            PairFormValue::ValueSymbol(value) => {
-                value_vars.push(value);
-            }
-            PairFormValue::ValuePathSymbol(value) => {
-                value_vars.push(value);
-            }
-            PairFormValue::MapForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                value_vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_pair_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        match self.first.clone() {
-            PairFormValue::TypeSymbol(value) => {
-                type_vars.push(Type::from_simple_value(&value).unwrap());
-            }
-            PairFormValue::TypePathSymbol(value) => {
-                type_vars.push(Type::from_simple_value(&value).unwrap());
-            }
-            PairFormValue::Type(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::MapForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_pair_form(self);
 
-        match self.second.clone() {
-            PairFormValue::TypeSymbol(value) => {
-                type_vars.push(Type::from_simple_value(&value).unwrap());
-            }
-            PairFormValue::TypePathSymbol(value) => {
-                type_vars.push(Type::from_simple_value(&value).unwrap());
-            }
-            PairFormValue::Type(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::MapForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self.first.clone() {
-            PairFormValue::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::TypeSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::ValuePathSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::TypePathSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::Type(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::MapForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
-
-        match self.second.clone() {
-            PairFormValue::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::TypeSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::ValuePathSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::TypePathSymbol(value) => {
-                vars.push(value);
-            }
-            PairFormValue::Type(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::MapForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::VecForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::ArrForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::ListForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            PairFormValue::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_pair_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<PairForm> {
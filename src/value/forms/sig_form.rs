@@ -1,9 +1,12 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::form::{Form, FormTailElement};
+use crate::value::forms::variables::Variables;
 use crate::value::types::{SimpleType, Type};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -12,6 +15,7 @@ pub struct SigForm {
     pub tokens: Box<Tokens>,
     pub name: SimpleValue,
     pub value: Box<Type>,
+    pub doc: Option<String>,
 }
 
 impl SigForm {
@@ -27,6 +31,10 @@ impl SigForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn is_empty_type(&self) -> bool {
         matches!(self.value.as_ref(), Type::Simple(SimpleType::Empty(_)))
     }
@@ -57,11 +65,17 @@ impl SigForm {
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        self.value.all_type_variables()
+        let mut collector = Variables::default();
+        collector.visit_sig_form(self);
+
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        self.value.all_variables()
+        let mut collector = Variables::default();
+        collector.visit_sig_form(self);
+
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<SigForm> {
@@ -165,6 +179,8 @@ mod tests {
         assert!(form.is_empty_type());
         assert_eq!(form.value.to_string(), "Empty".to_string());
         assert_eq!(form.to_string(), s.to_string());
+        assert_eq!(form.span().unwrap().len(), s.len());
+        assert_eq!(form.value.span().unwrap().start.offset, 7);
 
         s = "(sig t Atomic)";
 
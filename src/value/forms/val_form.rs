@@ -1,14 +1,20 @@
 use crate::error::{Error, SemanticError, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::syntax::is_value_symbol;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::case_form::CaseForm;
+use crate::value::forms::cast_form::CastForm;
 use crate::value::forms::form::{Form, FormTailElement};
 use crate::value::forms::fun_form::FunForm;
 use crate::value::forms::let_form::LetForm;
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::mem_form::MemForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use crate::value::Type;
 use std::fmt;
@@ -18,11 +24,15 @@ pub enum ValFormValue {
     Empty(SimpleValue),
     Panic(SimpleValue),
     Atomic(SimpleValue),
+    Builtin(SimpleValue),
     ValueSymbol(SimpleValue),
     PairForm(Box<PairForm>),
     FunForm(Box<FunForm>),
     LetForm(Box<LetForm>),
     AppForm(Box<AppForm>),
+    MemForm(Box<MemForm>),
+    LinearForm(Box<LinearForm>),
+    CastForm(Box<CastForm>),
     CaseForm(Box<CaseForm>),
 }
 
@@ -38,11 +48,15 @@ impl ValFormValue {
             ValFormValue::Empty(empty) => empty.file(),
             ValFormValue::Panic(panic) => panic.file(),
             ValFormValue::Atomic(atomic) => atomic.file(),
+            ValFormValue::Builtin(builtin) => builtin.file(),
             ValFormValue::ValueSymbol(symbol) => symbol.file(),
             ValFormValue::PairForm(form) => form.file(),
             ValFormValue::FunForm(form) => form.file(),
             ValFormValue::LetForm(form) => form.file(),
             ValFormValue::AppForm(form) => form.file(),
+            ValFormValue::MemForm(form) => form.file(),
+            ValFormValue::LinearForm(form) => form.file(),
+            ValFormValue::CastForm(form) => form.file(),
             ValFormValue::CaseForm(form) => form.file(),
         }
     }
@@ -52,26 +66,52 @@ impl ValFormValue {
             ValFormValue::Empty(empty) => empty.loc(),
             ValFormValue::Panic(panic) => panic.loc(),
             ValFormValue::Atomic(atomic) => atomic.loc(),
+            ValFormValue::Builtin(builtin) => builtin.loc(),
             ValFormValue::ValueSymbol(symbol) => symbol.loc(),
             ValFormValue::PairForm(form) => form.loc(),
             ValFormValue::FunForm(form) => form.loc(),
             ValFormValue::LetForm(form) => form.loc(),
             ValFormValue::AppForm(form) => form.loc(),
+            ValFormValue::MemForm(form) => form.loc(),
+            ValFormValue::LinearForm(form) => form.loc(),
+            ValFormValue::CastForm(form) => form.loc(),
             ValFormValue::CaseForm(form) => form.loc(),
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ValFormValue::Empty(empty) => empty.span(),
+            ValFormValue::Panic(panic) => panic.span(),
+            ValFormValue::Atomic(atomic) => atomic.span(),
+            ValFormValue::Builtin(builtin) => builtin.span(),
+            ValFormValue::ValueSymbol(symbol) => symbol.span(),
+            ValFormValue::PairForm(form) => form.span(),
+            ValFormValue::FunForm(form) => form.span(),
+            ValFormValue::LetForm(form) => form.span(),
+            ValFormValue::AppForm(form) => form.span(),
+            ValFormValue::MemForm(form) => form.span(),
+            ValFormValue::LinearForm(form) => form.span(),
+            ValFormValue::CastForm(form) => form.span(),
+            ValFormValue::CaseForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
             ValFormValue::Empty(_) => "()".into(),
             ValFormValue::Panic(_) => "panic".into(),
             ValFormValue::Atomic(atomic) => atomic.to_string(),
+            ValFormValue::Builtin(_) => "builtin".into(),
             ValFormValue::ValueSymbol(symbol) => symbol.to_string(),
             ValFormValue::PairForm(form) => form.to_string(),
             ValFormValue::FunForm(form) => form.to_string(),
             ValFormValue::LetForm(form) => form.to_string(),
             ValFormValue::AppForm(form) => form.to_string(),
+            ValFormValue::MemForm(form) => form.to_string(),
+            ValFormValue::LinearForm(form) => form.to_string(),
+            ValFormValue::CastForm(form) => form.to_string(),
             ValFormValue::CaseForm(form) => form.to_string(),
         }
     }
@@ -88,6 +128,7 @@ pub struct ValForm {
     pub tokens: Box<Tokens>,
     pub name: SimpleValue,
     pub value: ValFormValue,
+    pub doc: Option<String>,
 }
 
 impl ValForm {
@@ -103,6 +144,10 @@ impl ValForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn is_empty_literal(&self) -> bool {
         matches!(self.value, ValFormValue::Empty(_))
     }
@@ -115,6 +160,10 @@ impl ValForm {
         matches!(self.value, ValFormValue::Atomic(_))
     }
 
+    pub fn is_builtin(&self) -> bool {
+        matches!(self.value, ValFormValue::Builtin(_))
+    }
+
     pub fn is_value_symbol(&self) -> bool {
         matches!(self.value, ValFormValue::ValueSymbol(_))
     }
@@ -131,6 +180,18 @@ impl ValForm {
         matches!(self.value, ValFormValue::AppForm(_))
     }
 
+    pub fn is_memory_form(&self) -> bool {
+        matches!(self.value, ValFormValue::MemForm(_))
+    }
+
+    pub fn is_linear_form(&self) -> bool {
+        matches!(self.value, ValFormValue::LinearForm(_))
+    }
+
+    pub fn is_cast_form(&self) -> bool {
+        matches!(self.value, ValFormValue::CastForm(_))
+    }
+
     pub fn is_let_form(&self) -> bool {
         matches!(self.value, ValFormValue::LetForm(_))
     }
@@ -142,6 +203,7 @@ impl ValForm {
     pub fn is_value(&self) -> bool {
         self.is_empty_literal()
             || self.is_atomic()
+            || self.is_builtin()
             || self.is_value_symbol()
             || self.is_pair_form()
             || self.is_function_form()
@@ -151,109 +213,31 @@ impl ValForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_val_form(self);
 
-        match self.value.clone() {
-            ValFormValue::PairForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            ValFormValue::FunForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            ValFormValue::LetForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            ValFormValue::AppForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            ValFormValue::CaseForm(form) => {
-                params.extend(form.all_parameters());
-            }
-            _ => {}
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_val_form(self);
 
-        match self.value.clone() {
-            ValFormValue::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            ValFormValue::PairForm(form) => {
-                vars.extend(form.all_value_variables());
-            }
-            ValFormValue::FunForm(form) => {
-                vars.extend(form.all_value_variables());
-            }
-            ValFormValue::LetForm(form) => {
-                vars.extend(form.all_value_variables());
-            }
-            ValFormValue::AppForm(form) => {
-                vars.extend(form.all_value_variables());
-            }
-            ValFormValue::CaseForm(form) => {
-                vars.extend(form.all_value_variables());
-            }
-            _ => {}
-        }
-
-        vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_val_form(self);
 
-        match self.value.clone() {
-            ValFormValue::PairForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            ValFormValue::FunForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            ValFormValue::LetForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            ValFormValue::AppForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            ValFormValue::CaseForm(form) => {
-                type_vars.extend(form.all_type_variables());
-            }
-            _ => {}
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        match self.value.clone() {
-            ValFormValue::ValueSymbol(value) => {
-                vars.push(value);
-            }
-            ValFormValue::PairForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            ValFormValue::FunForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            ValFormValue::LetForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            ValFormValue::AppForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            ValFormValue::CaseForm(form) => {
-                vars.extend(form.all_variables());
-            }
-            _ => {}
-        }
+        let mut collector = Variables::default();
+        collector.visit_val_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn all_bound_variables(&self) -> Vec<SimpleValue> {
@@ -383,6 +367,12 @@ impl ValForm {
                 SimpleValue::Atomic(atomic) => {
                     val.value = ValFormValue::Atomic(SimpleValue::Atomic(atomic));
                 }
+                SimpleValue::ValueKeyword(ref keyword) if value.to_string() == "atomic" => {
+                    val.value = ValFormValue::Atomic(SimpleValue::ValueKeyword(keyword.clone()));
+                }
+                SimpleValue::ValueKeyword(ref keyword) if value.to_string() == "builtin" => {
+                    val.value = ValFormValue::Builtin(SimpleValue::ValueKeyword(keyword.clone()));
+                }
                 SimpleValue::ValueSymbol(symbol) => {
                     val.value = ValFormValue::ValueSymbol(SimpleValue::ValueSymbol(symbol));
                 }
@@ -411,6 +401,18 @@ impl ValForm {
                     let form = CaseForm::from_form(&form)?;
                     val.value = ValFormValue::CaseForm(Box::new(form));
                 }
+                "ref" | "deref" | "load" | "store" | "size" => {
+                    let form = MemForm::from_form(&form)?;
+                    val.value = ValFormValue::MemForm(Box::new(form));
+                }
+                "dup" | "drop" => {
+                    let form = LinearForm::from_form(&form)?;
+                    val.value = ValFormValue::LinearForm(Box::new(form));
+                }
+                "cast" => {
+                    let form = CastForm::from_form(&form)?;
+                    val.value = ValFormValue::CastForm(Box::new(form));
+                }
                 _ => {
                     if let Ok(form) = AppForm::from_form(&form) {
                         val.value = ValFormValue::AppForm(Box::new(form));
@@ -494,6 +496,31 @@ mod tests {
         assert!(form.is_atomic());
         assert!(form.is_value());
 
+        s = "(val add builtin)";
+
+        res = ValForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.name.to_string(), "add".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+        assert!(form.is_builtin());
+        assert!(form.is_value());
+
+        s = "(val true atomic)";
+
+        res = ValForm::from_str(s);
+
+        assert!(res.is_ok());
+
+        form = res.unwrap();
+
+        assert_eq!(form.name.to_string(), "true".to_string());
+        assert_eq!(form.to_string(), s.to_string());
+        assert!(form.is_atomic());
+
         s = "(val w x)";
 
         res = ValForm::from_str(s);
@@ -0,0 +1,207 @@
+use crate::value::forms::attrs_form::AttrsForm;
+use crate::value::forms::block_form::BlockFormEntry;
+use crate::value::forms::case_form::{CaseForm, CaseFormMatch};
+use crate::value::forms::fun_form::FunFormParameter;
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::LetFormEntry;
+use crate::value::forms::module_form::{ModuleForm, ModuleFormBlock};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::ValForm;
+use crate::value::types::SimpleType;
+use crate::value::visit::{
+    walk_block_form_entry, walk_fun_form_parameter, walk_let_form_entry, Visitor,
+};
+use crate::value::{SimpleValue, Type};
+
+// The branches of a case are alternatives, so a variable already collected
+// in the case is not collected again for a later branch.
+fn merge_branch<T: ToString>(vars: &mut Vec<T>, start: usize, mark: usize) {
+    let branch = vars.split_off(mark);
+
+    let new_vars = branch
+        .into_iter()
+        .filter(|bv| {
+            !vars[start..]
+                .iter()
+                .any(|v| v.to_string() == bv.to_string())
+        })
+        .collect::<Vec<T>>();
+
+    vars.extend(new_vars);
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Parameters {
+    in_parameter: bool,
+    pub params: Vec<SimpleValue>,
+}
+
+impl Visitor for Parameters {
+    fn visit_block_form_entry(&mut self, node: &BlockFormEntry) {
+        if let BlockFormEntry::ValForm(form) = node {
+            self.params.push(form.name.clone());
+        }
+
+        walk_block_form_entry(self, node);
+    }
+
+    fn visit_let_form_entry(&mut self, node: &LetFormEntry) {
+        if let LetFormEntry::ValForm(form) = node {
+            self.params.push(form.name.clone());
+        }
+
+        walk_let_form_entry(self, node);
+    }
+
+    fn visit_import_form_def(&mut self, node: &ImportFormDef) {
+        match node {
+            ImportFormDef::Ignore(value)
+            | ImportFormDef::Empty(value)
+            | ImportFormDef::ValueSymbol(value)
+            | ImportFormDef::TypeSymbol(value) => self.params.push(value.clone()),
+        }
+    }
+
+    fn visit_fun_form_parameter(&mut self, node: &FunFormParameter) {
+        self.in_parameter = true;
+        walk_fun_form_parameter(self, node);
+        self.in_parameter = false;
+    }
+
+    fn visit_case_form(&mut self, node: &CaseForm) {
+        let start = self.params.len();
+        self.visit_case_form_variable(&node.variable);
+
+        for branch in node.matches.iter() {
+            let mark = self.params.len();
+            self.visit_case_form_match(branch);
+            merge_branch(&mut self.params, start, mark);
+        }
+    }
+
+    fn visit_simple_value(&mut self, node: &SimpleValue) {
+        if self.in_parameter {
+            match node {
+                SimpleValue::ValueSymbol(_)
+                | SimpleValue::TypeSymbol(_)
+                | SimpleValue::ValuePathSymbol(_)
+                | SimpleValue::TypePathSymbol(_) => self.params.push(node.clone()),
+                _ => {}
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Variables {
+    pub value_vars: Vec<SimpleValue>,
+    pub type_vars: Vec<Type>,
+    pub vars: Vec<SimpleValue>,
+}
+
+impl Visitor for Variables {
+    fn visit_module_form(&mut self, node: &ModuleForm) {
+        if let ModuleFormBlock::Form(form) = &node.block {
+            self.visit_block_form(form);
+        }
+    }
+
+    fn visit_import_form(&mut self, node: &ImportForm) {
+        for item in node.type_variables.iter() {
+            self.visit_type(item);
+        }
+    }
+
+    fn visit_attrs_form(&mut self, node: &AttrsForm) {
+        for item in node.values.iter() {
+            self.visit_attrs_form_value(item);
+        }
+    }
+
+    fn visit_type_form(&mut self, node: &TypeForm) {
+        self.visit_type(&node.value);
+    }
+
+    fn visit_sig_form(&mut self, node: &SigForm) {
+        self.visit_type(&node.value);
+    }
+
+    fn visit_val_form(&mut self, node: &ValForm) {
+        self.visit_val_form_value(&node.value);
+    }
+
+    fn visit_fun_form_parameter(&mut self, _node: &FunFormParameter) {}
+
+    fn visit_case_form(&mut self, node: &CaseForm) {
+        let start = (self.value_vars.len(), self.type_vars.len(), self.vars.len());
+        self.visit_case_form_variable(&node.variable);
+
+        for branch in node.matches.iter() {
+            let mark = (self.value_vars.len(), self.type_vars.len(), self.vars.len());
+            self.visit_case_form_match(branch);
+            merge_branch(&mut self.value_vars, start.0, mark.0);
+            merge_branch(&mut self.type_vars, start.1, mark.1);
+            merge_branch(&mut self.vars, start.2, mark.2);
+        }
+    }
+
+    fn visit_case_form_match(&mut self, node: &CaseFormMatch) {
+        self.visit_case_form_match_action(&node.action);
+    }
+
+    fn visit_simple_type(&mut self, node: &SimpleType) {
+        self.type_vars.extend(node.all_type_variables());
+        self.vars.push(node.as_simple_value());
+    }
+
+    fn visit_simple_value(&mut self, node: &SimpleValue) {
+        match node {
+            SimpleValue::ValueSymbol(_) | SimpleValue::ValuePathSymbol(_) => {
+                self.value_vars.push(node.clone());
+                self.vars.push(node.clone());
+            }
+            SimpleValue::TypeSymbol(_) | SimpleValue::TypePathSymbol(_) => {
+                self.type_vars.push(Type::from_simple_value(node).unwrap());
+                self.vars.push(node.clone());
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn variables_case_branches() {
+        use super::{Parameters, Variables};
+        use crate::value::forms::case_form::CaseForm;
+        use crate::value::visit::Visitor;
+
+        let s = "(case (f x) (match A (fun a (g a y))) (match B (fun b (g b y))) (match C x))";
+        let form = CaseForm::from_str(s).unwrap();
+
+        let mut parameters = Parameters::default();
+        parameters.visit_case_form(&form);
+
+        let params = parameters
+            .params
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(params, vec!["a", "b"]);
+
+        let mut variables = Variables::default();
+        variables.visit_case_form(&form);
+
+        let vars = variables
+            .vars
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(vars, vec!["f", "x", "g", "a", "y", "b"]);
+        assert!(variables.type_vars.is_empty());
+    }
+}
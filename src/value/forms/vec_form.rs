@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::Tokens;
 use crate::value::forms::app_form::AppForm;
 use crate::value::forms::arr_form::ArrForm;
@@ -11,7 +12,9 @@ use crate::value::forms::let_form::LetForm;
 use crate::value::forms::list_form::ListForm;
 use crate::value::forms::map_form::MapForm;
 use crate::value::forms::pair_form::PairForm;
+use crate::value::forms::variables::{Parameters, Variables};
 use crate::value::types::Type;
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
 use std::fmt;
 
@@ -96,6 +99,31 @@ impl VecFormValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            VecFormValue::Ignore(ignore) => ignore.span(),
+            VecFormValue::Empty(empty) => empty.span(),
+            VecFormValue::Panic(panic) => panic.span(),
+            VecFormValue::Atomic(atomic) => atomic.span(),
+            VecFormValue::ValueKeyword(keyword) => keyword.span(),
+            VecFormValue::TypeKeyword(keyword) => keyword.span(),
+            VecFormValue::ValueSymbol(symbol) => symbol.span(),
+            VecFormValue::TypeSymbol(symbol) => symbol.span(),
+            VecFormValue::ValuePathSymbol(symbol) => symbol.span(),
+            VecFormValue::TypePathSymbol(symbol) => symbol.span(),
+            VecFormValue::Type(form) => form.span(),
+            VecFormValue::FunForm(form) => form.span(),
+            VecFormValue::CaseForm(form) => form.span(),
+            VecFormValue::LetForm(form) => form.span(),
+            VecFormValue::AppForm(form) => form.span(),
+            VecFormValue::PairForm(form) => form.span(),
+            VecFormValue::ListForm(form) => form.span(),
+            VecFormValue::ArrForm(form) => form.span(),
+            VecFormValue::MapForm(form) => form.span(),
+            VecFormValue::VecForm(form) => form.span(),
+        }
+    }
+
     #[allow(clippy::inherent_to_string_shadow_display)]
     pub fn to_string(&self) -> String {
         match self {
@@ -148,6 +176,10 @@ impl VecForm {
         self.tokens[0].loc()
     }
 
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
+
     pub fn len(&self) -> usize {
         self.values.len()
     }
@@ -171,31 +203,11 @@ impl VecForm {
                 | VecFormValue::CaseForm(_)
                 | VecFormValue::LetForm(_)
                 | VecFormValue::AppForm(_) => return false,
-                VecFormValue::PairForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                VecFormValue::ListForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                VecFormValue::ArrForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                VecFormValue::MapForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
-                VecFormValue::VecForm(form) => {
-                    if !form.can_be_parameter() {
-                        return false;
-                    }
-                }
+                VecFormValue::PairForm(form) if !form.can_be_parameter() => return false,
+                VecFormValue::ListForm(form) if !form.can_be_parameter() => return false,
+                VecFormValue::ArrForm(form) if !form.can_be_parameter() => return false,
+                VecFormValue::MapForm(form) if !form.can_be_parameter() => return false,
+                VecFormValue::VecForm(form) if !form.can_be_parameter() => return false,
                 _ => {}
             }
         }
@@ -212,189 +224,31 @@ impl VecForm {
     }
 
     pub fn all_parameters(&self) -> Vec<SimpleValue> {
-        let mut params = vec![];
+        let mut collector = Parameters::default();
+        collector.visit_vec_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                VecFormValue::FunForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::CaseForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::LetForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::AppForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::PairForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::ListForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::ArrForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::MapForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                VecFormValue::VecForm(form) => {
-                    params.extend(form.all_parameters());
-                }
-                _ => {}
-            }
-        }
-
-        params
+        collector.params
     }
 
     pub fn all_value_variables(&self) -> Vec<SimpleValue> {
-        let mut value_vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                VecFormValue::ValueSymbol(value) => {
-                    value_vars.push(value);
-                }
-                VecFormValue::ValuePathSymbol(value) => {
-                    value_vars.push(value);
-                }
-                VecFormValue::FunForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::CaseForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::LetForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::AppForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::PairForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::ListForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::ArrForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::MapForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                VecFormValue::VecForm(form) => {
-                    value_vars.extend(form.all_value_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_vec_form(self);
 
-        value_vars
+        collector.value_vars
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+        let mut collector = Variables::default();
+        collector.visit_vec_form(self);
 
-        for value in self.values.iter() {
-            match value.clone() {
-                VecFormValue::TypeSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                VecFormValue::TypePathSymbol(value) => {
-                    type_vars.push(Type::from_simple_value(&value).unwrap());
-                }
-                VecFormValue::Type(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::FunForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::CaseForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::LetForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::AppForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::PairForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::ListForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::ArrForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::MapForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                VecFormValue::VecForm(form) => {
-                    type_vars.extend(form.all_type_variables());
-                }
-                _ => {}
-            }
-        }
-
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
-        let mut vars = vec![];
-
-        for value in self.values.iter() {
-            match value.clone() {
-                VecFormValue::ValueSymbol(value) => {
-                    vars.push(value);
-                }
-                VecFormValue::TypeSymbol(value) => {
-                    vars.push(value);
-                }
-                VecFormValue::ValuePathSymbol(value) => {
-                    vars.push(value);
-                }
-                VecFormValue::TypePathSymbol(value) => {
-                    vars.push(value);
-                }
-                VecFormValue::Type(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::FunForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::CaseForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::LetForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::AppForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::PairForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::ListForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::ArrForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::MapForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                VecFormValue::VecForm(form) => {
-                    vars.extend(form.all_variables());
-                }
-                _ => {}
-            }
-        }
+        let mut collector = Variables::default();
+        collector.visit_vec_form(self);
 
-        vars
+        collector.vars
     }
 
     pub fn from_form(form: &Form) -> Result<VecForm> {
@@ -1,12 +1,17 @@
+pub mod fold;
 pub mod form_value;
 pub mod forms;
+pub mod object;
 pub mod simple_value;
 pub mod types;
 #[allow(clippy::module_inception)]
 pub mod value;
+pub mod visit;
+pub mod visit_mut;
 
 pub use form_value::*;
 pub use forms::*;
+pub use object::*;
 pub use simple_value::*;
 pub use types::*;
 pub use value::*;
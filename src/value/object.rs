@@ -0,0 +1,279 @@
+use crate::error::{Error, SemanticError, SyntacticError};
+use crate::result::Result;
+use crate::token::{Token, TokenKind};
+use crate::value::forms::fun_form::FunForm;
+use crate::value::{SimpleValue, Value};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Scope {
+    pub values: BTreeMap<String, Object>,
+    pub parent: Option<Rc<Scope>>,
+}
+
+impl Scope {
+    pub fn new() -> Scope {
+        Scope::default()
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<Object> {
+        match self.values.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.parent.as_ref().and_then(|parent| parent.lookup(name)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Closure {
+    pub ctx: usize,
+    pub form: FunForm,
+    pub env: Rc<Scope>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub enum Object {
+    #[default]
+    Empty,
+    UInt(u64),
+    Int(i64),
+    Float(f64),
+    Size(u64),
+    Pointer(u64),
+    Char(char),
+    String(String),
+    Atomic(String),
+    Tag(String),
+    Pair(Box<Object>, Box<Object>),
+    List(Vec<Object>),
+    Arr(Vec<Object>),
+    Vec(Vec<Object>),
+    Map(Vec<(Object, Object)>),
+    Fun(Rc<Closure>),
+    Builtin(String),
+    Ref(Rc<RefCell<Object>>),
+    IO,
+    Ctx,
+}
+
+impl Object {
+    pub fn new() -> Object {
+        Object::default()
+    }
+
+    pub fn new_ref(value: Object) -> Object {
+        Object::Ref(Rc::new(RefCell::new(value)))
+    }
+
+    pub fn is_data(&self) -> bool {
+        match self {
+            Object::Pointer(_)
+            | Object::Fun(_)
+            | Object::Builtin(_)
+            | Object::Ref(_)
+            | Object::IO
+            | Object::Ctx => false,
+            Object::Pair(first, second) => first.is_data() && second.is_data(),
+            Object::List(values) | Object::Arr(values) | Object::Vec(values) => {
+                values.iter().all(|value| value.is_data())
+            }
+            Object::Map(entries) => entries
+                .iter()
+                .all(|(key, value)| key.is_data() && value.is_data()),
+            _ => true,
+        }
+    }
+
+    pub fn from_token(token: &Token) -> Result<Object> {
+        let s = token.to_string();
+
+        let object = match token.kind {
+            TokenKind::EmptyLiteral => Some(Object::Empty),
+            TokenKind::UIntLiteral => token.to_uint().map(Object::UInt),
+            TokenKind::IntLiteral => token.to_int().map(Object::Int),
+            TokenKind::FloatLiteral => token.to_float().map(Object::Float),
+            TokenKind::CharLiteral => token.to_char().map(Object::Char),
+            TokenKind::StringLiteral => token.to_unescaped().map(Object::String),
+            _ => {
+                return Err(Error::Syntactic(SyntacticError {
+                    loc: token.loc(),
+                    desc: format!("expected a literal, found {}", s),
+                }));
+            }
+        };
+
+        object.ok_or_else(|| {
+            Error::Syntactic(SyntacticError {
+                loc: token.loc(),
+                desc: format!("invalid literal {}", s),
+            })
+        })
+    }
+
+    pub fn from_simple_value(value: &SimpleValue) -> Result<Object> {
+        Object::from_token(&value.token())
+    }
+
+    pub fn to_value(&self) -> Result<Value> {
+        if !self.is_data() {
+            return Err(Error::Semantic(SemanticError {
+                loc: None,
+                desc: format!("{} has no literal representation", self),
+            }));
+        }
+
+        Value::from_str(&self.to_string())
+    }
+
+    pub fn to_output(&self) -> String {
+        match self {
+            Object::String(s) => s.clone(),
+            Object::Char(c) => c.to_string(),
+            _ => self.to_string(),
+        }
+    }
+
+    #[allow(clippy::inherent_to_string_shadow_display)]
+    pub fn to_string(&self) -> String {
+        match self {
+            Object::Empty => "()".into(),
+            Object::UInt(n) => n.to_string(),
+            Object::Int(n) => format!("{:+}", n),
+            Object::Float(n) if n.is_finite() && n.fract() == 0.0 => format!("{:.1}", n),
+            Object::Float(n) => n.to_string(),
+            Object::Size(n) => n.to_string(),
+            Object::Pointer(n) => format!("{:#x}", n),
+            Object::Char(c) => format!("'{}'", escape(&c.to_string(), '\'')),
+            Object::String(s) => format!("\"{}\"", escape(s, '"')),
+            Object::Atomic(name) => name.clone(),
+            Object::Tag(name) => name.clone(),
+            Object::Pair(first, second) => format!("(pair {} {})", first, second),
+            Object::List(values) => format!("(list {})", objects_to_string(values)),
+            Object::Arr(values) => format!("(arr {})", objects_to_string(values)),
+            Object::Vec(values) => format!("(vec {})", objects_to_string(values)),
+            Object::Map(entries) => format!(
+                "(map {})",
+                entries
+                    .iter()
+                    .map(|(key, value)| format!("(pair {} {})", key, value))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            Object::Fun(closure) => closure.form.to_string(),
+            Object::Builtin(name) => name.clone(),
+            Object::Ref(value) => format!("(ref {})", value.borrow()),
+            Object::IO => "io".into(),
+            Object::Ctx => "ctx".into(),
+        }
+    }
+}
+
+impl fmt::Display for Object {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+fn objects_to_string(values: &[Object]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn escape(s: &str, quote: char) -> String {
+    let mut res = String::new();
+
+    for c in s.chars() {
+        match c {
+            '\n' => res.push_str("\\n"),
+            '\t' => res.push_str("\\t"),
+            '\r' => res.push_str("\\r"),
+            '\0' => res.push_str("\\0"),
+            '\\' => res.push_str("\\\\"),
+            c if c == quote => {
+                res.push('\\');
+                res.push(c);
+            }
+            c => res.push(c),
+        }
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn object_from_token() {
+        use super::Object;
+        use crate::token::Tokens;
+
+        let cases = [
+            ("()", Object::Empty),
+            ("42", Object::UInt(42)),
+            ("b101", Object::UInt(5)),
+            ("o17", Object::UInt(15)),
+            ("xff", Object::UInt(255)),
+            ("-12", Object::Int(-12)),
+            ("+7", Object::Int(7)),
+            ("-3.5", Object::Float(-3.5)),
+            ("'a'", Object::Char('a')),
+            ("'\\u{e9}'", Object::Char('\u{e9}')),
+            ("\"a\\tb\"", Object::String("a\tb".into())),
+        ];
+
+        for (s, expected) in cases.iter() {
+            let tokens = Tokens::from_str(s).unwrap();
+            let res = Object::from_token(&tokens[0]);
+
+            assert!(res.is_ok());
+            assert_eq!(res.unwrap(), *expected);
+        }
+
+        let tokens = Tokens::from_str("x").unwrap();
+        let res = Object::from_token(&tokens[0]);
+
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn object_to_string() {
+        use super::Object;
+
+        let mut object = Object::Pair(
+            Box::new(Object::Int(-1)),
+            Box::new(Object::List(vec![
+                Object::Float(2.0),
+                Object::Char('\''),
+                Object::String("a\nb".into()),
+            ])),
+        );
+
+        assert_eq!(
+            object.to_string(),
+            "(pair -1 (list 2.0 '\\'' \"a\\nb\"))".to_string()
+        );
+        assert_eq!(object.to_output(), object.to_string());
+        assert!(object.is_data());
+
+        object = Object::Map(vec![(Object::UInt(1), Object::Empty)]);
+
+        assert_eq!(object.to_string(), "(map (pair 1 ()))".to_string());
+        assert_eq!(
+            object.to_value().unwrap().to_string(),
+            "(map (pair 1 ()))".to_string()
+        );
+
+        object = Object::new_ref(Object::String("x".into()));
+
+        assert_eq!(object.to_string(), "(ref \"x\")".to_string());
+        assert_eq!(Object::String("x".into()).to_output(), "x".to_string());
+        assert!(!object.is_data());
+        assert!(object.to_value().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::syntax::is_value_keyword;
 use crate::token::{Token, TokenKind, Tokens};
 use std::fmt;
@@ -75,6 +76,21 @@ impl SimpleValue {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            SimpleValue::Ignore(token) => token.span(),
+            SimpleValue::Empty(token) => token.span(),
+            SimpleValue::Panic(token) => token.span(),
+            SimpleValue::ValueKeyword(token) => token.span(),
+            SimpleValue::TypeKeyword(token) => token.span(),
+            SimpleValue::Atomic(token) => token.span(),
+            SimpleValue::ValueSymbol(token) => token.span(),
+            SimpleValue::TypeSymbol(token) => token.span(),
+            SimpleValue::ValuePathSymbol(token) => token.span(),
+            SimpleValue::TypePathSymbol(token) => token.span(),
+        }
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<SimpleValue> {
         let tokens = Tokens::from_str(s)?;
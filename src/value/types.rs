@@ -2,9 +2,14 @@ use crate::chunk::StringChunks;
 use crate::error::{Error, SyntacticError};
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::{Token, TokenKind, Tokens};
+use crate::value::fold::{fold_type, Fold};
+use crate::value::forms::case_form::CaseFormMatchCase;
 use crate::value::forms::{Form, FormTailElement};
+use crate::value::visit::Visitor;
 use crate::value::SimpleValue;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::iter;
 use std::ops;
@@ -21,6 +26,77 @@ fn parse_form_tail_element(elem: &FormTailElement) -> Result<Type> {
     Ok(elem_type)
 }
 
+#[derive(Debug, Default)]
+struct TypeVariables {
+    type_vars: Vec<Type>,
+}
+
+impl Visitor for TypeVariables {
+    fn visit_simple_type(&mut self, node: &SimpleType) {
+        self.type_vars.extend(node.all_type_variables());
+    }
+}
+
+pub struct Substitution<'a> {
+    pub substitutions: &'a BTreeMap<String, Type>,
+}
+
+impl<'a> Fold for Substitution<'a> {
+    fn fold_type(&mut self, node: Type) -> Type {
+        match node {
+            Type::Simple(SimpleType::Symbol(ref value)) => {
+                let name = value.to_string();
+                self.substitutions.get(&name).cloned().unwrap_or(node)
+            }
+            node => fold_type(self, node),
+        }
+    }
+
+    fn fold_case_form_match_case(&mut self, node: CaseFormMatchCase) -> CaseFormMatchCase {
+        let substitution = match node {
+            CaseFormMatchCase::TypeSymbol(ref value) => self.substitutions.get(&value.to_string()),
+            _ => None,
+        };
+
+        match substitution.and_then(|t| t.as_simple_value()) {
+            Some(value @ SimpleValue::TypeKeyword(_)) => CaseFormMatchCase::TypeKeyword(value),
+            Some(value @ SimpleValue::TypeSymbol(_)) => CaseFormMatchCase::TypeSymbol(value),
+            Some(value @ SimpleValue::TypePathSymbol(_)) => {
+                CaseFormMatchCase::TypePathSymbol(value)
+            }
+            _ => node,
+        }
+    }
+}
+
+struct Resolution<'a> {
+    aliases: &'a BTreeMap<String, Type>,
+    expanding: Vec<String>,
+}
+
+impl<'a> Fold for Resolution<'a> {
+    fn fold_type(&mut self, node: Type) -> Type {
+        match node {
+            Type::Simple(SimpleType::Symbol(ref value))
+            | Type::Simple(SimpleType::PathSymbol(ref value)) => {
+                let name = value.to_string();
+
+                match self.aliases.get(&name) {
+                    Some(alias) if !self.expanding.contains(&name) => {
+                        self.expanding.push(name);
+                        let resolved = self.fold_type(alias.clone());
+                        self.expanding.pop();
+
+                        resolved
+                    }
+                    _ => node,
+                }
+            }
+            node => fold_type(self, node),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
 pub enum SimpleType {
     Builtin(SimpleValue),
@@ -132,6 +208,30 @@ impl SimpleType {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            SimpleType::Builtin(value) => value.span(),
+            SimpleType::Ignore(value) => value.span(),
+            SimpleType::Empty(value) => value.span(),
+            SimpleType::Atomic(value) => value.span(),
+            SimpleType::UInt(value) => value.span(),
+            SimpleType::Int(value) => value.span(),
+            SimpleType::Float(value) => value.span(),
+            SimpleType::Size(value) => value.span(),
+            SimpleType::Pointer(value) => value.span(),
+            SimpleType::Ref(value) => value.span(),
+            SimpleType::Char(value) => value.span(),
+            SimpleType::String(value) => value.span(),
+            SimpleType::Mem(value) => value.span(),
+            SimpleType::Path(value) => value.span(),
+            SimpleType::IO(value) => value.span(),
+            SimpleType::Ctx(value) => value.span(),
+            SimpleType::Type(value) => value.span(),
+            SimpleType::Symbol(value) => value.span(),
+            SimpleType::PathSymbol(value) => value.span(),
+        }
+    }
+
     pub fn as_simple_value(&self) -> SimpleValue {
         match self {
             SimpleType::Builtin(value) => value.clone(),
@@ -275,14 +375,15 @@ impl EnumType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        for elem in self.elements.iter() {
-            type_vars.extend(elem.all_type_variables());
-        }
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_enum_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -292,6 +393,10 @@ impl EnumType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> EnumType {
+        Substitution { substitutions }.fold_enum_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<EnumType> {
         let form = Form::from_str(s)?;
@@ -388,13 +493,15 @@ impl PairType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        type_vars.extend(self.first.all_type_variables());
-        type_vars.extend(self.second.all_type_variables());
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_pair_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -404,6 +511,10 @@ impl PairType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> PairType {
+        Substitution { substitutions }.fold_pair_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<PairType> {
         let form = Form::from_str(s)?;
@@ -477,14 +588,15 @@ impl ListType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        for elem in self.elements.iter() {
-            type_vars.extend(elem.all_type_variables());
-        }
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_list_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -494,6 +606,10 @@ impl ListType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> ListType {
+        Substitution { substitutions }.fold_list_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<ListType> {
         let form = Form::from_str(s)?;
@@ -589,14 +705,15 @@ impl ArrType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        for elem in self.elements.iter() {
-            type_vars.extend(elem.all_type_variables());
-        }
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_arr_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -606,6 +723,10 @@ impl ArrType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> ArrType {
+        Substitution { substitutions }.fold_arr_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<ArrType> {
         let form = Form::from_str(s)?;
@@ -701,14 +822,15 @@ impl VecType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        for elem in self.elements.iter() {
-            type_vars.extend(elem.all_type_variables());
-        }
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_vec_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -718,6 +840,10 @@ impl VecType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> VecType {
+        Substitution { substitutions }.fold_vec_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<VecType> {
         let form = Form::from_str(s)?;
@@ -813,14 +939,15 @@ impl MapType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        for entry in self.entries.iter() {
-            type_vars.extend(entry.all_type_variables());
-        }
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_map_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -830,6 +957,10 @@ impl MapType {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> MapType {
+        Substitution { substitutions }.fold_map_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<MapType> {
         let form = Form::from_str(s)?;
@@ -926,16 +1057,15 @@ impl FunType {
         self.tokens[0].loc()
     }
 
-    pub fn all_type_variables(&self) -> Vec<Type> {
-        let mut type_vars = vec![];
-
-        for param in self.parameters.iter() {
-            type_vars.extend(param.all_type_variables());
-        }
+    pub fn span(&self) -> Option<Span> {
+        self.tokens.span()
+    }
 
-        type_vars.extend(self.body.all_type_variables());
+    pub fn all_type_variables(&self) -> Vec<Type> {
+        let mut collector = TypeVariables::default();
+        collector.visit_fun_type(self);
 
-        type_vars
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -953,6 +1083,10 @@ impl FunType {
             .join(" ")
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> FunType {
+        Substitution { substitutions }.fold_fun_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<FunType> {
         let form = Form::from_str(s)?;
@@ -1060,6 +1194,19 @@ impl Type {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Type::Simple(simple_type) => simple_type.span(),
+            Type::Enum(enum_type) => enum_type.span(),
+            Type::Pair(pair_type) => pair_type.span(),
+            Type::List(list_type) => list_type.span(),
+            Type::Arr(arr_type) => arr_type.span(),
+            Type::Vec(vec_type) => vec_type.span(),
+            Type::Map(map_type) => map_type.span(),
+            Type::Fun(fun_type) => fun_type.span(),
+        }
+    }
+
     pub fn as_simple_value(&self) -> Option<SimpleValue> {
         match self {
             Type::Simple(simple_type) => Some(simple_type.as_simple_value()),
@@ -1068,16 +1215,10 @@ impl Type {
     }
 
     pub fn all_type_variables(&self) -> Vec<Type> {
-        match self {
-            Type::Simple(simple_type) => simple_type.all_type_variables(),
-            Type::Enum(enum_type) => enum_type.all_type_variables(),
-            Type::Pair(pair_type) => pair_type.all_type_variables(),
-            Type::List(list_type) => list_type.all_type_variables(),
-            Type::Arr(arr_type) => arr_type.all_type_variables(),
-            Type::Vec(vec_type) => vec_type.all_type_variables(),
-            Type::Map(map_type) => map_type.all_type_variables(),
-            Type::Fun(fun_type) => fun_type.all_type_variables(),
-        }
+        let mut collector = TypeVariables::default();
+        collector.visit_type(self);
+
+        collector.type_vars
     }
 
     pub fn all_variables(&self) -> Vec<SimpleValue> {
@@ -1087,6 +1228,18 @@ impl Type {
             .collect::<Vec<SimpleValue>>()
     }
 
+    pub fn substitute(&self, substitutions: &BTreeMap<String, Type>) -> Type {
+        Substitution { substitutions }.fold_type(self.clone())
+    }
+
+    pub fn resolve(&self, aliases: &BTreeMap<String, Type>) -> Type {
+        Resolution {
+            aliases,
+            expanding: vec![],
+        }
+        .fold_type(self.clone())
+    }
+
     #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Result<Type> {
         let form = Form::from_str(s)?;
@@ -1,6 +1,7 @@
 use crate::error::Error;
 use crate::loc::Loc;
 use crate::result::Result;
+use crate::span::Span;
 use crate::token::{Token, Tokens};
 use crate::value::forms::Form;
 use crate::value::{FormValue, SimpleValue};
@@ -37,6 +38,13 @@ impl Value {
         }
     }
 
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Value::Simple(value) => value.span(),
+            Value::Form(form) => form.span(),
+        }
+    }
+
     pub fn is_simple(&self) -> bool {
         matches!(self, Value::Simple(_))
     }
@@ -0,0 +1,851 @@
+use crate::program::Program;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::attrs_form::{AttrsForm, AttrsFormValue};
+use crate::value::forms::block_form::{BlockForm, BlockFormEntry};
+use crate::value::forms::case_form::{
+    CaseForm, CaseFormMatch, CaseFormMatchAction, CaseFormMatchCase, CaseFormVariable,
+};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::error_form::ErrorForm;
+use crate::value::forms::export_form::{ExportForm, ExportFormDef};
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::MemForm;
+use crate::value::forms::module_form::{ModuleForm, ModuleFormBlock};
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::simple_value::SimpleValue;
+use crate::value::types::{
+    ArrType, EnumType, FunType, ListType, MapType, PairType, SimpleType, Type, VecType,
+};
+
+pub trait Visitor {
+    fn visit_program(&mut self, node: &Program) {
+        walk_program(self, node);
+    }
+
+    fn visit_module_form(&mut self, node: &ModuleForm) {
+        walk_module_form(self, node);
+    }
+
+    fn visit_module_form_block(&mut self, node: &ModuleFormBlock) {
+        walk_module_form_block(self, node);
+    }
+
+    fn visit_block_form(&mut self, node: &BlockForm) {
+        walk_block_form(self, node);
+    }
+
+    fn visit_block_form_entry(&mut self, node: &BlockFormEntry) {
+        walk_block_form_entry(self, node);
+    }
+
+    fn visit_import_form(&mut self, node: &ImportForm) {
+        walk_import_form(self, node);
+    }
+
+    fn visit_import_form_def(&mut self, node: &ImportFormDef) {
+        walk_import_form_def(self, node);
+    }
+
+    fn visit_export_form(&mut self, node: &ExportForm) {
+        walk_export_form(self, node);
+    }
+
+    fn visit_export_form_def(&mut self, node: &ExportFormDef) {
+        walk_export_form_def(self, node);
+    }
+
+    fn visit_attrs_form(&mut self, node: &AttrsForm) {
+        walk_attrs_form(self, node);
+    }
+
+    fn visit_attrs_form_value(&mut self, node: &AttrsFormValue) {
+        walk_attrs_form_value(self, node);
+    }
+
+    fn visit_type_form(&mut self, node: &TypeForm) {
+        walk_type_form(self, node);
+    }
+
+    fn visit_sig_form(&mut self, node: &SigForm) {
+        walk_sig_form(self, node);
+    }
+
+    fn visit_val_form(&mut self, node: &ValForm) {
+        walk_val_form(self, node);
+    }
+
+    fn visit_val_form_value(&mut self, node: &ValFormValue) {
+        walk_val_form_value(self, node);
+    }
+
+    fn visit_error_form(&mut self, node: &ErrorForm) {
+        walk_error_form(self, node);
+    }
+
+    fn visit_fun_form(&mut self, node: &FunForm) {
+        walk_fun_form(self, node);
+    }
+
+    fn visit_fun_form_parameter(&mut self, node: &FunFormParameter) {
+        walk_fun_form_parameter(self, node);
+    }
+
+    fn visit_fun_form_body(&mut self, node: &FunFormBody) {
+        walk_fun_form_body(self, node);
+    }
+
+    fn visit_let_form(&mut self, node: &LetForm) {
+        walk_let_form(self, node);
+    }
+
+    fn visit_let_form_entry(&mut self, node: &LetFormEntry) {
+        walk_let_form_entry(self, node);
+    }
+
+    fn visit_app_form(&mut self, node: &AppForm) {
+        walk_app_form(self, node);
+    }
+
+    fn visit_app_form_value(&mut self, node: &AppFormValue) {
+        walk_app_form_value(self, node);
+    }
+
+    fn visit_case_form(&mut self, node: &CaseForm) {
+        walk_case_form(self, node);
+    }
+
+    fn visit_case_form_variable(&mut self, node: &CaseFormVariable) {
+        walk_case_form_variable(self, node);
+    }
+
+    fn visit_case_form_match(&mut self, node: &CaseFormMatch) {
+        walk_case_form_match(self, node);
+    }
+
+    fn visit_case_form_match_case(&mut self, node: &CaseFormMatchCase) {
+        walk_case_form_match_case(self, node);
+    }
+
+    fn visit_case_form_match_action(&mut self, node: &CaseFormMatchAction) {
+        walk_case_form_match_action(self, node);
+    }
+
+    fn visit_pair_form(&mut self, node: &PairForm) {
+        walk_pair_form(self, node);
+    }
+
+    fn visit_pair_form_value(&mut self, node: &PairFormValue) {
+        walk_pair_form_value(self, node);
+    }
+
+    fn visit_list_form(&mut self, node: &ListForm) {
+        walk_list_form(self, node);
+    }
+
+    fn visit_list_form_value(&mut self, node: &ListFormValue) {
+        walk_list_form_value(self, node);
+    }
+
+    fn visit_arr_form(&mut self, node: &ArrForm) {
+        walk_arr_form(self, node);
+    }
+
+    fn visit_arr_form_value(&mut self, node: &ArrFormValue) {
+        walk_arr_form_value(self, node);
+    }
+
+    fn visit_vec_form(&mut self, node: &VecForm) {
+        walk_vec_form(self, node);
+    }
+
+    fn visit_vec_form_value(&mut self, node: &VecFormValue) {
+        walk_vec_form_value(self, node);
+    }
+
+    fn visit_map_form(&mut self, node: &MapForm) {
+        walk_map_form(self, node);
+    }
+
+    fn visit_map_form_entry(&mut self, node: &MapFormEntry) {
+        walk_map_form_entry(self, node);
+    }
+
+    fn visit_mem_form(&mut self, node: &MemForm) {
+        walk_mem_form(self, node);
+    }
+
+    fn visit_linear_form(&mut self, node: &LinearForm) {
+        walk_linear_form(self, node);
+    }
+
+    fn visit_cast_form(&mut self, node: &CastForm) {
+        walk_cast_form(self, node);
+    }
+
+    fn visit_type(&mut self, node: &Type) {
+        walk_type(self, node);
+    }
+
+    fn visit_simple_type(&mut self, node: &SimpleType) {
+        walk_simple_type(self, node);
+    }
+
+    fn visit_enum_type(&mut self, node: &EnumType) {
+        walk_enum_type(self, node);
+    }
+
+    fn visit_pair_type(&mut self, node: &PairType) {
+        walk_pair_type(self, node);
+    }
+
+    fn visit_list_type(&mut self, node: &ListType) {
+        walk_list_type(self, node);
+    }
+
+    fn visit_arr_type(&mut self, node: &ArrType) {
+        walk_arr_type(self, node);
+    }
+
+    fn visit_vec_type(&mut self, node: &VecType) {
+        walk_vec_type(self, node);
+    }
+
+    fn visit_map_type(&mut self, node: &MapType) {
+        walk_map_type(self, node);
+    }
+
+    fn visit_fun_type(&mut self, node: &FunType) {
+        walk_fun_type(self, node);
+    }
+
+    fn visit_simple_value(&mut self, _node: &SimpleValue) {}
+}
+
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, node: &Program) {
+    for item in node.modules.iter() {
+        visitor.visit_module_form(item);
+    }
+}
+
+pub fn walk_module_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ModuleForm) {
+    visitor.visit_simple_value(&node.name);
+
+    for item in node.type_parameters.iter() {
+        visitor.visit_type(item);
+    }
+
+    visitor.visit_module_form_block(&node.block);
+}
+
+pub fn walk_module_form_block<V: Visitor + ?Sized>(visitor: &mut V, node: &ModuleFormBlock) {
+    match node {
+        ModuleFormBlock::Empty(value) => visitor.visit_simple_value(value),
+        ModuleFormBlock::Form(value) => visitor.visit_block_form(value),
+    }
+}
+
+pub fn walk_block_form<V: Visitor + ?Sized>(visitor: &mut V, node: &BlockForm) {
+    for item in node.entries.iter() {
+        visitor.visit_block_form_entry(item);
+    }
+}
+
+pub fn walk_block_form_entry<V: Visitor + ?Sized>(visitor: &mut V, node: &BlockFormEntry) {
+    match node {
+        BlockFormEntry::Empty(value) => visitor.visit_simple_value(value),
+        BlockFormEntry::ImportForm(value) => visitor.visit_import_form(value),
+        BlockFormEntry::ExportForm(value) => visitor.visit_export_form(value),
+        BlockFormEntry::AttrsForm(value) => visitor.visit_attrs_form(value),
+        BlockFormEntry::TypeForm(value) => visitor.visit_type_form(value),
+        BlockFormEntry::SigForm(value) => visitor.visit_sig_form(value),
+        BlockFormEntry::ValForm(value) => visitor.visit_val_form(value),
+        BlockFormEntry::ErrorForm(value) => visitor.visit_error_form(value),
+    }
+}
+
+pub fn walk_import_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ImportForm) {
+    visitor.visit_simple_value(&node.module);
+
+    if let Some(item) = &node.qualifier {
+        visitor.visit_simple_value(item);
+    }
+
+    for item in node.type_variables.iter() {
+        visitor.visit_type(item);
+    }
+
+    for item in node.defs.iter() {
+        visitor.visit_import_form_def(item);
+    }
+}
+
+pub fn walk_import_form_def<V: Visitor + ?Sized>(visitor: &mut V, node: &ImportFormDef) {
+    match node {
+        ImportFormDef::Ignore(value)
+        | ImportFormDef::Empty(value)
+        | ImportFormDef::ValueSymbol(value)
+        | ImportFormDef::TypeSymbol(value) => visitor.visit_simple_value(value),
+    }
+}
+
+pub fn walk_export_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ExportForm) {
+    for item in node.defs.iter() {
+        visitor.visit_export_form_def(item);
+    }
+}
+
+pub fn walk_export_form_def<V: Visitor + ?Sized>(visitor: &mut V, node: &ExportFormDef) {
+    match node {
+        ExportFormDef::Empty(value)
+        | ExportFormDef::ValueSymbol(value)
+        | ExportFormDef::TypeSymbol(value) => visitor.visit_simple_value(value),
+    }
+}
+
+pub fn walk_attrs_form<V: Visitor + ?Sized>(visitor: &mut V, node: &AttrsForm) {
+    visitor.visit_simple_value(&node.name);
+
+    for item in node.values.iter() {
+        visitor.visit_attrs_form_value(item);
+    }
+}
+
+pub fn walk_attrs_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &AttrsFormValue) {
+    match node {
+        AttrsFormValue::Empty(value)
+        | AttrsFormValue::Panic(value)
+        | AttrsFormValue::Atomic(value)
+        | AttrsFormValue::ValueSymbol(value)
+        | AttrsFormValue::TypeSymbol(value)
+        | AttrsFormValue::ValuePathSymbol(value)
+        | AttrsFormValue::TypePathSymbol(value) => visitor.visit_simple_value(value),
+        AttrsFormValue::Map(value) => visitor.visit_map_form(value),
+    }
+}
+
+pub fn walk_type_form<V: Visitor + ?Sized>(visitor: &mut V, node: &TypeForm) {
+    visitor.visit_simple_value(&node.name);
+    visitor.visit_type(&node.value);
+}
+
+pub fn walk_sig_form<V: Visitor + ?Sized>(visitor: &mut V, node: &SigForm) {
+    visitor.visit_simple_value(&node.name);
+    visitor.visit_type(&node.value);
+}
+
+pub fn walk_val_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ValForm) {
+    visitor.visit_simple_value(&node.name);
+    visitor.visit_val_form_value(&node.value);
+}
+
+pub fn walk_val_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &ValFormValue) {
+    match node {
+        ValFormValue::Empty(value)
+        | ValFormValue::Panic(value)
+        | ValFormValue::Atomic(value)
+        | ValFormValue::Builtin(value)
+        | ValFormValue::ValueSymbol(value) => visitor.visit_simple_value(value),
+        ValFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        ValFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        ValFormValue::LetForm(value) => visitor.visit_let_form(value),
+        ValFormValue::AppForm(value) => visitor.visit_app_form(value),
+        ValFormValue::MemForm(value) => visitor.visit_mem_form(value),
+        ValFormValue::LinearForm(value) => visitor.visit_linear_form(value),
+        ValFormValue::CastForm(value) => visitor.visit_cast_form(value),
+        ValFormValue::CaseForm(value) => visitor.visit_case_form(value),
+    }
+}
+
+pub fn walk_error_form<V: Visitor + ?Sized>(_visitor: &mut V, _node: &ErrorForm) {}
+
+pub fn walk_fun_form<V: Visitor + ?Sized>(visitor: &mut V, node: &FunForm) {
+    for item in node.parameters.iter() {
+        visitor.visit_fun_form_parameter(item);
+    }
+
+    visitor.visit_fun_form_body(&node.body);
+}
+
+pub fn walk_fun_form_parameter<V: Visitor + ?Sized>(visitor: &mut V, node: &FunFormParameter) {
+    match node {
+        FunFormParameter::Empty(value) | FunFormParameter::ValueSymbol(value) => {
+            visitor.visit_simple_value(value)
+        }
+        FunFormParameter::MapForm(value) => visitor.visit_map_form(value),
+        FunFormParameter::VecForm(value) => visitor.visit_vec_form(value),
+        FunFormParameter::ArrForm(value) => visitor.visit_arr_form(value),
+        FunFormParameter::ListForm(value) => visitor.visit_list_form(value),
+        FunFormParameter::PairForm(value) => visitor.visit_pair_form(value),
+    }
+}
+
+pub fn walk_fun_form_body<V: Visitor + ?Sized>(visitor: &mut V, node: &FunFormBody) {
+    match node {
+        FunFormBody::Empty(value)
+        | FunFormBody::Panic(value)
+        | FunFormBody::Atomic(value)
+        | FunFormBody::ValueSymbol(value)
+        | FunFormBody::ValuePathSymbol(value) => visitor.visit_simple_value(value),
+        FunFormBody::MapForm(value) => visitor.visit_map_form(value),
+        FunFormBody::VecForm(value) => visitor.visit_vec_form(value),
+        FunFormBody::ArrForm(value) => visitor.visit_arr_form(value),
+        FunFormBody::ListForm(value) => visitor.visit_list_form(value),
+        FunFormBody::PairForm(value) => visitor.visit_pair_form(value),
+        FunFormBody::AppForm(value) => visitor.visit_app_form(value),
+        FunFormBody::MemForm(value) => visitor.visit_mem_form(value),
+        FunFormBody::LinearForm(value) => visitor.visit_linear_form(value),
+        FunFormBody::CastForm(value) => visitor.visit_cast_form(value),
+        FunFormBody::LetForm(value) => visitor.visit_let_form(value),
+        FunFormBody::CaseForm(value) => visitor.visit_case_form(value),
+        FunFormBody::FunForm(value) => visitor.visit_fun_form(value),
+    }
+}
+
+pub fn walk_let_form<V: Visitor + ?Sized>(visitor: &mut V, node: &LetForm) {
+    for item in node.entries.iter() {
+        visitor.visit_let_form_entry(item);
+    }
+
+    visitor.visit_app_form_value(&node.value);
+}
+
+pub fn walk_let_form_entry<V: Visitor + ?Sized>(visitor: &mut V, node: &LetFormEntry) {
+    match node {
+        LetFormEntry::Empty(value) => visitor.visit_simple_value(value),
+        LetFormEntry::ImportForm(value) => visitor.visit_import_form(value),
+        LetFormEntry::AttrsForm(value) => visitor.visit_attrs_form(value),
+        LetFormEntry::TypeForm(value) => visitor.visit_type_form(value),
+        LetFormEntry::SigForm(value) => visitor.visit_sig_form(value),
+        LetFormEntry::ValForm(value) => visitor.visit_val_form(value),
+    }
+}
+
+pub fn walk_app_form<V: Visitor + ?Sized>(visitor: &mut V, node: &AppForm) {
+    visitor.visit_simple_value(&node.name);
+
+    for item in node.variables.iter() {
+        visitor.visit_app_form_value(item);
+    }
+}
+
+pub fn walk_app_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &AppFormValue) {
+    match node {
+        AppFormValue::Ignore(value)
+        | AppFormValue::Empty(value)
+        | AppFormValue::Panic(value)
+        | AppFormValue::Atomic(value)
+        | AppFormValue::ValueSymbol(value)
+        | AppFormValue::ValuePathSymbol(value) => visitor.visit_simple_value(value),
+        AppFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        AppFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        AppFormValue::LetForm(value) => visitor.visit_let_form(value),
+        AppFormValue::CaseForm(value) => visitor.visit_case_form(value),
+        AppFormValue::AppForm(value) => visitor.visit_app_form(value),
+        AppFormValue::MemForm(value) => visitor.visit_mem_form(value),
+        AppFormValue::LinearForm(value) => visitor.visit_linear_form(value),
+        AppFormValue::CastForm(value) => visitor.visit_cast_form(value),
+    }
+}
+
+pub fn walk_case_form<V: Visitor + ?Sized>(visitor: &mut V, node: &CaseForm) {
+    visitor.visit_case_form_variable(&node.variable);
+
+    for item in node.matches.iter() {
+        visitor.visit_case_form_match(item);
+    }
+}
+
+pub fn walk_case_form_variable<V: Visitor + ?Sized>(visitor: &mut V, node: &CaseFormVariable) {
+    match node {
+        CaseFormVariable::Empty(value)
+        | CaseFormVariable::Atomic(value)
+        | CaseFormVariable::ValueSymbol(value) => visitor.visit_simple_value(value),
+        CaseFormVariable::AppForm(value) => visitor.visit_app_form(value),
+        CaseFormVariable::MemForm(value) => visitor.visit_mem_form(value),
+        CaseFormVariable::LinearForm(value) => visitor.visit_linear_form(value),
+        CaseFormVariable::CastForm(value) => visitor.visit_cast_form(value),
+        CaseFormVariable::LetForm(value) => visitor.visit_let_form(value),
+        CaseFormVariable::CaseForm(value) => visitor.visit_case_form(value),
+    }
+}
+
+pub fn walk_case_form_match<V: Visitor + ?Sized>(visitor: &mut V, node: &CaseFormMatch) {
+    visitor.visit_case_form_match_case(&node.case);
+    visitor.visit_case_form_match_action(&node.action);
+}
+
+pub fn walk_case_form_match_case<V: Visitor + ?Sized>(visitor: &mut V, node: &CaseFormMatchCase) {
+    match node {
+        CaseFormMatchCase::Empty(value)
+        | CaseFormMatchCase::Atomic(value)
+        | CaseFormMatchCase::ValueKeyword(value)
+        | CaseFormMatchCase::TypeKeyword(value)
+        | CaseFormMatchCase::TypeSymbol(value)
+        | CaseFormMatchCase::ValueSymbol(value)
+        | CaseFormMatchCase::TypePathSymbol(value)
+        | CaseFormMatchCase::ValuePathSymbol(value) => visitor.visit_simple_value(value),
+    }
+}
+
+pub fn walk_case_form_match_action<V: Visitor + ?Sized>(
+    visitor: &mut V,
+    node: &CaseFormMatchAction,
+) {
+    match node {
+        CaseFormMatchAction::Ignore(value)
+        | CaseFormMatchAction::Empty(value)
+        | CaseFormMatchAction::Panic(value)
+        | CaseFormMatchAction::Atomic(value)
+        | CaseFormMatchAction::ValueKeyword(value)
+        | CaseFormMatchAction::ValueSymbol(value)
+        | CaseFormMatchAction::ValuePathSymbol(value) => visitor.visit_simple_value(value),
+        CaseFormMatchAction::PairForm(value) => visitor.visit_pair_form(value),
+        CaseFormMatchAction::FunForm(value) => visitor.visit_fun_form(value),
+        CaseFormMatchAction::LetForm(value) => visitor.visit_let_form(value),
+    }
+}
+
+pub fn walk_pair_form<V: Visitor + ?Sized>(visitor: &mut V, node: &PairForm) {
+    visitor.visit_pair_form_value(&node.first);
+    visitor.visit_pair_form_value(&node.second);
+}
+
+pub fn walk_pair_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &PairFormValue) {
+    match node {
+        PairFormValue::Ignore(value)
+        | PairFormValue::Empty(value)
+        | PairFormValue::Panic(value)
+        | PairFormValue::Atomic(value)
+        | PairFormValue::ValueKeyword(value)
+        | PairFormValue::TypeKeyword(value)
+        | PairFormValue::ValueSymbol(value)
+        | PairFormValue::TypeSymbol(value)
+        | PairFormValue::ValuePathSymbol(value)
+        | PairFormValue::TypePathSymbol(value) => visitor.visit_simple_value(value),
+        PairFormValue::Type(value) => visitor.visit_type(value),
+        PairFormValue::MapForm(value) => visitor.visit_map_form(value),
+        PairFormValue::VecForm(value) => visitor.visit_vec_form(value),
+        PairFormValue::ArrForm(value) => visitor.visit_arr_form(value),
+        PairFormValue::ListForm(value) => visitor.visit_list_form(value),
+        PairFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        PairFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        PairFormValue::CaseForm(value) => visitor.visit_case_form(value),
+        PairFormValue::LetForm(value) => visitor.visit_let_form(value),
+        PairFormValue::AppForm(value) => visitor.visit_app_form(value),
+    }
+}
+
+pub fn walk_list_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ListForm) {
+    for item in node.values.iter() {
+        visitor.visit_list_form_value(item);
+    }
+}
+
+pub fn walk_list_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &ListFormValue) {
+    match node {
+        ListFormValue::Ignore(value)
+        | ListFormValue::Empty(value)
+        | ListFormValue::Panic(value)
+        | ListFormValue::Atomic(value)
+        | ListFormValue::ValueKeyword(value)
+        | ListFormValue::TypeKeyword(value)
+        | ListFormValue::ValueSymbol(value)
+        | ListFormValue::TypeSymbol(value)
+        | ListFormValue::ValuePathSymbol(value)
+        | ListFormValue::TypePathSymbol(value) => visitor.visit_simple_value(value),
+        ListFormValue::Type(value) => visitor.visit_type(value),
+        ListFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        ListFormValue::CaseForm(value) => visitor.visit_case_form(value),
+        ListFormValue::LetForm(value) => visitor.visit_let_form(value),
+        ListFormValue::AppForm(value) => visitor.visit_app_form(value),
+        ListFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        ListFormValue::ArrForm(value) => visitor.visit_arr_form(value),
+        ListFormValue::VecForm(value) => visitor.visit_vec_form(value),
+        ListFormValue::MapForm(value) => visitor.visit_map_form(value),
+        ListFormValue::ListForm(value) => visitor.visit_list_form(value),
+    }
+}
+
+pub fn walk_arr_form<V: Visitor + ?Sized>(visitor: &mut V, node: &ArrForm) {
+    for item in node.values.iter() {
+        visitor.visit_arr_form_value(item);
+    }
+}
+
+pub fn walk_arr_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &ArrFormValue) {
+    match node {
+        ArrFormValue::Ignore(value)
+        | ArrFormValue::Empty(value)
+        | ArrFormValue::Panic(value)
+        | ArrFormValue::Atomic(value)
+        | ArrFormValue::ValueKeyword(value)
+        | ArrFormValue::TypeKeyword(value)
+        | ArrFormValue::ValueSymbol(value)
+        | ArrFormValue::TypeSymbol(value)
+        | ArrFormValue::ValuePathSymbol(value)
+        | ArrFormValue::TypePathSymbol(value) => visitor.visit_simple_value(value),
+        ArrFormValue::Type(value) => visitor.visit_type(value),
+        ArrFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        ArrFormValue::CaseForm(value) => visitor.visit_case_form(value),
+        ArrFormValue::LetForm(value) => visitor.visit_let_form(value),
+        ArrFormValue::AppForm(value) => visitor.visit_app_form(value),
+        ArrFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        ArrFormValue::ListForm(value) => visitor.visit_list_form(value),
+        ArrFormValue::VecForm(value) => visitor.visit_vec_form(value),
+        ArrFormValue::MapForm(value) => visitor.visit_map_form(value),
+        ArrFormValue::ArrForm(value) => visitor.visit_arr_form(value),
+    }
+}
+
+pub fn walk_vec_form<V: Visitor + ?Sized>(visitor: &mut V, node: &VecForm) {
+    for item in node.values.iter() {
+        visitor.visit_vec_form_value(item);
+    }
+}
+
+pub fn walk_vec_form_value<V: Visitor + ?Sized>(visitor: &mut V, node: &VecFormValue) {
+    match node {
+        VecFormValue::Ignore(value)
+        | VecFormValue::Empty(value)
+        | VecFormValue::Panic(value)
+        | VecFormValue::Atomic(value)
+        | VecFormValue::ValueKeyword(value)
+        | VecFormValue::TypeKeyword(value)
+        | VecFormValue::ValueSymbol(value)
+        | VecFormValue::TypeSymbol(value)
+        | VecFormValue::ValuePathSymbol(value)
+        | VecFormValue::TypePathSymbol(value) => visitor.visit_simple_value(value),
+        VecFormValue::Type(value) => visitor.visit_type(value),
+        VecFormValue::FunForm(value) => visitor.visit_fun_form(value),
+        VecFormValue::CaseForm(value) => visitor.visit_case_form(value),
+        VecFormValue::LetForm(value) => visitor.visit_let_form(value),
+        VecFormValue::AppForm(value) => visitor.visit_app_form(value),
+        VecFormValue::PairForm(value) => visitor.visit_pair_form(value),
+        VecFormValue::ListForm(value) => visitor.visit_list_form(value),
+        VecFormValue::ArrForm(value) => visitor.visit_arr_form(value),
+        VecFormValue::MapForm(value) => visitor.visit_map_form(value),
+        VecFormValue::VecForm(value) => visitor.visit_vec_form(value),
+    }
+}
+
+pub fn walk_map_form<V: Visitor + ?Sized>(visitor: &mut V, node: &MapForm) {
+    for item in node.entries.iter() {
+        visitor.visit_map_form_entry(item);
+    }
+}
+
+pub fn walk_map_form_entry<V: Visitor + ?Sized>(visitor: &mut V, node: &MapFormEntry) {
+    match node {
+        MapFormEntry::Ignore(value) | MapFormEntry::Empty(value) => {
+            visitor.visit_simple_value(value)
+        }
+        MapFormEntry::PairForm(value) => visitor.visit_pair_form(value),
+    }
+}
+
+pub fn walk_mem_form<V: Visitor + ?Sized>(visitor: &mut V, node: &MemForm) {
+    for item in node.values.iter() {
+        visitor.visit_app_form_value(item);
+    }
+}
+
+pub fn walk_linear_form<V: Visitor + ?Sized>(visitor: &mut V, node: &LinearForm) {
+    visitor.visit_app_form_value(&node.value);
+}
+
+pub fn walk_cast_form<V: Visitor + ?Sized>(visitor: &mut V, node: &CastForm) {
+    visitor.visit_type(&node.target);
+    visitor.visit_app_form_value(&node.value);
+}
+
+pub fn walk_type<V: Visitor + ?Sized>(visitor: &mut V, node: &Type) {
+    match node {
+        Type::Simple(value) => visitor.visit_simple_type(value),
+        Type::Enum(value) => visitor.visit_enum_type(value),
+        Type::Pair(value) => visitor.visit_pair_type(value),
+        Type::List(value) => visitor.visit_list_type(value),
+        Type::Arr(value) => visitor.visit_arr_type(value),
+        Type::Vec(value) => visitor.visit_vec_type(value),
+        Type::Map(value) => visitor.visit_map_type(value),
+        Type::Fun(value) => visitor.visit_fun_type(value),
+    }
+}
+
+pub fn walk_simple_type<V: Visitor + ?Sized>(visitor: &mut V, node: &SimpleType) {
+    match node {
+        SimpleType::Builtin(value)
+        | SimpleType::Ignore(value)
+        | SimpleType::Empty(value)
+        | SimpleType::Atomic(value)
+        | SimpleType::UInt(value)
+        | SimpleType::Int(value)
+        | SimpleType::Float(value)
+        | SimpleType::Size(value)
+        | SimpleType::Pointer(value)
+        | SimpleType::Ref(value)
+        | SimpleType::Char(value)
+        | SimpleType::String(value)
+        | SimpleType::Mem(value)
+        | SimpleType::Path(value)
+        | SimpleType::IO(value)
+        | SimpleType::Ctx(value)
+        | SimpleType::Type(value)
+        | SimpleType::Symbol(value)
+        | SimpleType::PathSymbol(value) => visitor.visit_simple_value(value),
+    }
+}
+
+pub fn walk_enum_type<V: Visitor + ?Sized>(visitor: &mut V, node: &EnumType) {
+    for item in node.elements.iter() {
+        visitor.visit_type(item);
+    }
+}
+
+pub fn walk_pair_type<V: Visitor + ?Sized>(visitor: &mut V, node: &PairType) {
+    visitor.visit_type(&node.first);
+    visitor.visit_type(&node.second);
+}
+
+pub fn walk_list_type<V: Visitor + ?Sized>(visitor: &mut V, node: &ListType) {
+    for item in node.elements.iter() {
+        visitor.visit_type(item);
+    }
+}
+
+pub fn walk_arr_type<V: Visitor + ?Sized>(visitor: &mut V, node: &ArrType) {
+    for item in node.elements.iter() {
+        visitor.visit_type(item);
+    }
+}
+
+pub fn walk_vec_type<V: Visitor + ?Sized>(visitor: &mut V, node: &VecType) {
+    for item in node.elements.iter() {
+        visitor.visit_type(item);
+    }
+}
+
+pub fn walk_map_type<V: Visitor + ?Sized>(visitor: &mut V, node: &MapType) {
+    for item in node.entries.iter() {
+        visitor.visit_pair_type(item);
+    }
+}
+
+pub fn walk_fun_type<V: Visitor + ?Sized>(visitor: &mut V, node: &FunType) {
+    for item in node.parameters.iter() {
+        visitor.visit_type(item);
+    }
+
+    visitor.visit_type(&node.body);
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn visit_fun_form_parameters() {
+        use super::{walk_fun_form_parameter, Visitor};
+        use crate::value::forms::fun_form::{FunForm, FunFormParameter};
+        use crate::value::SimpleValue;
+
+        #[derive(Default)]
+        struct Parameters {
+            in_parameter: bool,
+            params: Vec<String>,
+        }
+
+        impl Visitor for Parameters {
+            fn visit_fun_form_parameter(&mut self, node: &FunFormParameter) {
+                self.in_parameter = true;
+                walk_fun_form_parameter(self, node);
+                self.in_parameter = false;
+            }
+
+            fn visit_simple_value(&mut self, node: &SimpleValue) {
+                if self.in_parameter && matches!(node, SimpleValue::ValueSymbol(_)) {
+                    self.params.push(node.to_string());
+                }
+            }
+        }
+
+        let cases = [
+            "(fun () ())",
+            "(fun a b c (f a b c))",
+            "(fun io (pair nameA nameB) (printf io \"{} {}\" (pair nameA nameB)))",
+            "(fun a (fun b (pair a b)))",
+            "(fun (list a b) (vec c d) (map (pair k v)) (f a b c d k v))",
+        ];
+
+        for s in cases.iter() {
+            let form = FunForm::from_str(s).unwrap();
+
+            let mut visitor = Parameters::default();
+            visitor.visit_fun_form(&form);
+
+            let expected = form
+                .all_parameters()
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>();
+
+            assert_eq!(visitor.params, expected);
+        }
+    }
+
+    #[test]
+    fn visit_program() {
+        use super::Visitor;
+        use crate::program::Program;
+        use crate::value::forms::case_form::CaseFormMatch;
+        use crate::value::forms::fun_form::FunForm;
+        use crate::value::types::SimpleType;
+
+        #[derive(Default)]
+        struct Counter {
+            funs: usize,
+            matches: usize,
+            type_symbols: Vec<String>,
+        }
+
+        impl Visitor for Counter {
+            fn visit_fun_form(&mut self, node: &FunForm) {
+                self.funs += 1;
+                super::walk_fun_form(self, node);
+            }
+
+            fn visit_case_form_match(&mut self, node: &CaseFormMatch) {
+                self.matches += 1;
+                super::walk_case_form_match(self, node);
+            }
+
+            fn visit_simple_type(&mut self, node: &SimpleType) {
+                if let SimpleType::Symbol(value) = node {
+                    self.type_symbols.push(value.to_string());
+                }
+            }
+        }
+
+        let program = Program::from_file("./examples/result.at").unwrap();
+
+        let mut counter = Counter::default();
+        counter.visit_program(&program);
+
+        assert_eq!(counter.funs, 2);
+        assert_eq!(counter.matches, 2);
+        assert_eq!(
+            counter.type_symbols,
+            ["T", "E", "T", "E", "Result", "T", "StringError"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+}
@@ -0,0 +1,806 @@
+use crate::program::Program;
+use crate::value::forms::app_form::{AppForm, AppFormValue};
+use crate::value::forms::arr_form::{ArrForm, ArrFormValue};
+use crate::value::forms::attrs_form::{AttrsForm, AttrsFormValue};
+use crate::value::forms::block_form::{BlockForm, BlockFormEntry};
+use crate::value::forms::case_form::{
+    CaseForm, CaseFormMatch, CaseFormMatchAction, CaseFormMatchCase, CaseFormVariable,
+};
+use crate::value::forms::cast_form::CastForm;
+use crate::value::forms::error_form::ErrorForm;
+use crate::value::forms::export_form::{ExportForm, ExportFormDef};
+use crate::value::forms::fun_form::{FunForm, FunFormBody, FunFormParameter};
+use crate::value::forms::import_form::{ImportForm, ImportFormDef};
+use crate::value::forms::let_form::{LetForm, LetFormEntry};
+use crate::value::forms::linear_form::LinearForm;
+use crate::value::forms::list_form::{ListForm, ListFormValue};
+use crate::value::forms::map_form::{MapForm, MapFormEntry};
+use crate::value::forms::mem_form::MemForm;
+use crate::value::forms::module_form::{ModuleForm, ModuleFormBlock};
+use crate::value::forms::pair_form::{PairForm, PairFormValue};
+use crate::value::forms::sig_form::SigForm;
+use crate::value::forms::type_form::TypeForm;
+use crate::value::forms::val_form::{ValForm, ValFormValue};
+use crate::value::forms::vec_form::{VecForm, VecFormValue};
+use crate::value::simple_value::SimpleValue;
+use crate::value::types::{
+    ArrType, EnumType, FunType, ListType, MapType, PairType, SimpleType, Type, VecType,
+};
+
+pub trait VisitorMut {
+    fn visit_program_mut(&mut self, node: &mut Program) {
+        walk_program_mut(self, node);
+    }
+
+    fn visit_module_form_mut(&mut self, node: &mut ModuleForm) {
+        walk_module_form_mut(self, node);
+    }
+
+    fn visit_module_form_block_mut(&mut self, node: &mut ModuleFormBlock) {
+        walk_module_form_block_mut(self, node);
+    }
+
+    fn visit_block_form_mut(&mut self, node: &mut BlockForm) {
+        walk_block_form_mut(self, node);
+    }
+
+    fn visit_block_form_entry_mut(&mut self, node: &mut BlockFormEntry) {
+        walk_block_form_entry_mut(self, node);
+    }
+
+    fn visit_import_form_mut(&mut self, node: &mut ImportForm) {
+        walk_import_form_mut(self, node);
+    }
+
+    fn visit_import_form_def_mut(&mut self, node: &mut ImportFormDef) {
+        walk_import_form_def_mut(self, node);
+    }
+
+    fn visit_export_form_mut(&mut self, node: &mut ExportForm) {
+        walk_export_form_mut(self, node);
+    }
+
+    fn visit_export_form_def_mut(&mut self, node: &mut ExportFormDef) {
+        walk_export_form_def_mut(self, node);
+    }
+
+    fn visit_attrs_form_mut(&mut self, node: &mut AttrsForm) {
+        walk_attrs_form_mut(self, node);
+    }
+
+    fn visit_attrs_form_value_mut(&mut self, node: &mut AttrsFormValue) {
+        walk_attrs_form_value_mut(self, node);
+    }
+
+    fn visit_type_form_mut(&mut self, node: &mut TypeForm) {
+        walk_type_form_mut(self, node);
+    }
+
+    fn visit_sig_form_mut(&mut self, node: &mut SigForm) {
+        walk_sig_form_mut(self, node);
+    }
+
+    fn visit_val_form_mut(&mut self, node: &mut ValForm) {
+        walk_val_form_mut(self, node);
+    }
+
+    fn visit_val_form_value_mut(&mut self, node: &mut ValFormValue) {
+        walk_val_form_value_mut(self, node);
+    }
+
+    fn visit_error_form_mut(&mut self, node: &mut ErrorForm) {
+        walk_error_form_mut(self, node);
+    }
+
+    fn visit_fun_form_mut(&mut self, node: &mut FunForm) {
+        walk_fun_form_mut(self, node);
+    }
+
+    fn visit_fun_form_parameter_mut(&mut self, node: &mut FunFormParameter) {
+        walk_fun_form_parameter_mut(self, node);
+    }
+
+    fn visit_fun_form_body_mut(&mut self, node: &mut FunFormBody) {
+        walk_fun_form_body_mut(self, node);
+    }
+
+    fn visit_let_form_mut(&mut self, node: &mut LetForm) {
+        walk_let_form_mut(self, node);
+    }
+
+    fn visit_let_form_entry_mut(&mut self, node: &mut LetFormEntry) {
+        walk_let_form_entry_mut(self, node);
+    }
+
+    fn visit_app_form_mut(&mut self, node: &mut AppForm) {
+        walk_app_form_mut(self, node);
+    }
+
+    fn visit_app_form_value_mut(&mut self, node: &mut AppFormValue) {
+        walk_app_form_value_mut(self, node);
+    }
+
+    fn visit_case_form_mut(&mut self, node: &mut CaseForm) {
+        walk_case_form_mut(self, node);
+    }
+
+    fn visit_case_form_variable_mut(&mut self, node: &mut CaseFormVariable) {
+        walk_case_form_variable_mut(self, node);
+    }
+
+    fn visit_case_form_match_mut(&mut self, node: &mut CaseFormMatch) {
+        walk_case_form_match_mut(self, node);
+    }
+
+    fn visit_case_form_match_case_mut(&mut self, node: &mut CaseFormMatchCase) {
+        walk_case_form_match_case_mut(self, node);
+    }
+
+    fn visit_case_form_match_action_mut(&mut self, node: &mut CaseFormMatchAction) {
+        walk_case_form_match_action_mut(self, node);
+    }
+
+    fn visit_pair_form_mut(&mut self, node: &mut PairForm) {
+        walk_pair_form_mut(self, node);
+    }
+
+    fn visit_pair_form_value_mut(&mut self, node: &mut PairFormValue) {
+        walk_pair_form_value_mut(self, node);
+    }
+
+    fn visit_list_form_mut(&mut self, node: &mut ListForm) {
+        walk_list_form_mut(self, node);
+    }
+
+    fn visit_list_form_value_mut(&mut self, node: &mut ListFormValue) {
+        walk_list_form_value_mut(self, node);
+    }
+
+    fn visit_arr_form_mut(&mut self, node: &mut ArrForm) {
+        walk_arr_form_mut(self, node);
+    }
+
+    fn visit_arr_form_value_mut(&mut self, node: &mut ArrFormValue) {
+        walk_arr_form_value_mut(self, node);
+    }
+
+    fn visit_vec_form_mut(&mut self, node: &mut VecForm) {
+        walk_vec_form_mut(self, node);
+    }
+
+    fn visit_vec_form_value_mut(&mut self, node: &mut VecFormValue) {
+        walk_vec_form_value_mut(self, node);
+    }
+
+    fn visit_map_form_mut(&mut self, node: &mut MapForm) {
+        walk_map_form_mut(self, node);
+    }
+
+    fn visit_map_form_entry_mut(&mut self, node: &mut MapFormEntry) {
+        walk_map_form_entry_mut(self, node);
+    }
+
+    fn visit_mem_form_mut(&mut self, node: &mut MemForm) {
+        walk_mem_form_mut(self, node);
+    }
+
+    fn visit_linear_form_mut(&mut self, node: &mut LinearForm) {
+        walk_linear_form_mut(self, node);
+    }
+
+    fn visit_cast_form_mut(&mut self, node: &mut CastForm) {
+        walk_cast_form_mut(self, node);
+    }
+
+    fn visit_type_mut(&mut self, node: &mut Type) {
+        walk_type_mut(self, node);
+    }
+
+    fn visit_simple_type_mut(&mut self, node: &mut SimpleType) {
+        walk_simple_type_mut(self, node);
+    }
+
+    fn visit_enum_type_mut(&mut self, node: &mut EnumType) {
+        walk_enum_type_mut(self, node);
+    }
+
+    fn visit_pair_type_mut(&mut self, node: &mut PairType) {
+        walk_pair_type_mut(self, node);
+    }
+
+    fn visit_list_type_mut(&mut self, node: &mut ListType) {
+        walk_list_type_mut(self, node);
+    }
+
+    fn visit_arr_type_mut(&mut self, node: &mut ArrType) {
+        walk_arr_type_mut(self, node);
+    }
+
+    fn visit_vec_type_mut(&mut self, node: &mut VecType) {
+        walk_vec_type_mut(self, node);
+    }
+
+    fn visit_map_type_mut(&mut self, node: &mut MapType) {
+        walk_map_type_mut(self, node);
+    }
+
+    fn visit_fun_type_mut(&mut self, node: &mut FunType) {
+        walk_fun_type_mut(self, node);
+    }
+
+    fn visit_simple_value_mut(&mut self, _node: &mut SimpleValue) {}
+}
+
+pub fn walk_program_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Program) {
+    for item in node.modules.iter_mut() {
+        visitor.visit_module_form_mut(item);
+    }
+}
+
+pub fn walk_module_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ModuleForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+
+    for item in node.type_parameters.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+
+    visitor.visit_module_form_block_mut(&mut node.block);
+}
+
+pub fn walk_module_form_block_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut ModuleFormBlock,
+) {
+    match node {
+        ModuleFormBlock::Empty(value) => visitor.visit_simple_value_mut(value),
+        ModuleFormBlock::Form(value) => visitor.visit_block_form_mut(value),
+    }
+}
+
+pub fn walk_block_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut BlockForm) {
+    for item in node.entries.iter_mut() {
+        visitor.visit_block_form_entry_mut(item);
+    }
+}
+
+pub fn walk_block_form_entry_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut BlockFormEntry,
+) {
+    match node {
+        BlockFormEntry::Empty(value) => visitor.visit_simple_value_mut(value),
+        BlockFormEntry::ImportForm(value) => visitor.visit_import_form_mut(value),
+        BlockFormEntry::ExportForm(value) => visitor.visit_export_form_mut(value),
+        BlockFormEntry::AttrsForm(value) => visitor.visit_attrs_form_mut(value),
+        BlockFormEntry::TypeForm(value) => visitor.visit_type_form_mut(value),
+        BlockFormEntry::SigForm(value) => visitor.visit_sig_form_mut(value),
+        BlockFormEntry::ValForm(value) => visitor.visit_val_form_mut(value),
+        BlockFormEntry::ErrorForm(value) => visitor.visit_error_form_mut(value),
+    }
+}
+
+pub fn walk_import_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ImportForm) {
+    visitor.visit_simple_value_mut(&mut node.module);
+
+    if let Some(item) = &mut node.qualifier {
+        visitor.visit_simple_value_mut(item);
+    }
+
+    for item in node.type_variables.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+
+    for item in node.defs.iter_mut() {
+        visitor.visit_import_form_def_mut(item);
+    }
+}
+
+pub fn walk_import_form_def_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ImportFormDef) {
+    match node {
+        ImportFormDef::Ignore(value)
+        | ImportFormDef::Empty(value)
+        | ImportFormDef::ValueSymbol(value)
+        | ImportFormDef::TypeSymbol(value) => visitor.visit_simple_value_mut(value),
+    }
+}
+
+pub fn walk_export_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ExportForm) {
+    for item in node.defs.iter_mut() {
+        visitor.visit_export_form_def_mut(item);
+    }
+}
+
+pub fn walk_export_form_def_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ExportFormDef) {
+    match node {
+        ExportFormDef::Empty(value)
+        | ExportFormDef::ValueSymbol(value)
+        | ExportFormDef::TypeSymbol(value) => visitor.visit_simple_value_mut(value),
+    }
+}
+
+pub fn walk_attrs_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AttrsForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+
+    for item in node.values.iter_mut() {
+        visitor.visit_attrs_form_value_mut(item);
+    }
+}
+
+pub fn walk_attrs_form_value_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut AttrsFormValue,
+) {
+    match node {
+        AttrsFormValue::Empty(value)
+        | AttrsFormValue::Panic(value)
+        | AttrsFormValue::Atomic(value)
+        | AttrsFormValue::ValueSymbol(value)
+        | AttrsFormValue::TypeSymbol(value)
+        | AttrsFormValue::ValuePathSymbol(value)
+        | AttrsFormValue::TypePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        AttrsFormValue::Map(value) => visitor.visit_map_form_mut(value),
+    }
+}
+
+pub fn walk_type_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut TypeForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+    visitor.visit_type_mut(&mut node.value);
+}
+
+pub fn walk_sig_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut SigForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+    visitor.visit_type_mut(&mut node.value);
+}
+
+pub fn walk_val_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ValForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+    visitor.visit_val_form_value_mut(&mut node.value);
+}
+
+pub fn walk_val_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ValFormValue) {
+    match node {
+        ValFormValue::Empty(value)
+        | ValFormValue::Panic(value)
+        | ValFormValue::Atomic(value)
+        | ValFormValue::Builtin(value)
+        | ValFormValue::ValueSymbol(value) => visitor.visit_simple_value_mut(value),
+        ValFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        ValFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        ValFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        ValFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+        ValFormValue::MemForm(value) => visitor.visit_mem_form_mut(value),
+        ValFormValue::LinearForm(value) => visitor.visit_linear_form_mut(value),
+        ValFormValue::CastForm(value) => visitor.visit_cast_form_mut(value),
+        ValFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+    }
+}
+
+pub fn walk_error_form_mut<V: VisitorMut + ?Sized>(_visitor: &mut V, _node: &mut ErrorForm) {}
+
+pub fn walk_fun_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FunForm) {
+    for item in node.parameters.iter_mut() {
+        visitor.visit_fun_form_parameter_mut(item);
+    }
+
+    visitor.visit_fun_form_body_mut(&mut node.body);
+}
+
+pub fn walk_fun_form_parameter_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut FunFormParameter,
+) {
+    match node {
+        FunFormParameter::Empty(value) | FunFormParameter::ValueSymbol(value) => {
+            visitor.visit_simple_value_mut(value)
+        }
+        FunFormParameter::MapForm(value) => visitor.visit_map_form_mut(value),
+        FunFormParameter::VecForm(value) => visitor.visit_vec_form_mut(value),
+        FunFormParameter::ArrForm(value) => visitor.visit_arr_form_mut(value),
+        FunFormParameter::ListForm(value) => visitor.visit_list_form_mut(value),
+        FunFormParameter::PairForm(value) => visitor.visit_pair_form_mut(value),
+    }
+}
+
+pub fn walk_fun_form_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FunFormBody) {
+    match node {
+        FunFormBody::Empty(value)
+        | FunFormBody::Panic(value)
+        | FunFormBody::Atomic(value)
+        | FunFormBody::ValueSymbol(value)
+        | FunFormBody::ValuePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        FunFormBody::MapForm(value) => visitor.visit_map_form_mut(value),
+        FunFormBody::VecForm(value) => visitor.visit_vec_form_mut(value),
+        FunFormBody::ArrForm(value) => visitor.visit_arr_form_mut(value),
+        FunFormBody::ListForm(value) => visitor.visit_list_form_mut(value),
+        FunFormBody::PairForm(value) => visitor.visit_pair_form_mut(value),
+        FunFormBody::AppForm(value) => visitor.visit_app_form_mut(value),
+        FunFormBody::MemForm(value) => visitor.visit_mem_form_mut(value),
+        FunFormBody::LinearForm(value) => visitor.visit_linear_form_mut(value),
+        FunFormBody::CastForm(value) => visitor.visit_cast_form_mut(value),
+        FunFormBody::LetForm(value) => visitor.visit_let_form_mut(value),
+        FunFormBody::CaseForm(value) => visitor.visit_case_form_mut(value),
+        FunFormBody::FunForm(value) => visitor.visit_fun_form_mut(value),
+    }
+}
+
+pub fn walk_let_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut LetForm) {
+    for item in node.entries.iter_mut() {
+        visitor.visit_let_form_entry_mut(item);
+    }
+
+    visitor.visit_app_form_value_mut(&mut node.value);
+}
+
+pub fn walk_let_form_entry_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut LetFormEntry) {
+    match node {
+        LetFormEntry::Empty(value) => visitor.visit_simple_value_mut(value),
+        LetFormEntry::ImportForm(value) => visitor.visit_import_form_mut(value),
+        LetFormEntry::AttrsForm(value) => visitor.visit_attrs_form_mut(value),
+        LetFormEntry::TypeForm(value) => visitor.visit_type_form_mut(value),
+        LetFormEntry::SigForm(value) => visitor.visit_sig_form_mut(value),
+        LetFormEntry::ValForm(value) => visitor.visit_val_form_mut(value),
+    }
+}
+
+pub fn walk_app_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AppForm) {
+    visitor.visit_simple_value_mut(&mut node.name);
+
+    for item in node.variables.iter_mut() {
+        visitor.visit_app_form_value_mut(item);
+    }
+}
+
+pub fn walk_app_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut AppFormValue) {
+    match node {
+        AppFormValue::Ignore(value)
+        | AppFormValue::Empty(value)
+        | AppFormValue::Panic(value)
+        | AppFormValue::Atomic(value)
+        | AppFormValue::ValueSymbol(value)
+        | AppFormValue::ValuePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        AppFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        AppFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        AppFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        AppFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+        AppFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+        AppFormValue::MemForm(value) => visitor.visit_mem_form_mut(value),
+        AppFormValue::LinearForm(value) => visitor.visit_linear_form_mut(value),
+        AppFormValue::CastForm(value) => visitor.visit_cast_form_mut(value),
+    }
+}
+
+pub fn walk_case_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut CaseForm) {
+    visitor.visit_case_form_variable_mut(&mut node.variable);
+
+    for item in node.matches.iter_mut() {
+        visitor.visit_case_form_match_mut(item);
+    }
+}
+
+pub fn walk_case_form_variable_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut CaseFormVariable,
+) {
+    match node {
+        CaseFormVariable::Empty(value)
+        | CaseFormVariable::Atomic(value)
+        | CaseFormVariable::ValueSymbol(value) => visitor.visit_simple_value_mut(value),
+        CaseFormVariable::AppForm(value) => visitor.visit_app_form_mut(value),
+        CaseFormVariable::MemForm(value) => visitor.visit_mem_form_mut(value),
+        CaseFormVariable::LinearForm(value) => visitor.visit_linear_form_mut(value),
+        CaseFormVariable::CastForm(value) => visitor.visit_cast_form_mut(value),
+        CaseFormVariable::LetForm(value) => visitor.visit_let_form_mut(value),
+        CaseFormVariable::CaseForm(value) => visitor.visit_case_form_mut(value),
+    }
+}
+
+pub fn walk_case_form_match_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut CaseFormMatch) {
+    visitor.visit_case_form_match_case_mut(&mut node.case);
+    visitor.visit_case_form_match_action_mut(&mut node.action);
+}
+
+pub fn walk_case_form_match_case_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut CaseFormMatchCase,
+) {
+    match node {
+        CaseFormMatchCase::Empty(value)
+        | CaseFormMatchCase::Atomic(value)
+        | CaseFormMatchCase::ValueKeyword(value)
+        | CaseFormMatchCase::TypeKeyword(value)
+        | CaseFormMatchCase::TypeSymbol(value)
+        | CaseFormMatchCase::ValueSymbol(value)
+        | CaseFormMatchCase::TypePathSymbol(value)
+        | CaseFormMatchCase::ValuePathSymbol(value) => visitor.visit_simple_value_mut(value),
+    }
+}
+
+pub fn walk_case_form_match_action_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    node: &mut CaseFormMatchAction,
+) {
+    match node {
+        CaseFormMatchAction::Ignore(value)
+        | CaseFormMatchAction::Empty(value)
+        | CaseFormMatchAction::Panic(value)
+        | CaseFormMatchAction::Atomic(value)
+        | CaseFormMatchAction::ValueKeyword(value)
+        | CaseFormMatchAction::ValueSymbol(value)
+        | CaseFormMatchAction::ValuePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        CaseFormMatchAction::PairForm(value) => visitor.visit_pair_form_mut(value),
+        CaseFormMatchAction::FunForm(value) => visitor.visit_fun_form_mut(value),
+        CaseFormMatchAction::LetForm(value) => visitor.visit_let_form_mut(value),
+    }
+}
+
+pub fn walk_pair_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut PairForm) {
+    visitor.visit_pair_form_value_mut(&mut node.first);
+    visitor.visit_pair_form_value_mut(&mut node.second);
+}
+
+pub fn walk_pair_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut PairFormValue) {
+    match node {
+        PairFormValue::Ignore(value)
+        | PairFormValue::Empty(value)
+        | PairFormValue::Panic(value)
+        | PairFormValue::Atomic(value)
+        | PairFormValue::ValueKeyword(value)
+        | PairFormValue::TypeKeyword(value)
+        | PairFormValue::ValueSymbol(value)
+        | PairFormValue::TypeSymbol(value)
+        | PairFormValue::ValuePathSymbol(value)
+        | PairFormValue::TypePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        PairFormValue::Type(value) => visitor.visit_type_mut(value),
+        PairFormValue::MapForm(value) => visitor.visit_map_form_mut(value),
+        PairFormValue::VecForm(value) => visitor.visit_vec_form_mut(value),
+        PairFormValue::ArrForm(value) => visitor.visit_arr_form_mut(value),
+        PairFormValue::ListForm(value) => visitor.visit_list_form_mut(value),
+        PairFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        PairFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        PairFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+        PairFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        PairFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+    }
+}
+
+pub fn walk_list_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ListForm) {
+    for item in node.values.iter_mut() {
+        visitor.visit_list_form_value_mut(item);
+    }
+}
+
+pub fn walk_list_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ListFormValue) {
+    match node {
+        ListFormValue::Ignore(value)
+        | ListFormValue::Empty(value)
+        | ListFormValue::Panic(value)
+        | ListFormValue::Atomic(value)
+        | ListFormValue::ValueKeyword(value)
+        | ListFormValue::TypeKeyword(value)
+        | ListFormValue::ValueSymbol(value)
+        | ListFormValue::TypeSymbol(value)
+        | ListFormValue::ValuePathSymbol(value)
+        | ListFormValue::TypePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        ListFormValue::Type(value) => visitor.visit_type_mut(value),
+        ListFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        ListFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+        ListFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        ListFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+        ListFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        ListFormValue::ArrForm(value) => visitor.visit_arr_form_mut(value),
+        ListFormValue::VecForm(value) => visitor.visit_vec_form_mut(value),
+        ListFormValue::MapForm(value) => visitor.visit_map_form_mut(value),
+        ListFormValue::ListForm(value) => visitor.visit_list_form_mut(value),
+    }
+}
+
+pub fn walk_arr_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ArrForm) {
+    for item in node.values.iter_mut() {
+        visitor.visit_arr_form_value_mut(item);
+    }
+}
+
+pub fn walk_arr_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ArrFormValue) {
+    match node {
+        ArrFormValue::Ignore(value)
+        | ArrFormValue::Empty(value)
+        | ArrFormValue::Panic(value)
+        | ArrFormValue::Atomic(value)
+        | ArrFormValue::ValueKeyword(value)
+        | ArrFormValue::TypeKeyword(value)
+        | ArrFormValue::ValueSymbol(value)
+        | ArrFormValue::TypeSymbol(value)
+        | ArrFormValue::ValuePathSymbol(value)
+        | ArrFormValue::TypePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        ArrFormValue::Type(value) => visitor.visit_type_mut(value),
+        ArrFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        ArrFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+        ArrFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        ArrFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+        ArrFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        ArrFormValue::ListForm(value) => visitor.visit_list_form_mut(value),
+        ArrFormValue::VecForm(value) => visitor.visit_vec_form_mut(value),
+        ArrFormValue::MapForm(value) => visitor.visit_map_form_mut(value),
+        ArrFormValue::ArrForm(value) => visitor.visit_arr_form_mut(value),
+    }
+}
+
+pub fn walk_vec_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut VecForm) {
+    for item in node.values.iter_mut() {
+        visitor.visit_vec_form_value_mut(item);
+    }
+}
+
+pub fn walk_vec_form_value_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut VecFormValue) {
+    match node {
+        VecFormValue::Ignore(value)
+        | VecFormValue::Empty(value)
+        | VecFormValue::Panic(value)
+        | VecFormValue::Atomic(value)
+        | VecFormValue::ValueKeyword(value)
+        | VecFormValue::TypeKeyword(value)
+        | VecFormValue::ValueSymbol(value)
+        | VecFormValue::TypeSymbol(value)
+        | VecFormValue::ValuePathSymbol(value)
+        | VecFormValue::TypePathSymbol(value) => visitor.visit_simple_value_mut(value),
+        VecFormValue::Type(value) => visitor.visit_type_mut(value),
+        VecFormValue::FunForm(value) => visitor.visit_fun_form_mut(value),
+        VecFormValue::CaseForm(value) => visitor.visit_case_form_mut(value),
+        VecFormValue::LetForm(value) => visitor.visit_let_form_mut(value),
+        VecFormValue::AppForm(value) => visitor.visit_app_form_mut(value),
+        VecFormValue::PairForm(value) => visitor.visit_pair_form_mut(value),
+        VecFormValue::ListForm(value) => visitor.visit_list_form_mut(value),
+        VecFormValue::ArrForm(value) => visitor.visit_arr_form_mut(value),
+        VecFormValue::MapForm(value) => visitor.visit_map_form_mut(value),
+        VecFormValue::VecForm(value) => visitor.visit_vec_form_mut(value),
+    }
+}
+
+pub fn walk_map_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MapForm) {
+    for item in node.entries.iter_mut() {
+        visitor.visit_map_form_entry_mut(item);
+    }
+}
+
+pub fn walk_map_form_entry_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MapFormEntry) {
+    match node {
+        MapFormEntry::Ignore(value) | MapFormEntry::Empty(value) => {
+            visitor.visit_simple_value_mut(value)
+        }
+        MapFormEntry::PairForm(value) => visitor.visit_pair_form_mut(value),
+    }
+}
+
+pub fn walk_mem_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MemForm) {
+    for item in node.values.iter_mut() {
+        visitor.visit_app_form_value_mut(item);
+    }
+}
+
+pub fn walk_linear_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut LinearForm) {
+    visitor.visit_app_form_value_mut(&mut node.value);
+}
+
+pub fn walk_cast_form_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut CastForm) {
+    visitor.visit_type_mut(&mut node.target);
+    visitor.visit_app_form_value_mut(&mut node.value);
+}
+
+pub fn walk_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Type) {
+    match node {
+        Type::Simple(value) => visitor.visit_simple_type_mut(value),
+        Type::Enum(value) => visitor.visit_enum_type_mut(value),
+        Type::Pair(value) => visitor.visit_pair_type_mut(value),
+        Type::List(value) => visitor.visit_list_type_mut(value),
+        Type::Arr(value) => visitor.visit_arr_type_mut(value),
+        Type::Vec(value) => visitor.visit_vec_type_mut(value),
+        Type::Map(value) => visitor.visit_map_type_mut(value),
+        Type::Fun(value) => visitor.visit_fun_type_mut(value),
+    }
+}
+
+pub fn walk_simple_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut SimpleType) {
+    match node {
+        SimpleType::Builtin(value)
+        | SimpleType::Ignore(value)
+        | SimpleType::Empty(value)
+        | SimpleType::Atomic(value)
+        | SimpleType::UInt(value)
+        | SimpleType::Int(value)
+        | SimpleType::Float(value)
+        | SimpleType::Size(value)
+        | SimpleType::Pointer(value)
+        | SimpleType::Ref(value)
+        | SimpleType::Char(value)
+        | SimpleType::String(value)
+        | SimpleType::Mem(value)
+        | SimpleType::Path(value)
+        | SimpleType::IO(value)
+        | SimpleType::Ctx(value)
+        | SimpleType::Type(value)
+        | SimpleType::Symbol(value)
+        | SimpleType::PathSymbol(value) => visitor.visit_simple_value_mut(value),
+    }
+}
+
+pub fn walk_enum_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut EnumType) {
+    for item in node.elements.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+}
+
+pub fn walk_pair_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut PairType) {
+    visitor.visit_type_mut(&mut node.first);
+    visitor.visit_type_mut(&mut node.second);
+}
+
+pub fn walk_list_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ListType) {
+    for item in node.elements.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+}
+
+pub fn walk_arr_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut ArrType) {
+    for item in node.elements.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+}
+
+pub fn walk_vec_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut VecType) {
+    for item in node.elements.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+}
+
+pub fn walk_map_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut MapType) {
+    for item in node.entries.iter_mut() {
+        visitor.visit_pair_type_mut(item);
+    }
+}
+
+pub fn walk_fun_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut FunType) {
+    for item in node.parameters.iter_mut() {
+        visitor.visit_type_mut(item);
+    }
+
+    visitor.visit_type_mut(&mut node.body);
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn visit_mut_rename() {
+        use super::VisitorMut;
+        use crate::value::forms::module_form::ModuleForm;
+        use crate::value::SimpleValue;
+
+        struct Rename {
+            from: String,
+            to: SimpleValue,
+        }
+
+        impl VisitorMut for Rename {
+            fn visit_simple_value_mut(&mut self, node: &mut SimpleValue) {
+                if matches!(node, SimpleValue::ValueSymbol(_)) && node.to_string() == self.from {
+                    *node = self.to.clone();
+                }
+            }
+        }
+
+        let s = "(module m (block (sig x UInt) (val x 1) (sig f (Fun UInt UInt)) (val f (fun y (math.add x y)))))";
+
+        let mut form = ModuleForm::from_str(s).unwrap();
+
+        let mut rename = Rename {
+            from: "x".into(),
+            to: SimpleValue::from_str("z").unwrap(),
+        };
+
+        rename.visit_module_form_mut(&mut form);
+
+        assert_eq!(
+            form.to_string(),
+            "(module m (block (sig z UInt) (val z 1) (sig f (Fun UInt UInt)) (val f (fun y (math.add z y)))))".to_string()
+        );
+    }
+}